@@ -1,39 +1,50 @@
-use ratatui::crossterm::event::{Event, KeyEvent};
-
-/// Actions to be done by some component or by the app if returned
-#[derive(Debug, Clone, PartialEq)]
-pub enum Action {
-    Noop,
-    Quit,
-    KeyEvent(KeyEvent),
-    OtherEvent(Event),
-    ChangeEditCommand,
-    ChangeSelectedTable,
-    NotifyCompletion,
-    Refresh,
-    RevertCommandSelection,
-    RevertToMain,
-    HighlightChanged,
-    SelectionChanged,
-    VeryLoudWrongBuzzer,
-}
-
-/// Error for unhandled actions
-#[derive(Debug, Clone)]
-pub struct UnhandledActionError {
-    action: Action,
-}
-
-impl UnhandledActionError {
-    pub fn new(action: Action) -> UnhandledActionError {
-        UnhandledActionError { action }
-    }
-}
-
-impl std::error::Error for UnhandledActionError {}
-
-impl std::fmt::Display for UnhandledActionError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Trying to handle unhandled event: {:?}", self.action)
-    }
-}
+use std::time::Duration;
+
+use ratatui::crossterm::event::{Event, KeyEvent};
+
+use crate::value::Value;
+
+/// Actions to be done by some component or by the app if returned
+#[derive(Debug, Clone, PartialEq)]
+pub enum Action {
+    Noop,
+    Quit,
+    KeyEvent(KeyEvent),
+    OtherEvent(Event),
+    ChangeEditCommand,
+    ChangeSelectedTable,
+    DeselectAll,
+    BeginFilter,
+    EndFilter,
+    NotifyCompletion,
+    OpenTable(String),
+    /// Opens the referenced table filtered to the rows whose column holds
+    /// the value
+    NavigateToRelation {
+        table: String,
+        column: String,
+        value: Value,
+    },
+    Refresh,
+    /// Opens the popup exporting the shown table to a CSV file
+    ExportCsv,
+    /// Opens the popup filtering the rows of the shown table
+    OpenFilter,
+    /// Sorts the shown table by the column at the index
+    SortColumn(usize),
+    RevertCommandSelection,
+    RevertToMain,
+    SelectInsertedRow(i64),
+    HighlightChanged,
+    SelectionChanged,
+    StatusMessage(String),
+    VeryLoudWrongBuzzer,
+    /// Shows the error message over the main section for a short while
+    ShowError(String),
+    /// Shows the message in a corner of the app until the duration passes
+    ShowNotification(String, Duration),
+    /// Widens the highlit column by the amount, or narrows it if negative
+    ResizeColumn(i16),
+    Undo,
+    Redo,
+}
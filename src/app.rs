@@ -1,166 +1,731 @@
-use std::error::Error;
-
-use ratatui::{
-    crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
-    layout::{Constraint, Direction, Layout},
-    prelude::Backend,
-    Frame, Terminal,
-};
-
-use crate::{
-    action::Action,
-    component::{database_component::DatabaseComp, selected_table::TableSelection, Component},
-    config::DEFAULT_APP_COLORS,
-};
-
-#[derive(Debug, Clone, PartialEq, Eq)]
-enum FocusArea {
-    Tables,
-    Main,
-}
-
-/// The collection of state which the app runs off of
-pub struct App {
-    database_component: DatabaseComp,
-    focusing: FocusArea,
-    tables_component: TableSelection,
-}
-
-impl App {
-    /// Constructs the default app state for the CLI
-    pub fn new() -> Result<App, Box<dyn Error>> {
-        let mut app = Self {
-            database_component: DatabaseComp::new("", 2, false)?,
-            focusing: FocusArea::Tables,
-            tables_component: TableSelection::new(),
-        };
-        if let Some(starting_table) = app.tables_component.selected() {
-            app.database_component.change_table_used(starting_table)?;
-        }
-        Ok(app)
-    }
-
-    /// Handles actions which get passed to the app.
-    /// Returns true if the app should quit, false otherwise
-    fn handle_actions(&mut self, actions: Vec<Action>) -> Result<bool, Box<dyn Error>> {
-        // loop over all actions in order
-        for action in actions {
-            match action {
-                Action::Quit => return Ok(true),
-                Action::ChangeSelectedTable => {
-                    if let Some(table) = self.tables_component.selected() {
-                        self.database_component.change_table_used(table)?;
-                    }
-                }
-                Action::Refresh => {
-                    self.database_component.refresh()?;
-                }
-                Action::VeryLoudWrongBuzzer => print!("\x07"),
-                _ => {}
-            }
-        }
-        Ok(false)
-    }
-
-    pub fn run<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> Result<(), Box<dyn Error>> {
-        loop {
-            // draw the thing
-            terminal.draw(|frame: &mut Frame| self.render(frame))?;
-
-            // poll keypress event with an ~1 frame at ~60fps timeout on
-            // encountering an event to prevent infinite blocking, allowing
-            // any moving components of the UI to progress
-            if !event::poll(std::time::Duration::from_millis(16))? {
-                continue;
-            }
-            if let Event::Key(key) = event::read()? {
-                // ignore key releases
-                if key.kind == KeyEventKind::Release {
-                    continue;
-                }
-                let actions = match key {
-                    KeyEvent {
-                        code: KeyCode::Right,
-                        modifiers: KeyModifiers::CONTROL,
-                        ..
-                    } => {
-                        // ctrl+right moves the focus to the next component
-                        match self.focusing {
-                            FocusArea::Tables => {
-                                self.database_component.focus_first();
-                                self.focusing = FocusArea::Main;
-                            }
-                            FocusArea::Main => {
-                                if self.database_component.next_focus() {
-                                    self.focusing = FocusArea::Tables;
-                                }
-                            }
-                        }
-                        vec![Action::Noop]
-                    }
-                    KeyEvent {
-                        code: KeyCode::Left,
-                        modifiers: KeyModifiers::CONTROL,
-                        ..
-                    } => {
-                        // ctrl+left moves the focus to the prev component
-                        match self.focusing {
-                            FocusArea::Tables => {
-                                self.database_component.focus_last();
-                                self.focusing = FocusArea::Main;
-                            }
-                            FocusArea::Main => {
-                                if self.database_component.prev_focus() {
-                                    self.focusing = FocusArea::Tables;
-                                }
-                            }
-                        }
-                        vec![Action::Noop]
-                    }
-                    _ => match self.focusing {
-                        // pass non-hardcoded key events to focused component
-                        FocusArea::Main => self
-                            .database_component
-                            .handle_event(Action::KeyEvent(key))?,
-                        FocusArea::Tables => {
-                            self.tables_component.handle_event(Action::KeyEvent(key))?
-                        }
-                    },
-                };
-                // handle the actions returned by the focused component
-                if self.handle_actions(actions)? {
-                    return Ok(());
-                }
-            }
-        }
-    }
-
-    fn render(&mut self, frame: &mut Frame) {
-        // use the top of the screen for the tables tabs
-        let [tables_rect, main_section_rect, ..] = *Layout::default()
-            .margin(0)
-            .direction(Direction::Horizontal)
-            .constraints([
-                Constraint::Percentage(15), // 15% width for the list of tables to edit
-                Constraint::Percentage(85), // 85% width for the rest
-            ])
-            .split(frame.area())
-        else {
-            panic!("Not enough size to create the necessary rects or something");
-        };
-
-        // determine the blocks used by each component depending on focus
-        let get_block = |focus: FocusArea| {
-            if self.focusing == focus {
-                DEFAULT_APP_COLORS.focused_block()
-            } else {
-                DEFAULT_APP_COLORS.default_block()
-            }
-        };
-
-        self.tables_component
-            .render(frame, tables_rect, get_block(FocusArea::Tables));
-        self.database_component
-            .render(frame, main_section_rect, get_block(FocusArea::Main));
-    }
-}
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use ratatui::{
+    Frame, Terminal,
+    crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    layout::{Constraint, Direction, Layout, Margin, Rect},
+    prelude::Backend,
+    style::Stylize,
+    widgets::{Block, Clear, Paragraph},
+};
+use unicode_width::UnicodeWidthStr;
+
+use crate::{
+    action::Action,
+    component::{
+        Component, breadcrumb_bar::BreadcrumbBar, command_palette::CommandPalette,
+        database_component::DatabaseComp, help_popup::HelpPopup, popup::PopUpComponent,
+        selected_table::TableSelection, status_bar::StatusBar,
+    },
+    config::{Config, DEFAULT_APP_COLORS, MIN_HEIGHT, MIN_WIDTH, STATUS_MESSAGE_DURATION},
+    connection::Connection,
+    error::AppError,
+    value::Value,
+};
+
+/// Attaches each database configured to be attached to the connection,
+/// returning the schema each was attached under alongside its tables
+fn attach_databases<'a>(
+    connection: &Connection,
+    config: &'a Config,
+) -> Result<Vec<(&'a str, Vec<String>)>, AppError> {
+    config
+        .attached_databases
+        .iter()
+        .map(|(schema, path)| {
+            connection.attach(path, schema)?;
+            Ok((schema.as_str(), connection.get_attached_table_list(schema)?))
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum FocusArea {
+    Tables,
+    Main,
+}
+
+/// The collection of state which the app runs off of
+pub struct App {
+    breadcrumb_bar: BreadcrumbBar,
+    /// Popup listing the commands exposed by the components, if it is open
+    command_palette: Option<CommandPalette>,
+    config: Config,
+    database_component: DatabaseComp,
+    focusing: FocusArea,
+    /// Popup listing the keybindings, shown over everything but the
+    /// integrity popup
+    help_popup: Option<HelpPopup>,
+    /// Popup asking whether to continue in read-only mode, shown when the
+    /// database fails its integrity check
+    integrity_popup: Option<PopUpComponent>,
+    last_status: Option<(String, Instant)>,
+    /// Notification shown in the top right corner of the app, alongside
+    /// when it was shown and how long it's shown for
+    notification: Option<(String, Instant, Duration)>,
+    status_bar: StatusBar,
+    tables_component: TableSelection,
+    tick_duration: Duration,
+}
+
+impl App {
+    /// Constructs the default app state for the CLI
+    pub fn new(config: &Config) -> Result<App, AppError> {
+        // open a single connection to be shared by every component
+        let connection = Arc::new(if config.read_only {
+            Connection::new_read_only(&config.database_path, config.debug)?
+        } else {
+            Connection::new(&config.database_path, config.debug)?
+        });
+        let mut tables_component = TableSelection::new();
+        tables_component.add_views(connection.get_view_list()?);
+        for (schema, tables) in attach_databases(&connection, config)? {
+            tables_component.add_attached_schema(schema, tables);
+        }
+        // don't let a corrupted database be written to without asking first
+        let integrity_popup = (!config.read_only && !connection.check_integrity()?).then(|| {
+            PopUpComponent::new(
+                "Database integrity check failed. Open in read-only mode?".to_string(),
+                vec!["Yes".to_string(), "No".to_string()],
+                None,
+            )
+        });
+        let mut app = Self {
+            breadcrumb_bar: BreadcrumbBar::new(),
+            command_palette: None,
+            config: config.clone(),
+            database_component: DatabaseComp::new(connection.clone(), "", 2, false)?,
+            focusing: FocusArea::Tables,
+            help_popup: None,
+            integrity_popup,
+            last_status: None,
+            notification: None,
+            status_bar: StatusBar::new(),
+            tables_component,
+            tick_duration: config.tick_duration,
+        };
+        app.use_connection(connection)?;
+        Ok(app)
+    }
+
+    /// Replaces the database component with one using the passed connection,
+    /// showing the currently selected table
+    fn use_connection(&mut self, connection: Arc<Connection>) -> Result<(), AppError> {
+        self.database_component = DatabaseComp::new(connection, "", 2, false)?;
+        self.database_component
+            .set_show_command_icons(self.config.show_command_icons);
+        self.database_component
+            .set_undo_depth(self.config.undo_depth);
+        self.database_component
+            .set_animations(self.config.animations);
+        if let Some(table) = self.tables_component.selected() {
+            self.database_component.change_table_used(table)?;
+        }
+        Ok(())
+    }
+
+    /// Passes the key to the command palette, closing it and running the
+    /// chosen command once one is chosen. Returns true if the app should quit
+    fn handle_command_palette_key(&mut self, key: KeyEvent) -> Result<bool, AppError> {
+        let Some(palette) = &mut self.command_palette else {
+            return Ok(false);
+        };
+        let actions = palette.handle_key_event(key)?;
+        match actions[..] {
+            [Action::NotifyCompletion] => {
+                let action = palette.selected_action().cloned();
+                self.command_palette = None;
+                self.handle_actions(action.into_iter().collect())
+            }
+            [Action::Quit] => {
+                self.command_palette = None;
+                Ok(false)
+            }
+            _ => self.handle_actions(actions),
+        }
+    }
+
+    /// Passes the key to the integrity popup, reopening the database as
+    /// read-only if accepted. Returns true if the app should quit
+    fn handle_integrity_popup_key(&mut self, key: KeyEvent) -> Result<bool, AppError> {
+        let Some(popup) = &mut self.integrity_popup else {
+            return Ok(false);
+        };
+        let actions = popup.handle_key_event(key)?;
+        // index 0 is the yes choice
+        let confirmed = popup.get_choice() == 0;
+        match actions[..] {
+            [Action::NotifyCompletion] if confirmed => {
+                self.integrity_popup = None;
+                let connection =
+                    Connection::new_read_only(&self.config.database_path, self.config.debug)?;
+                // the attached tables are already listed, so only attach them again
+                attach_databases(&connection, &self.config)?;
+                self.use_connection(Arc::new(connection))?;
+                self.set_status("Opened the database in read-only mode".to_string());
+                Ok(false)
+            }
+            // refusing read-only mode exits rather than risk further corruption
+            [Action::NotifyCompletion] | [Action::Quit] => Ok(true),
+            _ => Ok(false),
+        }
+    }
+
+    /// Handles actions which get passed to the app.
+    /// Returns true if the app should quit, false otherwise
+    fn handle_actions(&mut self, actions: Vec<Action>) -> Result<bool, AppError> {
+        // loop over all actions in order
+        for action in actions {
+            match action {
+                Action::Quit => return Ok(true),
+                Action::ChangeSelectedTable => {
+                    if let Some(table) = self.tables_component.selected() {
+                        match self.database_component.change_table_used(table) {
+                            Ok(()) => {
+                                self.set_status(format!("Switched to {}", table.debug_string()))
+                            }
+                            Err(err) => self.set_status(err.to_string()),
+                        }
+                    }
+                }
+                Action::OpenTable(name) => {
+                    if !self.tables_component.select_by_name(&name) {
+                        self.handle_actions(vec![
+                            Action::VeryLoudWrongBuzzer,
+                            Action::StatusMessage(format!("No table named {}", name)),
+                        ])?;
+                    } else if let Some(table) = self.tables_component.selected()
+                        && let Err(err) = self.database_component.change_table_used(table)
+                    {
+                        self.set_status(err.to_string());
+                    }
+                }
+                Action::NavigateToRelation {
+                    table,
+                    column,
+                    value,
+                } => {
+                    if let Err(err) = self.navigate_to_relation(&table, &column, &value) {
+                        self.set_status(err.to_string());
+                    }
+                }
+                Action::Refresh => {
+                    if let Err(err) = self.database_component.refresh_preserving_position() {
+                        self.set_status(err.to_string());
+                    }
+                }
+                Action::ExportCsv | Action::OpenFilter | Action::SortColumn(_) => {
+                    self.focusing = FocusArea::Main;
+                    match self.database_component.run_command(action) {
+                        Ok(actions) => {
+                            if self.handle_actions(actions)? {
+                                return Ok(true);
+                            }
+                        }
+                        Err(err) => self.set_status(err.to_string()),
+                    }
+                }
+                Action::Undo => match self.database_component.undo() {
+                    Ok(actions) => {
+                        if self.handle_actions(actions)? {
+                            return Ok(true);
+                        }
+                    }
+                    Err(err) => self.set_status(err.to_string()),
+                },
+                Action::Redo => match self.database_component.redo() {
+                    Ok(actions) => {
+                        if self.handle_actions(actions)? {
+                            return Ok(true);
+                        }
+                    }
+                    Err(err) => self.set_status(err.to_string()),
+                },
+                Action::StatusMessage(message) => self.set_status(message),
+                Action::ShowNotification(message, duration) => {
+                    self.notification = Some((message, Instant::now(), duration));
+                }
+                Action::VeryLoudWrongBuzzer => print!("\x07"),
+                _ => {}
+            }
+        }
+        Ok(false)
+    }
+
+    /// Progresses anything animated or timed when a tick passes without any
+    /// event. Returns true if the app should quit, false otherwise
+    fn handle_tick(&mut self) -> Result<bool, AppError> {
+        // clear the status message once it has been shown for long enough
+        if self
+            .last_status
+            .as_ref()
+            .is_some_and(|(_, shown_at)| shown_at.elapsed() >= STATUS_MESSAGE_DURATION)
+        {
+            self.last_status = None;
+        }
+        // likewise dismiss the notification once its time is up
+        if self
+            .notification
+            .as_ref()
+            .is_some_and(|(_, shown_at, duration)| shown_at.elapsed() >= *duration)
+        {
+            self.notification = None;
+        }
+        let mut actions = self.database_component.handle_tick();
+        actions.extend(self.tables_component.handle_tick());
+        actions.extend(self.breadcrumb_bar.handle_tick());
+        self.handle_actions(actions)
+    }
+
+    /// Opens the table referenced by a foreign key filtered to the rows
+    /// holding the value, listing the table first if it wasn't already
+    fn navigate_to_relation(
+        &mut self,
+        table: &str,
+        column: &str,
+        value: &Value,
+    ) -> Result<(), AppError> {
+        if !self.tables_component.select_by_name(table) {
+            let metadata = self.database_component.metadata_for(table)?;
+            self.tables_component.add_table(metadata);
+            self.tables_component.select_by_name(table);
+        }
+        if let Some(metadata) = self.tables_component.selected() {
+            self.database_component.change_table_used(metadata)?;
+        }
+        self.database_component.filter_to_value(column, value)?;
+        self.set_status(format!("Showing {} where {} = {}", table, column, value));
+        Ok(())
+    }
+
+    /// Shows the passed message in the status line until it fades
+    fn set_status(&mut self, message: String) {
+        self.last_status = Some((message, Instant::now()));
+    }
+
+    /// Pushes the state shown by the status bar to it
+    fn update_status_bar(&mut self) {
+        let (cell, dimensions) = self.database_component.table_position();
+        let focused = self.focus_path().last().copied().unwrap_or_default();
+        self.status_bar.update(
+            self.tables_component
+                .selected()
+                .map(|table| table.display_name),
+            self.database_component
+                .selected_command()
+                .map(|command| command.to_string()),
+            cell,
+            dimensions,
+            focused,
+            self.database_component.in_batch_mode(),
+        );
+        self.status_bar
+            .set_jump_buffer(self.database_component.jump_buffer());
+    }
+
+    pub fn run<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> Result<(), AppError> {
+        loop {
+            // show the state left by the last event or tick
+            self.update_status_bar();
+            // draw the thing
+            terminal.draw(|frame: &mut Frame| self.render(frame))?;
+
+            // poll keypress event with an ~1 frame at ~60fps timeout on
+            // encountering an event to prevent infinite blocking, allowing
+            // any moving components of the UI to progress
+            if !event::poll(self.tick_duration)? {
+                if self.handle_tick()? {
+                    return Ok(());
+                }
+                continue;
+            }
+            let event = event::read()?;
+            if let Event::Resize(width, height) = event {
+                // resize right away so the old layout isn't drawn for another frame
+                terminal.resize(Rect::new(0, 0, width, height))?;
+                // clear any layout cached for the previous size
+                self.database_component.handle_resize(width, height);
+                self.tables_component.handle_resize(width, height);
+                // let every component update anything depending on the size
+                let mut actions = self
+                    .database_component
+                    .handle_event(Action::OtherEvent(event.clone()))
+                    .unwrap_or_else(|err| vec![Action::StatusMessage(err.to_string())]);
+                actions.extend(
+                    self.tables_component
+                        .handle_event(Action::OtherEvent(event.clone()))?,
+                );
+                actions.extend(
+                    self.breadcrumb_bar
+                        .handle_event(Action::OtherEvent(event))?,
+                );
+                if self.handle_actions(actions)? {
+                    return Ok(());
+                }
+                continue;
+            }
+            if let Event::Key(key) = event {
+                // ignore key releases
+                if key.kind == KeyEventKind::Release {
+                    continue;
+                }
+                // the integrity popup must be answered before anything else
+                if self.integrity_popup.is_some() {
+                    if self.handle_integrity_popup_key(key)? {
+                        return Ok(());
+                    }
+                    continue;
+                }
+                // the help popup takes every key while it's shown
+                if let Some(help) = &mut self.help_popup {
+                    if help.handle_key_event(key)?.contains(&Action::Quit) {
+                        self.help_popup = None;
+                    }
+                    continue;
+                }
+                // the command palette likewise takes every key while it's shown
+                if self.command_palette.is_some() {
+                    if self.handle_command_palette_key(key)? {
+                        return Ok(());
+                    }
+                    continue;
+                }
+                let actions = match key {
+                    KeyEvent {
+                        code: KeyCode::Right,
+                        modifiers: KeyModifiers::CONTROL,
+                        ..
+                    } => {
+                        // ctrl+right moves the focus to the next component
+                        match self.focusing {
+                            FocusArea::Tables => {
+                                self.database_component.focus_first();
+                                self.focusing = FocusArea::Main;
+                            }
+                            FocusArea::Main => {
+                                if self.database_component.next_focus() {
+                                    self.focusing = FocusArea::Tables;
+                                }
+                            }
+                        }
+                        vec![Action::Noop]
+                    }
+                    KeyEvent {
+                        code: KeyCode::Left,
+                        modifiers: KeyModifiers::CONTROL,
+                        ..
+                    } => {
+                        // ctrl+left moves the focus to the prev component
+                        match self.focusing {
+                            FocusArea::Tables => {
+                                self.database_component.focus_last();
+                                self.focusing = FocusArea::Main;
+                            }
+                            FocusArea::Main => {
+                                if self.database_component.prev_focus() {
+                                    self.focusing = FocusArea::Tables;
+                                }
+                            }
+                        }
+                        vec![Action::Noop]
+                    }
+                    // ? shows the keybindings, unless it's being typed
+                    KeyEvent {
+                        code: KeyCode::Char('?'),
+                        ..
+                    } if self.focusing == FocusArea::Tables
+                        || !self.database_component.is_taking_text() =>
+                    {
+                        self.help_popup = Some(HelpPopup::new());
+                        vec![Action::Noop]
+                    }
+                    // : opens the command palette, unless it's being typed
+                    KeyEvent {
+                        code: KeyCode::Char(':'),
+                        ..
+                    } if self.focusing == FocusArea::Tables
+                        || !self.database_component.is_taking_text() =>
+                    {
+                        let mut commands = self.tables_component.commands();
+                        commands.extend(self.database_component.commands());
+                        self.command_palette = Some(CommandPalette::new(commands));
+                        vec![Action::Noop]
+                    }
+                    // ctrl+z and ctrl+y undo and redo changes to the database,
+                    // unless they'd undo and redo the text being typed
+                    KeyEvent {
+                        code: KeyCode::Char('z'),
+                        modifiers: KeyModifiers::CONTROL,
+                        ..
+                    } if self.focusing == FocusArea::Tables
+                        || !self.database_component.is_taking_text() =>
+                    {
+                        vec![Action::Undo]
+                    }
+                    KeyEvent {
+                        code: KeyCode::Char('y'),
+                        modifiers: KeyModifiers::CONTROL,
+                        ..
+                    } if self.focusing == FocusArea::Tables
+                        || !self.database_component.is_taking_text() =>
+                    {
+                        vec![Action::Redo]
+                    }
+                    _ => match self.focusing {
+                        // pass non-hardcoded key events to focused component
+                        // surface errors from database operations instead of quitting
+                        FocusArea::Main => self
+                            .database_component
+                            .handle_event(Action::KeyEvent(key))
+                            .unwrap_or_else(|err| vec![Action::StatusMessage(err.to_string())]),
+                        FocusArea::Tables => {
+                            self.tables_component.handle_event(Action::KeyEvent(key))?
+                        }
+                    },
+                };
+                // handle the actions returned by the focused component
+                if self.handle_actions(actions)? {
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    /// Walks the focused components to build the path shown in the breadcrumbs
+    fn focus_path(&self) -> Vec<&'static str> {
+        let mut path = vec![self.tables_component.name()];
+        if let Some(table) = self.tables_component.selected() {
+            path.push(table.display_name);
+        }
+        if self.focusing == FocusArea::Main {
+            path.extend(self.database_component.focus_path());
+        }
+        path
+    }
+
+    fn render(&mut self, frame: &mut Frame) {
+        // the layout can't fit within a tiny terminal, so ask for more room
+        let area = frame.area();
+        if area.width < MIN_WIDTH || area.height < MIN_HEIGHT {
+            let [_, warning_rect, _] = Layout::vertical([
+                Constraint::Fill(1),
+                Constraint::Length(1),
+                Constraint::Fill(1),
+            ])
+            .areas(area);
+            frame.render_widget(Block::new().bg(DEFAULT_APP_COLORS.main_bg), area);
+            frame.render_widget(
+                Paragraph::new("Terminal too small — please resize")
+                    .centered()
+                    .fg(DEFAULT_APP_COLORS.main_fg),
+                warning_rect,
+            );
+            return;
+        }
+        // use the very top line of the screen for the breadcrumbs
+        // and the bottom two lines for status messages and the status bar
+        let [breadcrumb_rect, app_rect, status_rect, status_bar_rect, ..] = *Layout::default()
+            .margin(0)
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1), // 1 line for the focus path
+                Constraint::Min(0),    // the rest for the app itself
+                Constraint::Length(1), // 1 line for the status message
+                Constraint::Length(1), // 1 line for the status bar
+            ])
+            .split(frame.area())
+        else {
+            panic!("Not enough size to create the necessary rects or something");
+        };
+
+        // use the left side of the screen for the tables tabs
+        let [tables_rect, main_section_rect, ..] = *Layout::default()
+            .margin(0)
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage(15), // 15% width for the list of tables to edit
+                Constraint::Percentage(85), // 85% width for the rest
+            ])
+            .split(app_rect)
+        else {
+            panic!("Not enough size to create the necessary rects or something");
+        };
+
+        // determine the blocks used by each component depending on focus
+        let get_block = |focus: FocusArea| {
+            if self.focusing == focus && self.database_component.is_read_only() {
+                // mark that nothing can be changed in another color
+                DEFAULT_APP_COLORS.focused_read_only_block()
+            } else if self.focusing == focus {
+                DEFAULT_APP_COLORS.focused_block()
+            } else {
+                DEFAULT_APP_COLORS.default_block()
+            }
+        };
+
+        self.breadcrumb_bar.set_path(self.focus_path());
+        self.breadcrumb_bar
+            .render(frame, breadcrumb_rect, Block::new());
+        self.tables_component
+            .render(frame, tables_rect, get_block(FocusArea::Tables));
+        self.database_component
+            .render(frame, main_section_rect, get_block(FocusArea::Main));
+
+        let status = self
+            .last_status
+            .as_ref()
+            .map_or("", |(message, _)| message.as_str());
+        frame.render_widget(
+            Paragraph::new(format!(" {}", status))
+                .fg(DEFAULT_APP_COLORS.main_fg)
+                .bg(DEFAULT_APP_COLORS.main_bg),
+            status_rect,
+        );
+        self.status_bar.render(frame, status_bar_rect, Block::new());
+
+        // overlay the notification over the top right of the app
+        if let Some((message, _, _)) = &self.notification {
+            let width = (message.width() as u16 + 4).min(app_rect.width);
+            let notification_rect = Rect {
+                x: app_rect.right().saturating_sub(width),
+                y: app_rect.y,
+                width,
+                height: 3.min(app_rect.height),
+            };
+            frame.render_widget(Clear, notification_rect);
+            frame.render_widget(
+                Paragraph::new(message.as_str())
+                    .centered()
+                    .fg(DEFAULT_APP_COLORS.main_fg)
+                    .bg(DEFAULT_APP_COLORS.alt_bg)
+                    .block(DEFAULT_APP_COLORS.default_block()),
+                notification_rect,
+            );
+        }
+
+        // overlay the help popup over the whole app
+        if let Some(help) = &mut self.help_popup {
+            help.render(
+                frame,
+                app_rect.inner(Margin {
+                    horizontal: app_rect.width / 6,
+                    vertical: app_rect.height / 6,
+                }),
+                DEFAULT_APP_COLORS.focused_block(),
+            );
+        }
+
+        // overlay the command palette over the whole app
+        if let Some(palette) = &mut self.command_palette {
+            palette.render(
+                frame,
+                app_rect.inner(Margin {
+                    horizontal: app_rect.width / 4,
+                    vertical: app_rect.height / 6,
+                }),
+                DEFAULT_APP_COLORS.focused_block(),
+            );
+        }
+
+        // overlay the integrity popup over the whole app
+        if let Some(popup) = &mut self.integrity_popup {
+            popup.render(
+                frame,
+                app_rect.inner(Margin {
+                    horizontal: app_rect.width / 5,
+                    vertical: app_rect.height / 3,
+                }),
+                DEFAULT_APP_COLORS.default_block(),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, path::PathBuf};
+
+    use ratatui::{Terminal, backend::TestBackend};
+
+    use super::*;
+
+    #[test]
+    fn the_layout_fits_within_the_minimum_size() {
+        let path = env::temp_dir().join(format!("cli-min-size-{}.db", std::process::id()));
+        fs::File::create(&path).unwrap();
+        // the first configured table is shown when the app starts
+        Connection::new(&path, false)
+            .unwrap()
+            .modify(
+                "CREATE TABLE category (id INTEGER PRIMARY KEY, name TEXT);",
+                [],
+            )
+            .unwrap();
+        let config = Config {
+            database_path: path.clone(),
+            working_directory: PathBuf::from("."),
+            show_command_icons: false,
+            tick_duration: Duration::from_millis(16),
+            animations: false,
+            debug: false,
+            read_only: false,
+            attached_databases: Vec::new(),
+            undo_depth: 1,
+        };
+        let mut app = App::new(&config).unwrap();
+        let mut terminal = Terminal::new(TestBackend::new(MIN_WIDTH, MIN_HEIGHT)).unwrap();
+        terminal.draw(|frame| app.render(frame)).unwrap();
+        let rendered: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(!rendered.contains("Terminal too small"));
+
+        drop(app);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn configured_databases_are_attached_with_their_tables() {
+        let dir = env::temp_dir();
+        let main_path = dir.join(format!("cli-attach-main-{}.db", std::process::id()));
+        let aux_path = dir.join(format!("cli-attach-aux-{}.db", std::process::id()));
+        fs::File::create(&main_path).unwrap();
+        fs::File::create(&aux_path).unwrap();
+        Connection::new(&aux_path, false)
+            .unwrap()
+            .modify("CREATE TABLE notes (id INTEGER PRIMARY KEY);", [])
+            .unwrap();
+        let config = Config {
+            database_path: main_path.clone(),
+            working_directory: PathBuf::from("."),
+            show_command_icons: false,
+            tick_duration: Duration::from_millis(16),
+            animations: false,
+            debug: false,
+            read_only: false,
+            attached_databases: vec![("aux".to_string(), aux_path.clone())],
+            undo_depth: 1,
+        };
+
+        let connection = Connection::new(&main_path, false).unwrap();
+        assert_eq!(
+            attach_databases(&connection, &config).unwrap(),
+            [("aux", vec!["notes".to_string()])]
+        );
+        // as done when reopening the database in read-only mode
+        let read_only = Connection::new_read_only(&main_path, false).unwrap();
+        attach_databases(&read_only, &config).unwrap();
+        assert_eq!(read_only.get_row_count("aux.notes").unwrap(), 0);
+
+        drop((connection, read_only));
+        fs::remove_file(&main_path).unwrap();
+        fs::remove_file(&aux_path).unwrap();
+    }
+}
@@ -1,42 +1,120 @@
-use glob::{MatchOptions, glob_with};
-
-use crate::config::PHP_PATH;
-
-pub type AutoFillFn = fn(&str) -> Option<String>;
-
-/// Provides with an option for the filepath directing to an HTML file
-/// associated with a PHP file stored in the pre-defined `PHP_PATH` folder.
-pub fn html_filepath(content: &str) -> Option<String> {
-    if content.is_empty() {
-        return None;
-    }
-    let options = MatchOptions {
-        case_sensitive: false,
-        require_literal_separator: false,
-        require_literal_leading_dot: false,
-    };
-    let search_path = format!("{}{}*", PHP_PATH, content);
-    let paths = match glob_with(&search_path, options) {
-        Ok(p) => p,
-        Err(_) => return None,
-    };
-    let mut suggestion: Option<String> = None;
-    // grab the first globbed path
-    if let Some(path) = paths.flatten().next() {
-        let suggested_path = if path.is_dir() {
-            path
-        } else {
-            path.with_extension("html")
-        };
-        if let Some(suggested_string) = suggested_path.to_str() {
-            let lead_dirname = if let Some(stripped) = PHP_PATH.strip_prefix("./") {
-                stripped
-            } else {
-                PHP_PATH
-            };
-            // remove the leading, already present content
-            suggestion = Some(suggested_string[(lead_dirname.len() + content.len())..].to_string());
-        }
-    }
-    suggestion
-}
+use std::{collections::HashMap, sync::Arc};
+
+use glob::{MatchOptions, glob_with};
+
+use crate::{
+    config::{PHP_PATH, VALUE_AUTOFILL_LIMIT},
+    connection::{Connection, escape_like, quote_identifier, quote_table},
+};
+
+/// Function providing an optional suggestion to append to the passed content,
+/// given the content and the index of the cursor within its characters.
+/// Closures may be used so that suggestions can depend on captured state
+pub type AutoFillFn = Arc<dyn Fn(&str, usize) -> Option<String> + Send + Sync>;
+
+/// Function creating the autofill functions for a table's columns once a
+/// connection to the database is available
+pub type AutoFillFactory = fn(&Connection) -> HashMap<&'static str, AutoFillFn>;
+
+/// Creates an autofill function which provides with an option for the
+/// filepath to a file within the `base_path` folder, where the suggested
+/// file has the passed extension in place of the globbed file's own.
+pub fn filepath_autofill_factory(base_path: &'static str, extension: &'static str) -> AutoFillFn {
+    Arc::new(move |content: &str, _| filepath(base_path, extension, content))
+}
+
+/// Creates an autofill function which completes the content from the values
+/// already within the passed column of the table, for columns which have no
+/// autofill function of their own
+pub fn value_autofill_factory(
+    connection: Arc<Connection>,
+    table: &str,
+    column: &str,
+) -> AutoFillFn {
+    let (table, column) = (table.to_owned(), column.to_owned());
+    Arc::new(move |content: &str, _| value_autofill(&connection, &table, &column, content))
+}
+
+/// Provides with an option for the rest of a value beginning with the prefix
+/// within the passed column of the table. Only the part which every such
+/// value shares is suggested, so that what's suggested is never ambiguous
+pub fn value_autofill(
+    connection: &Connection,
+    table: &str,
+    column: &str,
+    prefix: &str,
+) -> Option<String> {
+    if prefix.is_empty() {
+        return None;
+    }
+    // match the prefix literally rather than as a pattern
+    let pattern = format!("{}%", escape_like(prefix));
+    let query = format!(
+        "SELECT DISTINCT {0} FROM {1} WHERE {0} LIKE ?1 ESCAPE '\\' LIMIT {2};",
+        quote_identifier(column),
+        quote_table(table),
+        VALUE_AUTOFILL_LIMIT
+    );
+    let matches = connection.query(&query, [pattern]).ok()?;
+    // LIKE ignores case, so skip the prefix by its length rather than its text
+    let prefix_len = prefix.chars().count();
+    let mut suffixes = matches.rows.iter().map(|row| {
+        row[0]
+            .to_string()
+            .chars()
+            .skip(prefix_len)
+            .collect::<String>()
+    });
+    let first = suffixes.next()?;
+    let shared = suffixes.fold(first, |shared, suffix| {
+        std::iter::zip(shared.chars(), suffix.chars())
+            .take_while(|(a, b)| a == b)
+            .map(|(a, _)| a)
+            .collect()
+    });
+    (!shared.is_empty()).then_some(shared)
+}
+
+/// Provides with an option for the filepath directing to an HTML file
+/// associated with a PHP file stored in the pre-defined `PHP_PATH` folder.
+#[allow(dead_code)]
+pub fn html_filepath(content: &str) -> Option<String> {
+    filepath(PHP_PATH, "html", content)
+}
+
+/// Provides with an option for the filepath of a file within the `base_path`
+/// folder which begins with the content, given the passed extension
+fn filepath(base_path: &str, extension: &str, content: &str) -> Option<String> {
+    if content.is_empty() {
+        return None;
+    }
+    let options = MatchOptions {
+        case_sensitive: false,
+        require_literal_separator: false,
+        require_literal_leading_dot: false,
+    };
+    let search_path = format!("{}{}*", base_path, content);
+    let paths = match glob_with(&search_path, options) {
+        Ok(p) => p,
+        Err(_) => return None,
+    };
+    let mut suggestion: Option<String> = None;
+    // grab the first globbed path
+    if let Some(path) = paths.flatten().next() {
+        let suggested_path = if path.is_dir() {
+            path
+        } else {
+            path.with_extension(extension)
+        };
+        if let Some(suggested_string) = suggested_path.to_str() {
+            let lead_dirname = if let Some(stripped) = base_path.strip_prefix("./") {
+                stripped
+            } else {
+                base_path
+            };
+            // remove the leading, already present content
+            suggestion = Some(suggested_string[(lead_dirname.len() + content.len())..].to_string());
+        }
+    }
+    suggestion
+}
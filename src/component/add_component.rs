@@ -1,360 +1,548 @@
-use std::{borrow::Cow, iter::zip};
-
-use editable_text::EditableText;
-use ratatui::{
-    layout::Margin,
-    widgets::{Padding, Paragraph},
-};
-use rusqlite::{params_from_iter, types::Value as RsqValue};
-
-use crate::{
-    connection::{ColumnInfo, Connection},
-    value::Value,
-};
-
-use super::{popup::PopUpComponent, *};
-
-#[derive(Debug, PartialEq, Eq)]
-enum FocusArea {
-    Main,
-    Popup,
-    Submit,
-}
-
-pub struct AddComponent {
-    connection: Connection,
-    column_info: Vec<ColumnInfo>,
-    columns: Vec<String>,
-    fields: Vec<EditableText>,
-    focusing: FocusArea,
-    hovering: usize,
-    popup: PopUpComponent,
-    selected_field: Option<usize>,
-    table: String,
-}
-
-impl AddComponent {
-    pub fn new(table: &str) -> Result<Self, Box<dyn Error>> {
-        let connection = Connection::new()?;
-        let column_info = connection.get_column_info(table)?;
-        // collect column names and determine if that field is required (NOT NULL)
-        let columns = connection.get_columns(table)?;
-        // create an EditableTextComponent for each field
-        let fields = columns.iter().map(|_| EditableText::default()).collect();
-        Ok(Self {
-            connection,
-            column_info,
-            columns,
-            fields,
-            focusing: FocusArea::Main,
-            hovering: 0,
-            popup: PopUpComponent::new(
-                format!("Add this row to the {} table?", table),
-                vec!["Yes".to_string(), "No".to_string()],
-                None,
-            ),
-            selected_field: None,
-            table: table.to_owned(),
-        })
-    }
-
-    /// Simple check to ensure that the required fields are filled and
-    /// each field contains the correct data type.
-    fn requirements_filled(&self) -> bool {
-        for (col, field) in zip(self.column_info.iter(), self.fields.iter()) {
-            if !field.is_empty() {
-                // ensure the value of the field can be properly parsed
-                if Value::parse_column(&col.data_type, &field.text()).is_ok() {
-                    // all is okay so far, so continue
-                    continue;
-                } else {
-                    // improper data type in some field, so it is not valid
-                    return false;
-                }
-            } else if col.is_not_null {
-                // there is a required field that is empty, so it is not valid
-                return false;
-            }
-        }
-        true
-    }
-
-    /// Submits the current fields of the row for insertion into the table.
-    /// Does not assume correctness of fields, and so will not submit if
-    /// there are missing NON-NULL fields or invalid data types.
-    fn submit(&mut self) -> Result<Vec<Action>, Box<dyn Error>> {
-        let mut cols: Vec<String> = Vec::with_capacity(self.column_info.len());
-        let mut values: Vec<Value> = Vec::with_capacity(self.fields.len());
-        // pair columns and fields together, ignoring empty fields,
-        // and also ensure required fields are filled
-        for (col, field) in zip(self.column_info.iter(), self.fields.iter()) {
-            if !field.is_empty() {
-                // ensure the value of the field can be properly parsed
-                if let Ok(val) = Value::parse_column(&col.data_type, &field.text()) {
-                    // add the column name and associated value to the list
-                    cols.push(col.name.to_owned());
-                    values.push(val);
-                } else {
-                    return Ok(vec![Action::VeryLoudWrongBuzzer]);
-                }
-            } else if col.is_not_null {
-                // there is a required field that is empty, so don't submit
-                return Ok(vec![Action::VeryLoudWrongBuzzer]);
-            }
-        }
-        if !cols.is_empty() {
-            // create a list of the positional arguments for joining into the query
-            // as well as parse each value into a Rusqlite Value in order to bind
-            // them as params within a prepared statement
-            let (pos, params): (Vec<String>, Vec<RsqValue>) = values
-                .into_iter()
-                .enumerate()
-                .map(|(ind, val)| (format!("?{}", ind + 1), val.into()))
-                .unzip();
-            // create the query with positional params as placeholders for the values
-            let query = format!(
-                "INSERT INTO {} ({}) VALUES ({});",
-                self.table,
-                cols.join(", "),
-                pos.join(", ")
-            );
-            // run the query to insert the value with the intended params
-            // TODO: STORE RESULT SOMEWHERE PROBABLY AS IT RETURNS THE ROW
-            //       INDEX OF THE INSERTED ROW WHICH CAN BE USED FOR KEEPING
-            //       THAT ROW SHOWN OR SOMETHING
-            self.connection.insert(&query, params_from_iter(params))?;
-        }
-        Ok(vec![
-            Action::RevertToMain,
-            Action::RevertCommandSelection,
-            Action::Refresh,
-        ])
-    }
-
-    fn handle_submit_keys(&mut self, key: KeyEvent) -> Result<Vec<Action>, Box<dyn Error>> {
-        match key.code {
-            KeyCode::Esc => Ok(vec![Action::Quit]), // terminate on encountering Esc
-            KeyCode::Up => {
-                if self.selected_field.is_none() {
-                    // move up to the main section
-                    self.focusing = FocusArea::Main;
-                }
-                Ok(vec![Action::Noop])
-            }
-            KeyCode::Enter => {
-                if self.requirements_filled() {
-                    self.focusing = FocusArea::Popup; // show popup to confirm
-                    Ok(vec![Action::Noop])
-                } else {
-                    Ok(vec![Action::VeryLoudWrongBuzzer])
-                }
-            }
-            _ => Ok(vec![Action::Noop]),
-        }
-    }
-
-    fn handle_main_keys(&mut self, key: KeyEvent) -> Result<Vec<Action>, Box<dyn Error>> {
-        match key.code {
-            KeyCode::Esc => {
-                // if a field is focused when Esc is pressed,
-                // defocus it, else terminate
-                if let Some(focus_ind) = self.selected_field {
-                    self.fields[focus_ind].toggle_focus();
-                    self.selected_field = None;
-                    Ok(vec![Action::Noop])
-                } else {
-                    self.selected_field = Some(self.hovering);
-                    self.fields[self.hovering].toggle_focus();
-                    Ok(vec![Action::Quit])
-                }
-            }
-            KeyCode::Left => {
-                if let Some(focus_ind) = self.selected_field {
-                    self.fields[focus_ind].handle_key_event(key)
-                } else {
-                    self.hovering = self.hovering.saturating_sub(1);
-                    Ok(vec![Action::Noop])
-                }
-            }
-            KeyCode::Right => {
-                if let Some(focus_ind) = self.selected_field {
-                    self.fields[focus_ind].handle_key_event(key)
-                } else {
-                    self.hovering = (self.hovering.saturating_add(1)).min(self.fields.len() - 1);
-                    Ok(vec![Action::Noop])
-                }
-            }
-            KeyCode::Down => {
-                if self.selected_field.is_none() {
-                    // move down to the submit button
-                    self.focusing = FocusArea::Submit;
-                }
-                Ok(vec![Action::Noop])
-            }
-            KeyCode::Enter => {
-                // enter will toggle the focus of the text component, and,
-                // as shift-enter is not valid, newlines are added using some
-                // other key combination determined in EditableTextComponent
-                if let Some(focus_ind) = self.selected_field {
-                    self.fields[focus_ind].toggle_focus();
-                    self.selected_field = None;
-                } else {
-                    self.selected_field = Some(self.hovering);
-                    self.fields[self.hovering].toggle_focus();
-                }
-                Ok(vec![Action::Noop])
-            }
-            _ => {
-                // get the focused text component to funnel events to it
-                if let Some(focus_ind) = self.selected_field {
-                    self.fields[focus_ind].handle_key_event(key)
-                } else {
-                    Ok(vec![Action::Noop])
-                }
-            }
-        }
-    }
-}
-
-impl Component for AddComponent {
-    fn handle_key_event(&mut self, key: KeyEvent) -> Result<Vec<Action>, Box<dyn Error>> {
-        // ignore key releases
-        if key.kind == KeyEventKind::Release {
-            return Ok(vec![Action::Noop]);
-        }
-        match self.focusing {
-            FocusArea::Main => self.handle_main_keys(key),
-            FocusArea::Submit => self.handle_submit_keys(key),
-            FocusArea::Popup => {
-                let actions = self.popup.handle_key_event(key)?;
-                let mut handled = false;
-                let handled_actions = match actions[..] {
-                    [Action::NotifyCompletion] => {
-                        handled = true;
-                        // index 0 is the yes choice, so confirm submission
-                        if self.popup.get_choice() == 0 {
-                            self.submit()
-                        } else {
-                            // close the popup since they chose cancel
-                            self.focusing = FocusArea::Submit;
-                            Ok(vec![Action::Noop])
-                        }
-                    }
-                    [Action::Quit] => {
-                        handled = true;
-                        self.focusing = FocusArea::Submit; // hide popup
-                        Ok(vec![Action::Noop])
-                    }
-                    _ => Ok(vec![Action::Noop]),
-                };
-                if handled {
-                    handled_actions
-                } else {
-                    Ok(actions)
-                }
-            }
-        }
-    }
-
-    fn render(&mut self, f: &mut Frame, rect: Rect, block: Block) {
-        // create a Rect which doesn't include the block/border
-        let borderless = rect.inner(Margin::new(1, 1));
-        // set up styles
-        let header_style = Style::new()
-            .fg(DEFAULT_APP_COLORS.header_fg)
-            .bg(DEFAULT_APP_COLORS.header_bg);
-        let header_hover_style = Style::new().bg(DEFAULT_APP_COLORS.selection_one_bg);
-        let field_height = borderless.height - 3; // -1 for the header, -1 for column info, and -1 for submit
-        let field_width = borderless.width / self.fields.len() as u16;
-
-        let base_style = Style::new()
-            .fg(DEFAULT_APP_COLORS.main_fg)
-            .bg(DEFAULT_APP_COLORS.main_bg);
-        let alt_style = Style::new()
-            .fg(DEFAULT_APP_COLORS.main_fg)
-            .bg(DEFAULT_APP_COLORS.alt_bg);
-
-        // render an empty paragraph for external border and background
-        f.render_widget(Paragraph::new("").style(base_style).block(block), rect);
-
-        // render the info for each column above each column name
-        for (ind, info) in self.column_info.iter().enumerate() {
-            f.render_widget(
-                Paragraph::new(info.to_string())
-                    .centered()
-                    .style(header_style)
-                    .block(Block::new().padding(Padding::symmetric(1, 0))),
-                Rect::new(
-                    borderless.x + (field_width * ind as u16),
-                    borderless.y,
-                    field_width,
-                    1,
-                ),
-            );
-        }
-
-        // render the header as a series of paragraphs
-        for (ind, column) in self.columns.iter().enumerate() {
-            let col_style = if self.focusing == FocusArea::Main && ind == self.hovering {
-                header_hover_style
-            } else {
-                header_style
-            };
-            f.render_widget(
-                Paragraph::new(Cow::from(column))
-                    .centered()
-                    .style(col_style),
-                Rect::new(
-                    borderless.x + (field_width * ind as u16),
-                    borderless.y + 1,
-                    field_width,
-                    1,
-                ),
-            );
-        }
-
-        // render each field's input location
-        for (ind, text_component) in self.fields.iter_mut().enumerate() {
-            text_component.render_with_style(
-                f,
-                Rect::new(
-                    borderless.x + (field_width * ind as u16),
-                    borderless.y + 2,
-                    field_width,
-                    field_height,
-                ),
-                Block::new(),
-                if ind % 2 == 0 { alt_style } else { base_style },
-            );
-        }
-
-        // render the submit button
-        f.render_widget(
-            Paragraph::new("Submit")
-                .centered()
-                .style(if self.focusing == FocusArea::Submit {
-                    header_hover_style
-                } else {
-                    header_style
-                }),
-            Rect::new(
-                borderless.x,
-                borderless.y + borderless.height - 1,
-                borderless.width,
-                1,
-            ),
-        );
-
-        // if the popup is focused, also show that
-        if self.focusing == FocusArea::Popup {
-            self.popup.render(
-                f,
-                borderless.inner(Margin {
-                    horizontal: borderless.width / 5,
-                    vertical: borderless.height / 5,
-                }),
-                DEFAULT_APP_COLORS.default_block(),
-            );
-        }
-    }
-}
+use std::{borrow::Cow, iter::zip, sync::Arc};
+
+use editable_text::EditableText;
+use ratatui::{
+    layout::Margin,
+    widgets::{Padding, Paragraph},
+};
+use rusqlite::{params_from_iter, types::Value as RsqValue};
+
+use crate::{
+    connection::{ColumnInfo, Connection, quote_identifier, quote_table},
+    value::Value,
+};
+
+use super::{popup::PopUpComponent, *};
+
+#[derive(Debug, PartialEq, Eq)]
+enum FocusArea {
+    Main,
+    Popup,
+    Submit,
+}
+
+pub struct AddComponent {
+    connection: Arc<Connection>,
+    column_info: Vec<ColumnInfo>,
+    columns: Vec<String>,
+    fields: Vec<EditableText>,
+    focusing: FocusArea,
+    hovering: usize,
+    popup: PopUpComponent,
+    selected_field: Option<usize>,
+    table: String,
+    /// Whether the content of each field can be parsed as its column's type
+    valid_fields: Vec<bool>,
+}
+
+impl AddComponent {
+    pub fn new(connection: Arc<Connection>, table: &str) -> Result<Self, AppError> {
+        let column_info = connection.get_column_info(table)?;
+        // collect column names and determine if that field is required (NOT NULL)
+        let columns = connection.get_columns(table)?;
+        // create an EditableTextComponent for each field
+        let fields = columns.iter().map(|_| EditableText::default()).collect();
+        let valid_fields = vec![true; columns.len()];
+        Ok(Self {
+            connection,
+            column_info,
+            columns,
+            fields,
+            focusing: FocusArea::Main,
+            hovering: 0,
+            popup: PopUpComponent::new(
+                format!("Add this row to the {} table?", table),
+                vec!["Yes".to_string(), "No".to_string()],
+                None,
+            ),
+            selected_field: None,
+            table: table.to_owned(),
+            valid_fields,
+        })
+    }
+
+    /// Creates an add component whose fields are prefilled with the values
+    /// of the passed row, retrieved by column name, leaving the primary key
+    /// fields, NULLs, and blobs empty
+    pub fn from_row(
+        connection: Arc<Connection>,
+        table: &str,
+        row: &[(String, Value)],
+    ) -> Result<Self, AppError> {
+        let mut add_comp = Self::new(connection, table)?;
+        for (info, field) in zip(add_comp.column_info.iter(), add_comp.fields.iter_mut()) {
+            if info.is_primary_key {
+                continue;
+            }
+            if let Some((_, value)) = row.iter().find(|(name, _)| *name == info.name)
+                && !matches!(value, Value::Null | Value::Blob(_))
+            {
+                *field = EditableText::from(value.to_string().as_str());
+            }
+        }
+        for ind in 0..add_comp.fields.len() {
+            add_comp.update_validity(ind);
+        }
+        Ok(add_comp)
+    }
+
+    /// Rechecks whether the field at the index can be parsed as its column's
+    /// type, treating empty fields as valid as they may be optional
+    fn update_validity(&mut self, ind: usize) {
+        let field = &self.fields[ind];
+        self.valid_fields[ind] = field.is_empty()
+            || Value::parse_column(&self.column_info[ind].data_type, &field.text()).is_ok();
+    }
+
+    /// Simple check to ensure that the required fields are filled and
+    /// each field contains the correct data type.
+    fn requirements_filled(&self) -> bool {
+        for (col, field) in zip(self.column_info.iter(), self.fields.iter()) {
+            if !field.is_empty() {
+                // ensure the value of the field can be properly parsed
+                if Value::parse_column(&col.data_type, &field.text()).is_ok() {
+                    // all is okay so far, so continue
+                    continue;
+                } else {
+                    // improper data type in some field, so it is not valid
+                    return false;
+                }
+            } else if col.is_not_null {
+                // there is a required field that is empty, so it is not valid
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Submits the current fields of the row for insertion into the table.
+    /// Does not assume correctness of fields, and so will not submit if
+    /// there are missing NON-NULL fields or invalid data types.
+    fn submit(&mut self) -> Result<Vec<Action>, AppError> {
+        if self.connection.is_read_only() {
+            return Ok(vec![Action::VeryLoudWrongBuzzer]);
+        }
+        let mut cols: Vec<String> = Vec::with_capacity(self.column_info.len());
+        let mut values: Vec<Value> = Vec::with_capacity(self.fields.len());
+        // pair columns and fields together, ignoring empty fields,
+        // and also ensure required fields are filled
+        for (col, field) in zip(self.column_info.iter(), self.fields.iter()) {
+            if !field.is_empty() {
+                // ensure the value of the field can be properly parsed
+                let val = match Value::parse_column(&col.data_type, &field.text()) {
+                    Ok(val) => val,
+                    Err(err) => {
+                        // say why rather than only that the value is wrong
+                        return Ok(vec![
+                            Action::VeryLoudWrongBuzzer,
+                            Action::StatusMessage(err.to_string()),
+                        ]);
+                    }
+                };
+                // ensure the value isn't already used within a unique column
+                if col.is_unique
+                    && self
+                        .connection
+                        .count_matching(&self.table, &col.name, (&val).into())?
+                        > 0
+                {
+                    // return to the fields so the value can be changed
+                    self.focusing = FocusArea::Main;
+                    return Ok(vec![
+                        Action::VeryLoudWrongBuzzer,
+                        Action::StatusMessage(format!(
+                            "{} must be unique, but {} is already used",
+                            col.name, val
+                        )),
+                    ]);
+                }
+                // add the quoted column name and associated value to the list
+                cols.push(quote_identifier(&col.name));
+                values.push(val);
+            } else if col.is_not_null {
+                // there is a required field that is empty, so don't submit
+                return Ok(vec![Action::VeryLoudWrongBuzzer]);
+            }
+        }
+        if !cols.is_empty() {
+            // create a list of the positional arguments for joining into the query
+            // as well as parse each value into a Rusqlite Value in order to bind
+            // them as params within a prepared statement
+            let (pos, params): (Vec<String>, Vec<RsqValue>) = values
+                .into_iter()
+                .enumerate()
+                .map(|(ind, val)| (format!("?{}", ind + 1), val.into()))
+                .unzip();
+            // create the query with positional params as placeholders for the values
+            let query = format!(
+                "INSERT INTO {} ({}) VALUES ({});",
+                quote_table(&self.table),
+                cols.join(", "),
+                pos.join(", ")
+            );
+            // run the query to insert the value with the intended params,
+            // letting the container refresh and show the inserted row
+            let rowid = self.connection.insert(&query, params_from_iter(params))?;
+            return Ok(vec![
+                Action::RevertToMain,
+                Action::RevertCommandSelection,
+                Action::SelectInsertedRow(rowid),
+            ]);
+        }
+        Ok(vec![
+            Action::RevertToMain,
+            Action::RevertCommandSelection,
+            Action::Refresh,
+        ])
+    }
+
+    /// Returns the names of the focused components nested within this one
+    pub fn focus_path(&self) -> Vec<&'static str> {
+        match self.focusing {
+            FocusArea::Popup => vec![self.popup.name()],
+            FocusArea::Main | FocusArea::Submit => Vec::new(),
+        }
+    }
+
+    fn handle_submit_keys(&mut self, key: KeyEvent) -> Result<Vec<Action>, AppError> {
+        match key.code {
+            KeyCode::Esc => Ok(vec![Action::Quit]), // terminate on encountering Esc
+            KeyCode::Up => {
+                if self.selected_field.is_none() {
+                    // move up to the main section
+                    self.focusing = FocusArea::Main;
+                }
+                Ok(vec![Action::Noop])
+            }
+            KeyCode::Enter => {
+                if self.requirements_filled() {
+                    self.focusing = FocusArea::Popup; // show popup to confirm
+                    Ok(vec![Action::Noop])
+                } else {
+                    Ok(vec![Action::VeryLoudWrongBuzzer])
+                }
+            }
+            _ => Ok(vec![Action::Noop]),
+        }
+    }
+
+    fn handle_main_keys(&mut self, key: KeyEvent) -> Result<Vec<Action>, AppError> {
+        match key.code {
+            KeyCode::Esc => {
+                // if a field is focused when Esc is pressed,
+                // defocus it, else terminate
+                if let Some(focus_ind) = self.selected_field {
+                    self.fields[focus_ind].toggle_focus();
+                    self.selected_field = None;
+                    Ok(vec![Action::Noop])
+                } else {
+                    self.selected_field = Some(self.hovering);
+                    self.fields[self.hovering].toggle_focus();
+                    Ok(vec![Action::Quit])
+                }
+            }
+            KeyCode::Left => {
+                if let Some(focus_ind) = self.selected_field {
+                    self.fields[focus_ind].handle_key_event(key)
+                } else {
+                    self.hovering = self.hovering.saturating_sub(1);
+                    Ok(vec![Action::Noop])
+                }
+            }
+            KeyCode::Right => {
+                if let Some(focus_ind) = self.selected_field {
+                    self.fields[focus_ind].handle_key_event(key)
+                } else {
+                    self.hovering = (self.hovering.saturating_add(1)).min(self.fields.len() - 1);
+                    Ok(vec![Action::Noop])
+                }
+            }
+            KeyCode::Down => {
+                if self.selected_field.is_none() {
+                    // move down to the submit button
+                    self.focusing = FocusArea::Submit;
+                }
+                Ok(vec![Action::Noop])
+            }
+            KeyCode::Enter => {
+                // enter will toggle the focus of the text component, and,
+                // as shift-enter is not valid, newlines are added using some
+                // other key combination determined in EditableTextComponent
+                if let Some(focus_ind) = self.selected_field {
+                    self.fields[focus_ind].toggle_focus();
+                    self.selected_field = None;
+                } else {
+                    self.selected_field = Some(self.hovering);
+                    self.fields[self.hovering].toggle_focus();
+                }
+                Ok(vec![Action::Noop])
+            }
+            _ => {
+                // get the focused text component to funnel events to it
+                if let Some(focus_ind) = self.selected_field {
+                    self.fields[focus_ind].handle_key_event(key)
+                } else {
+                    Ok(vec![Action::Noop])
+                }
+            }
+        }
+    }
+}
+
+impl Component for AddComponent {
+    fn name(&self) -> &'static str {
+        "Add"
+    }
+
+    fn handle_resize(&mut self, width: u16, height: u16) {
+        for field in self.fields.iter_mut() {
+            field.handle_resize(width, height);
+        }
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent) -> Result<Vec<Action>, AppError> {
+        // ignore key releases
+        if key.kind == KeyEventKind::Release {
+            return Ok(vec![Action::Noop]);
+        }
+        match self.focusing {
+            FocusArea::Main => {
+                let actions = self.handle_main_keys(key);
+                // the focused field may have been edited, so revalidate it
+                if let Some(focus_ind) = self.selected_field {
+                    self.update_validity(focus_ind);
+                }
+                actions
+            }
+            FocusArea::Submit => self.handle_submit_keys(key),
+            FocusArea::Popup => {
+                let actions = self.popup.handle_key_event(key)?;
+                let mut handled = false;
+                let handled_actions = match actions[..] {
+                    [Action::NotifyCompletion] => {
+                        handled = true;
+                        // index 0 is the yes choice, so confirm submission
+                        if self.popup.get_choice() == 0 {
+                            self.submit()
+                        } else {
+                            // close the popup since they chose cancel
+                            self.focusing = FocusArea::Submit;
+                            Ok(vec![Action::Noop])
+                        }
+                    }
+                    [Action::Quit] => {
+                        handled = true;
+                        self.focusing = FocusArea::Submit; // hide popup
+                        Ok(vec![Action::Noop])
+                    }
+                    _ => Ok(vec![Action::Noop]),
+                };
+                if handled {
+                    handled_actions
+                } else {
+                    Ok(actions)
+                }
+            }
+        }
+    }
+
+    fn render(&mut self, f: &mut Frame, rect: Rect, block: Block) {
+        // create a Rect which doesn't include the block/border
+        let borderless = rect.inner(Margin::new(1, 1));
+        // set up styles
+        let header_style = Style::new()
+            .fg(DEFAULT_APP_COLORS.header_fg)
+            .bg(DEFAULT_APP_COLORS.header_bg);
+        let header_hover_style = Style::new().bg(DEFAULT_APP_COLORS.selection_one_bg);
+        let field_height = borderless.height - 3; // -1 for the header, -1 for column info, and -1 for submit
+        let field_width = borderless.width / self.fields.len() as u16;
+
+        let base_style = Style::new()
+            .fg(DEFAULT_APP_COLORS.main_fg)
+            .bg(DEFAULT_APP_COLORS.main_bg);
+        let alt_style = Style::new()
+            .fg(DEFAULT_APP_COLORS.main_fg)
+            .bg(DEFAULT_APP_COLORS.alt_bg);
+        let invalid_style = Style::new()
+            .fg(DEFAULT_APP_COLORS.main_fg)
+            .bg(DEFAULT_APP_COLORS.invalid_field_bg);
+
+        // render an empty paragraph for external border and background
+        f.render_widget(Paragraph::new("").style(base_style).block(block), rect);
+
+        // render the info for each column above each column name
+        for (ind, info) in self.column_info.iter().enumerate() {
+            f.render_widget(
+                Paragraph::new(info.to_string())
+                    .centered()
+                    .style(header_style)
+                    .block(Block::new().padding(Padding::symmetric(1, 0))),
+                Rect::new(
+                    borderless.x + (field_width * ind as u16),
+                    borderless.y,
+                    field_width,
+                    1,
+                ),
+            );
+        }
+
+        // render the header as a series of paragraphs
+        for (ind, column) in self.columns.iter().enumerate() {
+            let col_style = if self.focusing == FocusArea::Main && ind == self.hovering {
+                header_hover_style
+            } else {
+                header_style
+            };
+            f.render_widget(
+                Paragraph::new(Cow::from(column))
+                    .centered()
+                    .style(col_style),
+                Rect::new(
+                    borderless.x + (field_width * ind as u16),
+                    borderless.y + 1,
+                    field_width,
+                    1,
+                ),
+            );
+        }
+
+        // render each field's input location
+        for (ind, text_component) in self.fields.iter_mut().enumerate() {
+            text_component.render_with_style(
+                f,
+                Rect::new(
+                    borderless.x + (field_width * ind as u16),
+                    borderless.y + 2,
+                    field_width,
+                    field_height,
+                ),
+                Block::new(),
+                if !self.valid_fields[ind] {
+                    invalid_style
+                } else if ind % 2 == 0 {
+                    alt_style
+                } else {
+                    base_style
+                },
+            );
+        }
+
+        // render the submit button
+        f.render_widget(
+            Paragraph::new("Submit")
+                .centered()
+                .style(if self.focusing == FocusArea::Submit {
+                    header_hover_style
+                } else {
+                    header_style
+                }),
+            Rect::new(
+                borderless.x,
+                borderless.y + borderless.height - 1,
+                borderless.width,
+                1,
+            ),
+        );
+
+        // if the popup is focused, also show that
+        if self.focusing == FocusArea::Popup {
+            self.popup.render(
+                f,
+                borderless.inner(Margin {
+                    horizontal: borderless.width / 5,
+                    vertical: borderless.height / 5,
+                }),
+                DEFAULT_APP_COLORS.default_block(),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::crossterm::event::KeyModifiers;
+
+    use super::*;
+
+    fn people_connection() -> Arc<Connection> {
+        let connection = Connection::open_in_memory().unwrap();
+        connection
+            .create_test_table(
+                "CREATE TABLE people (id INTEGER PRIMARY KEY, name TEXT NOT NULL, age INTEGER);
+                INSERT INTO people (name, age) VALUES ('ann', 31);",
+            )
+            .unwrap();
+        Arc::new(connection)
+    }
+
+    fn press(add_comp: &mut AddComponent, code: KeyCode) -> Vec<Action> {
+        add_comp
+            .handle_key_event(KeyEvent::new(code, KeyModifiers::NONE))
+            .unwrap()
+    }
+
+    /// Moves right to the next field and types the text into it
+    fn fill_next_field(add_comp: &mut AddComponent, text: &str) {
+        press(add_comp, KeyCode::Right);
+        press(add_comp, KeyCode::Enter);
+        for c in text.chars() {
+            press(add_comp, KeyCode::Char(c));
+        }
+        press(add_comp, KeyCode::Enter);
+    }
+
+    #[test]
+    fn submitting_the_fields_inserts_a_row() {
+        let connection = people_connection();
+        let mut add_comp = AddComponent::new(connection.clone(), "people").unwrap();
+        fill_next_field(&mut add_comp, "bob");
+        fill_next_field(&mut add_comp, "42");
+        press(&mut add_comp, KeyCode::Down);
+        press(&mut add_comp, KeyCode::Enter);
+        // confirm the popup, whose first choice is yes
+        let actions = press(&mut add_comp, KeyCode::Enter);
+        assert!(actions.contains(&Action::SelectInsertedRow(2)));
+
+        let table = connection
+            .query("SELECT name, age FROM people WHERE id = 2;", [])
+            .unwrap();
+        assert_eq!(
+            table.rows,
+            [vec![Value::Text("bob".to_string()), Value::Integer(42)]]
+        );
+    }
+
+    #[test]
+    fn inserts_into_tables_with_awkward_names() {
+        let connection = Connection::open_in_memory().unwrap();
+        connection
+            .create_test_table(
+                "CREATE TABLE \"my table\" (id INTEGER PRIMARY KEY, \"order\" INTEGER);",
+            )
+            .unwrap();
+        let connection = Arc::new(connection);
+        let mut add_comp = AddComponent::new(connection.clone(), "my table").unwrap();
+        fill_next_field(&mut add_comp, "3");
+        press(&mut add_comp, KeyCode::Down);
+        press(&mut add_comp, KeyCode::Enter);
+        let actions = press(&mut add_comp, KeyCode::Enter);
+        assert!(actions.contains(&Action::SelectInsertedRow(1)));
+
+        let table = connection
+            .query("SELECT \"order\" FROM \"my table\";", [])
+            .unwrap();
+        assert_eq!(table.rows, [vec![Value::Integer(3)]]);
+    }
+
+    #[test]
+    fn submitting_without_required_fields_buzzes() {
+        let connection = people_connection();
+        let mut add_comp = AddComponent::new(connection.clone(), "people").unwrap();
+        press(&mut add_comp, KeyCode::Down);
+        assert_eq!(
+            press(&mut add_comp, KeyCode::Enter),
+            vec![Action::VeryLoudWrongBuzzer]
+        );
+        assert_eq!(connection.get_row_count("people").unwrap(), 1);
+    }
+}
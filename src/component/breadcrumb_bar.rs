@@ -0,0 +1,35 @@
+use ratatui::widgets::Paragraph;
+
+use super::*;
+
+/// Single line component showing the path of components which are
+/// currently focused, from the outermost to the innermost
+#[derive(Default)]
+pub struct BreadcrumbBar {
+    path: Vec<&'static str>,
+}
+
+impl BreadcrumbBar {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replaces the displayed path with the passed one
+    pub fn set_path(&mut self, path: Vec<&'static str>) {
+        self.path = path;
+    }
+}
+
+impl Component for BreadcrumbBar {
+    fn name(&self) -> &'static str {
+        "Breadcrumbs"
+    }
+
+    fn render(&mut self, f: &mut Frame, rect: Rect, block: Block) {
+        let breadcrumbs = Paragraph::new(format!(" {}", self.path.join(" > ")))
+            .fg(DEFAULT_APP_COLORS.header_fg)
+            .bg(DEFAULT_APP_COLORS.header_bg)
+            .block(block);
+        f.render_widget(breadcrumbs, rect);
+    }
+}
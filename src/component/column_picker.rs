@@ -0,0 +1,178 @@
+use std::collections::HashSet;
+
+use ratatui::{
+    layout::{Constraint, Layout},
+    widgets::{Clear, List, ListState, Scrollbar, ScrollbarOrientation, ScrollbarState},
+};
+
+use super::*;
+
+/// Popup listing every column of the shown table with a checkbox marking
+/// whether it is shown. Space toggles the highlit column, and Enter
+/// confirms which columns are hidden
+pub struct ColumnPickerPopup {
+    columns: Vec<String>,
+    /// Whether each column is checked to be shown
+    shown: Vec<bool>,
+    list_state: ListState,
+    scroll_state: ScrollbarState,
+}
+
+impl ColumnPickerPopup {
+    /// Creates the picker for the passed columns, leaving those at the
+    /// hidden indices unchecked
+    pub fn new(columns: &[String], hidden: &HashSet<usize>) -> Self {
+        Self {
+            columns: columns.to_vec(),
+            shown: (0..columns.len()).map(|x| !hidden.contains(&x)).collect(),
+            list_state: ListState::default().with_selected(Some(0)),
+            scroll_state: ScrollbarState::new(columns.len().saturating_sub(1)),
+        }
+    }
+
+    /// Returns the indices of the columns left unchecked
+    pub fn hidden_columns(&self) -> HashSet<usize> {
+        self.shown
+            .iter()
+            .enumerate()
+            .filter(|(_, shown)| !**shown)
+            .map(|(x, _)| x)
+            .collect()
+    }
+}
+
+impl Component for ColumnPickerPopup {
+    fn name(&self) -> &'static str {
+        "Columns"
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent) -> Result<Vec<Action>, AppError> {
+        // ignore key releases
+        if key.kind == KeyEventKind::Release {
+            return Ok(vec![Action::Noop]);
+        }
+
+        match key.code {
+            KeyCode::Esc => Ok(vec![Action::Quit]), // close popup
+            // at least one column has to remain shown
+            KeyCode::Enter if !self.shown.contains(&true) => Ok(vec![Action::VeryLoudWrongBuzzer]),
+            KeyCode::Enter => Ok(vec![Action::NotifyCompletion]), // notify container
+            KeyCode::Char(' ') => {
+                if let Some(shown) = self
+                    .list_state
+                    .selected()
+                    .and_then(|y| self.shown.get_mut(y))
+                {
+                    *shown = !*shown;
+                }
+                Ok(vec![Action::Noop])
+            }
+            KeyCode::Up => {
+                self.list_state.select_previous();
+                self.scroll_state.prev();
+                Ok(vec![Action::Noop])
+            }
+            KeyCode::Down => {
+                // the list state doesn't clamp to the last column until rendered
+                if self
+                    .list_state
+                    .selected()
+                    .is_some_and(|y| y + 1 < self.columns.len())
+                {
+                    self.list_state.select_next();
+                    self.scroll_state.next();
+                }
+                Ok(vec![Action::Noop])
+            }
+            _ => Ok(vec![Action::Noop]),
+        }
+    }
+
+    fn render(&mut self, f: &mut Frame, rect: Rect, block: Block) {
+        // clear the rendered content behind the popup
+        f.render_widget(Clear, rect);
+        let inner = block.inner(rect);
+        f.render_widget(
+            block
+                .title(" Columns (space to toggle) ")
+                .bg(DEFAULT_APP_COLORS.alt_bg),
+            rect,
+        );
+
+        let [list_rect, scrollbar_rect] =
+            Layout::horizontal([Constraint::Fill(1), Constraint::Length(1)]).areas(inner);
+        let list = List::from_iter(
+            std::iter::zip(&self.columns, &self.shown)
+                .map(|(column, shown)| format!("[{}] {}", if *shown { 'x' } else { ' ' }, column)),
+        )
+        .fg(DEFAULT_APP_COLORS.main_fg)
+        .highlight_style(Style::new().reversed());
+        f.render_stateful_widget(list, list_rect, &mut self.list_state);
+        f.render_stateful_widget(
+            Scrollbar::default()
+                .orientation(ScrollbarOrientation::VerticalRight)
+                .begin_symbol(None)
+                .end_symbol(None)
+                .style(DEFAULT_APP_COLORS.main_fg),
+            scrollbar_rect,
+            &mut self.scroll_state,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::crossterm::event::KeyModifiers;
+
+    use super::*;
+
+    fn press(picker: &mut ColumnPickerPopup, code: KeyCode) -> Vec<Action> {
+        picker
+            .handle_key_event(KeyEvent::new(code, KeyModifiers::NONE))
+            .unwrap()
+    }
+
+    fn columns() -> Vec<String> {
+        ["id", "name", "age"].map(String::from).to_vec()
+    }
+
+    #[test]
+    fn starts_with_the_hidden_columns_unchecked() {
+        let picker = ColumnPickerPopup::new(&columns(), &HashSet::from([1]));
+        assert_eq!(picker.hidden_columns(), HashSet::from([1]));
+    }
+
+    #[test]
+    fn space_toggles_the_highlit_column() {
+        let mut picker = ColumnPickerPopup::new(&columns(), &HashSet::new());
+        press(&mut picker, KeyCode::Down);
+        press(&mut picker, KeyCode::Char(' '));
+        assert_eq!(picker.hidden_columns(), HashSet::from([1]));
+        press(&mut picker, KeyCode::Char(' '));
+        assert!(picker.hidden_columns().is_empty());
+    }
+
+    #[test]
+    fn highlight_stays_on_the_last_column() {
+        let mut picker = ColumnPickerPopup::new(&columns(), &HashSet::new());
+        for _ in 0..5 {
+            press(&mut picker, KeyCode::Down);
+        }
+        press(&mut picker, KeyCode::Char(' '));
+        assert_eq!(picker.hidden_columns(), HashSet::from([2]));
+    }
+
+    #[test]
+    fn enter_refuses_to_hide_every_column() {
+        let mut picker = ColumnPickerPopup::new(&columns(), &HashSet::from([0, 1, 2]));
+        assert_eq!(
+            press(&mut picker, KeyCode::Enter),
+            vec![Action::VeryLoudWrongBuzzer]
+        );
+        press(&mut picker, KeyCode::Char(' '));
+        assert_eq!(
+            press(&mut picker, KeyCode::Enter),
+            vec![Action::NotifyCompletion]
+        );
+    }
+}
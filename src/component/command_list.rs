@@ -1,179 +1,307 @@
-use ratatui::{
-    layout::Constraint,
-    style::{Color, Style, Stylize},
-    text::Text,
-    widgets::{Cell, Row, Table, TableState},
-};
-
-use super::*;
-
-#[derive(Debug, Clone, PartialEq, Eq, strum_macros::Display)]
-pub enum EditCommand {
-    Add,
-    Modify,
-    Delete,
-    Reorder,
-    Swap,
-}
-
-impl EditCommand {
-    pub fn num_selections(&self) -> usize {
-        match self {
-            Self::Add => 0,
-            Self::Modify => 1,
-            Self::Delete => 1,
-            Self::Reorder => 4,
-            Self::Swap => 2,
-        }
-    }
-
-    pub fn uses_rows(&self) -> bool {
-        !matches!(self, Self::Modify)
-    }
-}
-
-pub struct CommandListComponent {
-    commands: Vec<EditCommand>,
-    state: TableState,
-    selected: Option<usize>,
-    prev_selected: Option<usize>,
-}
-
-impl CommandListComponent {
-    pub fn new(commands: Vec<EditCommand>) -> Self {
-        Self {
-            commands,
-            state: TableState::new().with_selected_column(Some(0)),
-            selected: Some(0),
-            prev_selected: None,
-        }
-    }
-
-    pub fn selected(&self) -> Option<EditCommand> {
-        self.selected.map(|ind| self.commands[ind].clone())
-    }
-
-    pub fn change_commands(&mut self, commands: Vec<EditCommand>) {
-        // ensure no reading of empty list of commands when changing a default component
-        let highlit_opt = self.state.selected_column();
-        // highlit_opt must be Some as in order to select there must be some highlit component
-        if !self.commands.is_empty() && highlit_opt.is_some() {
-            let highlit_ind = highlit_opt.unwrap();
-            let highlit_command = &self.commands[highlit_ind];
-            // highlight the same command in the new list of commands if it exists,
-            // or reset to first column if the highlit command is not in the new list
-            let assoc_ind = commands
-                .iter()
-                .position(|com| com == highlit_command)
-                .unwrap_or(0);
-            self.state.select_column(Some(assoc_ind));
-
-            // select either previously selected option or just the first option, which is default
-            self.selected = self.selected.map_or(Some(0), |ind| {
-                let selected_command = &self.commands[ind];
-                commands
-                    .iter()
-                    .position(|com| com == selected_command)
-                    .or(Some(0))
-            });
-        }
-        self.commands = commands;
-    }
-
-    /// Makes the current selection be the previously selected item
-    pub fn revert_selection(&mut self) {
-        self.selected = self.prev_selected;
-    }
-
-    fn scroll_left_by(&mut self, amount: u16) {
-        if let Some(x) = self.state.selected_column() {
-            if x == 0 {
-                self.state.select_last_column();
-                return;
-            }
-        }
-        self.state.scroll_left_by(amount);
-    }
-
-    fn scroll_right_by(&mut self, amount: u16) {
-        if let Some(x) = self.state.selected_column() {
-            if x == self.commands.len() - 1 {
-                self.state.select_first_column();
-                return;
-            }
-        }
-        self.state.scroll_right_by(amount);
-    }
-}
-
-impl Component for CommandListComponent {
-    fn handle_key_event(&mut self, key: KeyEvent) -> Result<Vec<Action>, Box<dyn Error>> {
-        // ignore key releases
-        if key.kind == KeyEventKind::Release {
-            return Ok(vec![Action::Noop]);
-        }
-        match key.code {
-            KeyCode::Esc => Ok(vec![Action::Quit]), // terminate on encountering Esc
-            KeyCode::Enter => {
-                let newly_selected = self.state.selected_column();
-                if self.selected != newly_selected {
-                    // needed in cases where the action shouldn't actually stay selected
-                    self.prev_selected = self.selected;
-                    self.selected = newly_selected;
-                    Ok(vec![Action::ChangeEditCommand])
-                } else {
-                    Ok(vec![Action::Noop])
-                }
-            }
-            KeyCode::Left => {
-                self.scroll_left_by(1);
-                Ok(vec![Action::Noop])
-            }
-            KeyCode::Right => {
-                self.scroll_right_by(1);
-                Ok(vec![Action::Noop])
-            }
-            _ => Ok(vec![Action::Noop]),
-        }
-    }
-
-    fn render(&mut self, f: &mut Frame, rect: Rect, block: Block) {
-        let commands = if !self.commands.is_empty() {
-            let highlight_style = Style::new().reversed();
-            let strings: Vec<String> = self
-                .commands
-                .iter()
-                .map(|command| command.to_string())
-                .collect();
-            Table::default()
-                .fg(DEFAULT_APP_COLORS.main_fg)
-                .bg(DEFAULT_APP_COLORS.main_bg)
-                .column_highlight_style(highlight_style)
-                .cell_highlight_style(highlight_style)
-                .block(block)
-                .widths(
-                    strings
-                        .iter()
-                        .map(|s| Constraint::Max(s.len() as u16 + 4))
-                        .collect::<Vec<Constraint>>(),
-                )
-                .rows([Row::from_iter(strings.into_iter().enumerate().map(
-                    |(ind, s)| {
-                        let mut cell = Cell::new(Text::from(s).centered());
-                        if Some(ind) == self.selected {
-                            cell = cell.bg(DEFAULT_APP_COLORS.selection_one_bg);
-                        }
-                        cell
-                    },
-                ))])
-        } else {
-            Table::default()
-                .fg(DEFAULT_APP_COLORS.main_fg)
-                .bg(DEFAULT_APP_COLORS.main_bg)
-                .cell_highlight_style(Color::LightBlue)
-                .block(block)
-                .rows([Row::new(vec!["No", "Items", "Present"])])
-        };
-        f.render_stateful_widget(commands, rect, &mut self.state);
-    }
-}
+use ratatui::{
+    layout::Constraint,
+    style::{Color, Style, Stylize},
+    text::Text,
+    widgets::{Cell, Row, Table, TableState},
+};
+
+use super::*;
+use crate::config::LOCALE;
+
+#[derive(Debug, Clone, PartialEq, Eq, strum_macros::IntoStaticStr)]
+pub enum EditCommand {
+    Add,
+    Modify,
+    Delete,
+    Reorder,
+    Swap,
+    Search,
+    Filter,
+    Import,
+}
+
+impl EditCommand {
+    pub fn num_selections(&self) -> usize {
+        match self {
+            Self::Add => 0,
+            Self::Modify => 1,
+            Self::Delete => usize::MAX, // any number of rows may be deleted at once
+            Self::Reorder => 4,
+            Self::Swap => 2,
+            Self::Search => 0,
+            Self::Filter => 0,
+            Self::Import => 0,
+        }
+    }
+
+    pub fn uses_rows(&self) -> bool {
+        !matches!(
+            self,
+            Self::Modify | Self::Search | Self::Filter | Self::Import
+        )
+    }
+
+    /// Whether each selection must be within a different row
+    pub fn requires_unique_rows(&self) -> bool {
+        matches!(self, Self::Reorder | Self::Swap)
+    }
+
+    /// Name of the command as displayed in the passed locale. Every locale
+    /// currently uses the English names until translations are added
+    pub fn localized_name(&self, locale: &str) -> &'static str {
+        match (self, locale) {
+            (Self::Add, _) => "Add",
+            (Self::Modify, _) => "Modify",
+            (Self::Delete, _) => "Delete",
+            (Self::Reorder, _) => "Reorder",
+            (Self::Swap, _) => "Swap",
+            (Self::Search, _) => "Search",
+            (Self::Filter, _) => "Filter",
+            (Self::Import, _) => "Import",
+        }
+    }
+
+    /// Unicode symbol displayed alongside the command's name
+    pub fn icon(&self) -> &'static str {
+        match self {
+            Self::Add => "✚",
+            Self::Modify => "✎",
+            Self::Delete => "✖",
+            Self::Reorder => "⇅",
+            Self::Swap => "⇄",
+            Self::Search => "⌕",
+            Self::Filter => "🔍",
+            Self::Import => "⇩",
+        }
+    }
+
+    /// Human-readable description of what the command does
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::Add => "Fill in the fields of a new row to add it",
+            Self::Modify => "Select a cell to modify its value",
+            Self::Delete => "Select rows to delete them",
+            Self::Reorder => "Select rows to change their display order",
+            Self::Swap => "Select two rows to swap their positions",
+            Self::Search => "Search within a single column",
+            Self::Filter => "Filter the shown rows with a WHERE or GROUP BY clause",
+            Self::Import => "Insert the rows of a CSV file into the table",
+        }
+    }
+
+    /// Description of the keys used to carry out the command, if it has any
+    pub fn keybinding_hint(&self) -> Option<&'static str> {
+        match self {
+            Self::Add => Some("Enter on a field to edit it"),
+            Self::Modify => Some("Enter on a cell, then Enter again to submit"),
+            Self::Delete => Some("Enter on each row, then d to delete them"),
+            Self::Reorder => Some("Enter on rows in their new order, then r to reorder them"),
+            Self::Swap => None,
+            Self::Search => Some("Enter on a column, then type the term to search for"),
+            Self::Filter => Some("Ctrl+F also opens the filter"),
+            Self::Import => Some("Type the path of a CSV file whose header names the columns"),
+        }
+    }
+}
+
+impl std::fmt::Display for EditCommand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.localized_name(LOCALE))
+    }
+}
+
+pub struct CommandListComponent {
+    area: Rect,
+    commands: Vec<EditCommand>,
+    state: TableState,
+    selected: Option<usize>,
+    prev_selected: Option<usize>,
+    show_icons: bool,
+}
+
+impl CommandListComponent {
+    pub fn new(commands: Vec<EditCommand>) -> Self {
+        Self {
+            area: Rect::default(),
+            commands,
+            state: TableState::new().with_selected_column(Some(0)),
+            selected: Some(0),
+            prev_selected: None,
+            show_icons: true,
+        }
+    }
+
+    /// Sets whether each command is displayed with its icon, as some
+    /// terminals can't display them
+    pub fn set_show_icons(&mut self, show_icons: bool) {
+        self.show_icons = show_icons;
+    }
+
+    /// Returns true if the passed command is one of the listed commands
+    pub fn has_command(&self, command: &EditCommand) -> bool {
+        self.commands.contains(command)
+    }
+
+    pub fn selected(&self) -> Option<EditCommand> {
+        self.selected.map(|ind| self.commands[ind].clone())
+    }
+
+    pub fn change_commands(&mut self, commands: Vec<EditCommand>) {
+        // ensure no reading of empty list of commands when changing a default component
+        let highlit_opt = self.state.selected_column();
+        // highlit_opt must be Some as in order to select there must be some highlit component
+        if !self.commands.is_empty()
+            && let Some(highlit_ind) = highlit_opt
+        {
+            let highlit_command = &self.commands[highlit_ind];
+            // highlight the same command in the new list of commands if it exists,
+            // or reset to first column if the highlit command is not in the new list
+            let assoc_ind = commands
+                .iter()
+                .position(|com| com == highlit_command)
+                .unwrap_or(0);
+            self.state.select_column(Some(assoc_ind));
+
+            // select either previously selected option or just the first option, which is default
+            self.selected = self.selected.map_or(Some(0), |ind| {
+                let selected_command = &self.commands[ind];
+                commands
+                    .iter()
+                    .position(|com| com == selected_command)
+                    .or(Some(0))
+            });
+        }
+        self.commands = commands;
+    }
+
+    /// Makes the current selection be the previously selected item
+    pub fn revert_selection(&mut self) {
+        self.selected = self.prev_selected;
+    }
+
+    fn scroll_left_by(&mut self, amount: u16) {
+        if let Some(x) = self.state.selected_column()
+            && x == 0
+        {
+            self.state.select_last_column();
+            return;
+        }
+        self.state.scroll_left_by(amount);
+    }
+
+    fn scroll_right_by(&mut self, amount: u16) {
+        if let Some(x) = self.state.selected_column()
+            && x == self.commands.len() - 1
+        {
+            self.state.select_first_column();
+            return;
+        }
+        self.state.scroll_right_by(amount);
+    }
+}
+
+impl Component for CommandListComponent {
+    fn name(&self) -> &'static str {
+        "Commands"
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent) -> Result<Vec<Action>, AppError> {
+        // ignore key releases
+        if key.kind == KeyEventKind::Release {
+            return Ok(vec![Action::Noop]);
+        }
+        match key.code {
+            KeyCode::Esc => Ok(vec![Action::Quit]), // terminate on encountering Esc
+            KeyCode::Enter => {
+                let newly_selected = self.state.selected_column();
+                if self.selected != newly_selected {
+                    // needed in cases where the action shouldn't actually stay selected
+                    self.prev_selected = self.selected;
+                    self.selected = newly_selected;
+                    Ok(vec![Action::ChangeEditCommand])
+                } else {
+                    Ok(vec![Action::Noop])
+                }
+            }
+            KeyCode::Left => {
+                self.scroll_left_by(1);
+                Ok(vec![Action::Noop])
+            }
+            KeyCode::Right => {
+                self.scroll_right_by(1);
+                Ok(vec![Action::Noop])
+            }
+            _ => Ok(vec![Action::Noop]),
+        }
+    }
+
+    fn render(&mut self, f: &mut Frame, rect: Rect, block: Block) {
+        self.set_area(rect);
+        let commands = if !self.commands.is_empty() {
+            let highlight_style = Style::new().reversed();
+            // show the description of the highlit command as a tooltip
+            let block = match self.state.selected_column() {
+                Some(ind) if ind < self.commands.len() => {
+                    let command = &self.commands[ind];
+                    let tooltip = match command.keybinding_hint() {
+                        Some(hint) => format!(" {} ({}) ", command.description(), hint),
+                        None => format!(" {} ", command.description()),
+                    };
+                    block.title_bottom(tooltip)
+                }
+                _ => block,
+            };
+            let strings: Vec<String> = self
+                .commands
+                .iter()
+                .map(|command| {
+                    let name = command.localized_name(LOCALE);
+                    if self.show_icons {
+                        format!("{} {}", command.icon(), name)
+                    } else {
+                        name.to_string()
+                    }
+                })
+                .collect();
+            Table::default()
+                .fg(DEFAULT_APP_COLORS.main_fg)
+                .bg(DEFAULT_APP_COLORS.main_bg)
+                .column_highlight_style(highlight_style)
+                .cell_highlight_style(highlight_style)
+                .block(block)
+                .widths(
+                    strings
+                        .iter()
+                        .map(|s| Constraint::Max(s.width() as u16 + 4))
+                        .collect::<Vec<Constraint>>(),
+                )
+                .rows([Row::from_iter(strings.into_iter().enumerate().map(
+                    |(ind, s)| {
+                        let mut cell = Cell::new(Text::from(s).centered());
+                        if Some(ind) == self.selected {
+                            cell = cell.bg(DEFAULT_APP_COLORS.selection_one_bg);
+                        }
+                        cell
+                    },
+                ))])
+        } else {
+            Table::default()
+                .fg(DEFAULT_APP_COLORS.main_fg)
+                .bg(DEFAULT_APP_COLORS.main_bg)
+                .cell_highlight_style(Color::LightBlue)
+                .block(block)
+                .rows([Row::new(vec!["No", "Items", "Present"])])
+        };
+        f.render_stateful_widget(commands, rect, &mut self.state);
+    }
+}
+
+impl AreaComponent for CommandListComponent {
+    fn area(&self) -> Rect {
+        self.area
+    }
+
+    fn set_area(&mut self, rect: Rect) {
+        self.area = rect;
+    }
+}
@@ -0,0 +1,137 @@
+use ratatui::{
+    layout::{Constraint, Layout},
+    widgets::{Clear, List, ListState, Scrollbar, ScrollbarOrientation, ScrollbarState},
+};
+
+use super::*;
+
+/// Popup listing the named actions exposed by the app's components, where
+/// Enter runs the highlit one in place of its keybinding
+pub struct CommandPalette {
+    commands: Vec<(String, Action)>,
+    list_state: ListState,
+    scroll_state: ScrollbarState,
+}
+
+impl CommandPalette {
+    /// Creates the palette listing the passed commands in order
+    pub fn new(commands: Vec<(String, Action)>) -> Self {
+        let scroll_state = ScrollbarState::new(commands.len().saturating_sub(1));
+        Self {
+            commands,
+            list_state: ListState::default().with_selected(Some(0)),
+            scroll_state,
+        }
+    }
+
+    /// Returns the action of the highlit command, if there are any commands
+    pub fn selected_action(&self) -> Option<&Action> {
+        self.list_state
+            .selected()
+            .and_then(|y| self.commands.get(y))
+            .map(|(_, action)| action)
+    }
+}
+
+impl Component for CommandPalette {
+    fn name(&self) -> &'static str {
+        "Commands"
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent) -> Result<Vec<Action>, AppError> {
+        // ignore key releases
+        if key.kind == KeyEventKind::Release {
+            return Ok(vec![Action::Noop]);
+        }
+
+        match key.code {
+            KeyCode::Esc => Ok(vec![Action::Quit]), // close popup
+            KeyCode::Enter if self.selected_action().is_none() => {
+                Ok(vec![Action::VeryLoudWrongBuzzer])
+            }
+            KeyCode::Enter => Ok(vec![Action::NotifyCompletion]), // notify container
+            KeyCode::Up => {
+                self.list_state.select_previous();
+                self.scroll_state.prev();
+                Ok(vec![Action::Noop])
+            }
+            KeyCode::Down => {
+                // the list state doesn't clamp to the last command until rendered
+                if self
+                    .list_state
+                    .selected()
+                    .is_some_and(|y| y + 1 < self.commands.len())
+                {
+                    self.list_state.select_next();
+                    self.scroll_state.next();
+                }
+                Ok(vec![Action::Noop])
+            }
+            _ => Ok(vec![Action::Noop]),
+        }
+    }
+
+    fn render(&mut self, f: &mut Frame, rect: Rect, block: Block) {
+        // clear the rendered content behind the popup
+        f.render_widget(Clear, rect);
+        let inner = block.inner(rect);
+        f.render_widget(
+            block.title(" Commands ").bg(DEFAULT_APP_COLORS.alt_bg),
+            rect,
+        );
+
+        let [list_rect, scrollbar_rect] =
+            Layout::horizontal([Constraint::Fill(1), Constraint::Length(1)]).areas(inner);
+        let list = List::from_iter(self.commands.iter().map(|(name, _)| name.as_str()))
+            .fg(DEFAULT_APP_COLORS.main_fg)
+            .highlight_style(Style::new().reversed());
+        f.render_stateful_widget(list, list_rect, &mut self.list_state);
+        f.render_stateful_widget(
+            Scrollbar::default()
+                .orientation(ScrollbarOrientation::VerticalRight)
+                .begin_symbol(None)
+                .end_symbol(None)
+                .style(DEFAULT_APP_COLORS.main_fg),
+            scrollbar_rect,
+            &mut self.scroll_state,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::crossterm::event::KeyModifiers;
+
+    use super::*;
+
+    fn press(palette: &mut CommandPalette, code: KeyCode) -> Vec<Action> {
+        palette
+            .handle_key_event(KeyEvent::new(code, KeyModifiers::NONE))
+            .unwrap()
+    }
+
+    #[test]
+    fn enter_notifies_with_the_highlit_action() {
+        let mut palette = CommandPalette::new(vec![
+            ("Refresh".to_string(), Action::Refresh),
+            ("Filter…".to_string(), Action::OpenFilter),
+        ]);
+        press(&mut palette, KeyCode::Down);
+        press(&mut palette, KeyCode::Down);
+        assert_eq!(
+            press(&mut palette, KeyCode::Enter),
+            vec![Action::NotifyCompletion]
+        );
+        assert_eq!(palette.selected_action(), Some(&Action::OpenFilter));
+    }
+
+    #[test]
+    fn enter_without_commands_buzzes() {
+        let mut palette = CommandPalette::new(Vec::new());
+        assert_eq!(
+            press(&mut palette, KeyCode::Enter),
+            vec![Action::VeryLoudWrongBuzzer]
+        );
+        assert_eq!(press(&mut palette, KeyCode::Esc), vec![Action::Quit]);
+    }
+}
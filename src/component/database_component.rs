@@ -1,612 +1,2813 @@
-use std::collections::HashMap;
-
-use super::*;
-use crate::{
-    autofill::AutoFillFn,
-    component::{
-        add_component::AddComponent,
-        command_list::{CommandListComponent, EditCommand},
-        selected_table::TableMetadata,
-        table_display::MultiTableSelection,
-    },
-    connection::{ColumnInfo, Connection},
-    value::Value,
-};
-use editable_text::EditableText;
-use table_display::TableDisplay;
-
-use ratatui::{
-    layout::{Constraint, Direction, Layout},
-    widgets::Paragraph,
-};
-use rusqlite::{params_from_iter, types::Value as RsqValue};
-
-#[derive(PartialEq)]
-enum FocusArea {
-    Commands,
-    Main,
-}
-
-pub struct DatabaseComp {
-    add_component: Option<AddComponent>,
-    autofill_funcs: HashMap<&'static str, AutoFillFn>,
-    cell_display: Option<EditableText>,
-    column_info: Vec<ColumnInfo>,
-    command_list: CommandListComponent,
-    connection: Connection,
-    focus: FocusArea,
-    focusing_editor: bool,
-    max_selections: usize,
-    query: Option<String>,
-    table: Option<TableDisplay>,
-    table_name: String,
-    uses_rows: bool,
-}
-
-impl DatabaseComp {
-    /// Creates a new database viewing component with its table data
-    /// uninstantiated. To query the table initially,
-    /// `BaseDatabaseComponent.filter` must be called.
-    pub fn new(
-        table_name: &str,
-        max_selections: usize,
-        uses_rows: bool,
-    ) -> Result<Self, Box<dyn Error>> {
-        let connection = Connection::new()?;
-        Ok(Self {
-            add_component: None,
-            autofill_funcs: HashMap::with_capacity(0),
-            cell_display: None,
-            column_info: Vec::new(),
-            command_list: CommandListComponent::new(Vec::new()),
-            connection,
-            focus: FocusArea::Main,
-            focusing_editor: false,
-            max_selections,
-            query: None,
-            table: None,
-            table_name: table_name.to_owned(),
-            uses_rows,
-        })
-    }
-
-    /// Updates the passed components of the app to display the passed table
-    /// and its associated edit commands.
-    pub fn change_table_used(&mut self, table: &TableMetadata) -> Result<(), Box<dyn Error>> {
-        self.command_list.change_commands(table.commands.clone());
-        self.autofill_funcs = table.autofill_funcs.clone();
-        self.unfocus_editor();
-        if let Some(table) = &mut self.table {
-            table.reset_selections();
-            // TODO: MAY WANT TO CHANGE THIS SO THAT STATE FROM THE ADD SCREEN IS STORED
-            //       INSTEAD OF DESTROYED WHEN EDIT CHOICES ARE CHANGED
-            self.add_component = None;
-        }
-        if let Some(command) = self.command_list.selected() {
-            self.set_max_selections(command.num_selections());
-        }
-        self.change_stored_table(table.table_name)?;
-        // initially there is no filtering query, so just refresh and select all
-        self.refresh()?;
-        // now that the table is setup, make the reader show cell (0, 0)
-        self.update_cell_display();
-        Ok(())
-    }
-
-    /// Calls the previously stored query again if there is one present,
-    /// otherwise simply queries to select all rows from the table
-    pub fn refresh(&mut self) -> Result<(), Box<dyn Error>> {
-        let (query, selections_opt): (&String, Option<&[MultiTableSelection]>) =
-            if let Some(stored_query) = self.query.as_ref() {
-                // as refresh is calling the stored query and not a new one
-                // we can guarantee that the selections should stay the same
-                // as we update selections within any modifying function
-
-                // TODO: DETERMINE HOW I WANT THIS TO BE DONE AS THE ADD COMPONENT
-                //       HAS NO NOTION OF WHAT SELECTIONS ARE PRESENT AND SO CANNOT
-                //       SHIFT ANY ONES WHICH OCCUR AFTER IT AS OF RIGHT NOW.
-                //       MAYBE ADD AN ACTION TO SHIFT THE SELECTIONS WHICH OCCUR AFTER
-                //       THE INDEX RETURNED BY THE CALL TO INSERT (doesn't work with ORDER BY)
-                // let prev_selections = self.table.as_ref().map(|table| table.selections());
-                // (stored_query, prev_selections)
-                (stored_query, None)
-            } else {
-                // reset the query to the default one, and do not carry over selections
-                self.query = Some(format!("SELECT * FROM {};", self.table_name));
-                (self.query.as_ref().unwrap(), None)
-            };
-        let mut new_table = TableDisplay::from_table(
-            self.connection.query(query, [])?,
-            self.uses_rows,
-            self.max_selections,
-        )?;
-        if let Some(selections) = selections_opt {
-            // if there are selections to carry over, select each one with the new table
-            selections
-                .iter()
-                .for_each(|selection| new_table.select(*selection));
-        }
-        self.table = Some(new_table);
-        Ok(())
-    }
-
-    /// Creates a string denoting the positional arguments which specify
-    /// the primary keys for the table in the format of
-    ///
-    ///     "COL_NAME = ?IND AND COL_NAME = ?IND AND ..."
-    ///
-    /// alongside the list of Rusqlite Values for the passed row which
-    /// may be bound to the positional args in a prepared statement.
-    ///
-    /// It is an error to call this with no table present
-    fn pk_positional_args(&self, row: usize, start_offset: usize) -> (String, Vec<RsqValue>) {
-        assert!(
-            self.table.is_some(),
-            "Attempting to get positional args for a table which doesn't exist"
-        );
-
-        let (pos, params): (Vec<String>, Vec<RsqValue>) = self
-            .column_info
-            .iter()
-            .enumerate()
-            .filter_map(|(ind, info)| {
-                if info.is_primary_key {
-                    // as the column name is taken directly from pragma_table_info,
-                    // the column should be present within the columns
-                    // create positional argument in the form of "COL_NAME = ?IND"
-                    Some((
-                        format!("{} = ?{}", info.name, ind + start_offset + 1),
-                        self.table
-                            .as_ref()
-                            .unwrap()
-                            .table
-                            .row_get(row, &info.name)
-                            .expect("Somehow pragma_table_info has a bad column name")
-                            .into(),
-                    ))
-                } else {
-                    None
-                }
-            })
-            .unzip();
-        (pos.join(" AND "), params)
-    }
-
-    /// Deletes the currently selected row from the table within the database.
-    /// Only works if there is 1 selected row for now.
-    /// Returns true if a row was removed, false if not
-    fn delete(&mut self) -> Result<bool, Box<dyn Error>> {
-        // only allow removal of a row, not a cell
-        assert!(self.uses_rows);
-
-        if let Some(table) = &self.table {
-            if table.selections().len() != 1 {
-                return Ok(false);
-            }
-            if let MultiTableSelection::Row(row) = table.selections()[0] {
-                let (pos, params) = self.pk_positional_args(row, 0);
-
-                // DELETE FROM table WHERE col_name1 = value1 AND col_name2 = value2 LIMIT num;
-                let query = format!(
-                    // "DELETE FROM {} WHERE {} LIMIT 1;",
-                    "DELETE FROM {} WHERE {};",
-                    self.table_name, pos
-                );
-                // TODO: maybe store the response to show as a thingy
-                self.connection.delete(&query, params_from_iter(params))?;
-                // refresh the database and update the command list
-                self.refresh()?;
-                return Ok(true);
-            }
-        }
-        Ok(false)
-    }
-
-    /// Filters the table's retrieved rows depending on the passed filter.
-    /// Filters should take the form of "WHERE ..." or "GROUP BY ...",
-    /// as those keywords are not included in the default filter.
-    /// Passing an empty filter will simply select all rows from the table.
-    fn filter(&mut self, filter: &str) -> Result<(), Box<dyn Error>> {
-        let query = format!("SELECT * FROM {} {};", self.table_name, filter);
-        let table = self.connection.query(&query, [])?;
-        // store the expanded_sql query for reuse if possible
-        self.query = table.query.clone();
-        self.table = Some(TableDisplay::from_table(
-            table,
-            self.uses_rows,
-            self.max_selections,
-        )?);
-        Ok(())
-    }
-
-    /// Updates the currently selected cell to have the value currently stored
-    /// in the editor, if that value is valid.
-    /// Requires there only be 1 selected cell.
-    /// Returns true if the cell was updated, false if not
-    fn submit_modify(&mut self) -> Result<bool, Box<dyn Error>> {
-        assert!(
-            self.table.is_some(),
-            "Attempting to modify a table which doesn't exist"
-        );
-        assert!(
-            self.cell_display.is_some(),
-            "Trying to submit modification from an editor which doesn't exist"
-        );
-        let table = self.table.as_ref().unwrap();
-        let to_update: Option<(usize, usize, Value)>;
-        match table.selections() {
-            [MultiTableSelection::Cell((y, x))] => {
-                let (y, x) = (*y, *x);
-                let (pos, params) = self.pk_positional_args(y, 1);
-                // UPDATE table SET col_name = value WHERE pk_name = pk_val;
-                let query = format!(
-                    "UPDATE {} SET {} = ?1 WHERE {};",
-                    self.table_name,
-                    table.columns()[x],
-                    pos
-                );
-
-                let editor = self.cell_display.as_ref().unwrap();
-                if self.column_info[x].is_not_null && editor.is_empty() {
-                    // there is a required field that is empty, so don't allow change
-                    return Ok(false);
-                }
-                // validate the column has a proper value
-                if let Ok(new_val) =
-                    Value::parse_column(&self.column_info[x].data_type, &editor.text())
-                {
-                    // do nothing if the value wasn't changed
-                    if new_val == table.rows()[y][x] {
-                        return Ok(true);
-                    }
-                    self.connection.modify(
-                        &query,
-                        params_from_iter(std::iter::once((&new_val).into()).chain(params)),
-                    )?;
-                    to_update = Some((y, x, new_val));
-                } else {
-                    return Ok(false);
-                }
-            }
-            _ => panic!("Trying to edit a whole row or multiple cells at once"),
-        }
-
-        // update the content of the stored cell instead of refreshing the whole table
-        let table = self.table.as_mut().unwrap();
-        if let Some((y, x, val)) = to_update {
-            table.table.rows[y][x] = val;
-        }
-        Ok(true)
-    }
-
-    /// Shifts focus to the next focusable component.
-    /// Returns true if at the end of its selection of focusable components
-    /// and its containing component should move to its next component,
-    /// false if this was able to change focus
-    pub fn next_focus(&mut self) -> bool {
-        match self.focus {
-            FocusArea::Commands => {
-                self.focus = FocusArea::Main;
-                false
-            }
-            FocusArea::Main => true,
-        }
-    }
-
-    /// Shifts focus to the previous focusable component.
-    /// Returns true if at the end of its selection of focusable components
-    /// and its containing component should move to its previous component,
-    /// false if this was able to change focus
-    pub fn prev_focus(&mut self) -> bool {
-        match self.focus {
-            FocusArea::Main => {
-                self.focus = FocusArea::Commands;
-                false
-            }
-            FocusArea::Commands => true,
-        }
-    }
-
-    pub fn focus_first(&mut self) {
-        self.focus = FocusArea::Commands;
-    }
-
-    pub fn focus_last(&mut self) {
-        self.focus = FocusArea::Main;
-    }
-
-    /// Updates the number of selections to hold the new max number.
-    /// Truncates the list, removing the more recent selections, if new_max is
-    /// less than the current max selections.
-    fn set_max_selections(&mut self, new_max: usize) {
-        if self.max_selections == new_max {
-            return;
-        }
-        if let Some(table) = &mut self.table {
-            table.set_max_selections(new_max);
-        }
-        self.max_selections = new_max;
-    }
-
-    /// Updates the selection type to be the new type.
-    /// Removes selections of the old type if it is changed.
-    fn set_selection_type(&mut self, use_rows: bool) {
-        if self.uses_rows == use_rows {
-            return;
-        }
-        if let Some(table) = &mut self.table {
-            table.set_selection_type(use_rows);
-        }
-        self.uses_rows = use_rows;
-    }
-
-    /// Changes the table stored to be the passed one, and reverts the
-    /// stored query to the default one.
-    fn change_stored_table(&mut self, table_name: &str) -> Result<(), Box<dyn Error>> {
-        if table_name != self.table_name {
-            self.table_name = table_name.to_owned();
-            self.query = None;
-            // update column info
-            self.column_info = self.connection.get_column_info(table_name)?;
-        }
-        Ok(())
-    }
-
-    /// Hides/Shows the add component depending on the newly selected command,
-    /// focuses the main section (table), and ensures the editor is not selected.
-    /// Should only be called if the edit command changed to something different
-    fn handle_edit_command_change(&mut self) {
-        if let Some(command) = self.command_list.selected() {
-            match command {
-                EditCommand::Add => match AddComponent::new(&self.table_name) {
-                    Err(err) => panic!("{:?}", err),
-                    Ok(add_comp) => self.add_component = Some(add_comp),
-                },
-                _ => {
-                    // TODO: MAY WANT TO CHANGE THIS SO THAT STATE FROM THE ADD SCREEN IS STORED
-                    //       INSTEAD OF DESTROYED WHEN EDIT CHOICES ARE CHANGED
-                    self.add_component = None;
-                    self.set_max_selections(command.num_selections());
-                    self.set_selection_type(command.uses_rows());
-                }
-            }
-            // change the focused element to be the table now
-            self.focus = FocusArea::Main;
-            self.unfocus_editor();
-            // remove all selections
-            if let Some(table) = &mut self.table {
-                table.reset_selections();
-            }
-        }
-    }
-
-    /// Runs upon handling a SelectionChanged Action
-    fn handle_table_selection(&mut self) -> Result<(), Box<dyn Error>> {
-        let command = self
-            .command_list
-            .selected()
-            .expect("Should be unable to change selection without an edit mode selected");
-        match command {
-            EditCommand::Delete => {
-                // delete the selected item
-                self.delete()?;
-                Ok(())
-            }
-            EditCommand::Modify => {
-                self.focusing_editor = true;
-                if let Some(editor) = &mut self.cell_display {
-                    editor.toggle_focus();
-                }
-                Ok(())
-            }
-            _ => Ok(()), // do nothing for most
-        }
-    }
-
-    // Runs when the highlit cell within the table changes
-    fn update_cell_display(&mut self) {
-        if let Some(table) = &self.table {
-            if let Some(highlit_cell) = table.highlit_cell_value() {
-                let col_name = table
-                    .highlit_col_name()
-                    .expect("Cell is highlit but no column name was available");
-                let autofill = self.autofill_funcs.get(col_name.as_str()).cloned();
-                self.cell_display = Some(EditableText::new(&highlit_cell, autofill));
-            }
-        }
-    }
-
-    fn unfocus_editor(&mut self) {
-        self.update_cell_display();
-        self.focusing_editor = false;
-    }
-
-    fn handle_actions(&mut self, actions: Vec<Action>) -> Vec<Action> {
-        // handle the actions which may be returned by the add component or the commandlist
-        let mut actions = actions;
-        // loops over the actions in order, removing any which return false (which are handled),
-        // returning the list of actions which weren't handled
-        actions.retain(|action| match action {
-            Action::ChangeEditCommand => {
-                self.handle_edit_command_change();
-                false
-            }
-            Action::RevertCommandSelection => {
-                self.command_list.revert_selection();
-                false
-            }
-            Action::RevertToMain => {
-                // TODO: MAY WANT TO CHANGE THIS SO THAT STATE FROM THE ADD SCREEN IS STORED
-                //       INSTEAD OF DESTROYED WHEN EDIT CHOICES ARE CHANGED
-                self.add_component = None;
-                false
-            }
-            _ => true,
-        });
-        actions
-    }
-}
-
-impl Component for DatabaseComp {
-    fn handle_event(&mut self, event: Action) -> Result<Vec<Action>, Box<dyn Error>> {
-        match self.focus {
-            FocusArea::Commands => {
-                let actions = self.command_list.handle_event(event)?;
-                Ok(self.handle_actions(actions))
-            }
-            FocusArea::Main => {
-                // handle the add component if there is one showing
-                if let Some(add_comp) = &mut self.add_component {
-                    let actions = add_comp.handle_event(event)?;
-                    return Ok(self.handle_actions(actions));
-                }
-                match event {
-                    Action::Noop => Ok(vec![Action::Noop]),
-                    Action::Quit => Ok(vec![Action::Quit]),
-                    Action::KeyEvent(key_event) => {
-                        if !self.focusing_editor {
-                            self.handle_key_event(key_event)
-                        } else {
-                            match key_event.code {
-                                KeyCode::Esc => {
-                                    self.unfocus_editor();
-                                    if let Some(table) = &mut self.table {
-                                        table.reset_selections();
-                                    }
-                                    Ok(vec![Action::Noop])
-                                }
-                                KeyCode::Enter => {
-                                    if self.submit_modify()? {
-                                        self.unfocus_editor();
-                                        if let Some(table) = &mut self.table {
-                                            table.reset_selections();
-                                        }
-                                        Ok(vec![Action::Noop])
-                                    } else {
-                                        Ok(vec![Action::VeryLoudWrongBuzzer])
-                                    }
-                                }
-                                _ => {
-                                    if let Some(editor) = &mut self.cell_display {
-                                        editor.handle_key_event(key_event)
-                                    } else {
-                                        panic!("Somehow focusing editor without editor present");
-                                    }
-                                }
-                            }
-                        }
-                    }
-                    Action::OtherEvent(other_event) => self.handle_other_event(other_event),
-                    // Action::Filter(filter) => {
-                    //     self.filter(&filter)?;
-                    //     Ok(vec![Action::Noop])
-                    // }
-                    unhandled => Err(Box::new(UnhandledActionError::new(unhandled))),
-                }
-            }
-        }
-    }
-
-    fn handle_key_event(&mut self, key: KeyEvent) -> Result<Vec<Action>, Box<dyn Error>> {
-        if let Some(table) = &mut self.table {
-            let mut actions = table.handle_key_event(key)?;
-            // handle any changes of highlight or selection in the table within this component
-            let mut highlight_changed = false;
-            let mut selection_changed = false;
-            actions.retain(|a| match a {
-                Action::HighlightChanged => {
-                    highlight_changed = true;
-                    false
-                }
-                Action::SelectionChanged => {
-                    selection_changed = true;
-                    false
-                }
-                _ => true,
-            });
-            if highlight_changed {
-                self.update_cell_display();
-            }
-            if selection_changed {
-                self.handle_table_selection()?;
-            }
-            Ok(actions)
-        } else {
-            Ok(vec![Action::Noop])
-        }
-    }
-
-    fn render(&mut self, f: &mut Frame, rect: Rect, block: Block) {
-        // split the passed rect for the edits commands and the table itself
-        let [commands_rect, main_rect, ..] = *Layout::default()
-            .margin(0)
-            .direction(Direction::Vertical)
-            .constraints([
-                Constraint::Length(3), // 3 pixels of height for the list of commands
-                Constraint::Min(7),    // At least 7 pixels of height for the rest
-            ])
-            .split(rect)
-        else {
-            panic!("Not enough size to create the necessary rects");
-        };
-
-        if self.table.is_none() {
-            f.render_widget(
-                Paragraph::new("No table queried").centered().block(block),
-                rect,
-            );
-            return;
-        }
-
-        let table = self.table.as_mut().unwrap();
-        // uses the passed block for the potentially focused component as
-        // the block will be unfocused if this component is not focused
-        let (commands_block, main_block) = match self.focus {
-            FocusArea::Commands => (block, DEFAULT_APP_COLORS.default_block()),
-            FocusArea::Main => (DEFAULT_APP_COLORS.default_block(), block),
-        };
-        self.command_list.render(f, commands_rect, commands_block);
-        if let Some(add_comp) = &mut self.add_component {
-            // render the add component if it is shown
-            add_comp.render(f, main_rect, main_block);
-        } else if let Some(cell_display) = &mut self.cell_display {
-            // split the main_rect to show the cell display
-            let [table_rect, mut cell_display_rect, ..] = *Layout::default()
-                .margin(1) // 1 margin to account for border
-                .direction(Direction::Horizontal)
-                .constraints([
-                    Constraint::Percentage(75), // table takes up 75% of main area
-                    Constraint::Min(8),         // cell display requires at least 8 cols width
-                ])
-                .split(main_rect)
-            else {
-                panic!("Not enough size to create the necessary rects");
-            };
-            // render the main border block separately
-            f.render_widget(main_block.bg(DEFAULT_APP_COLORS.main_bg), main_rect);
-            // allot space for the title of the cell display
-            let mut cell_display_title_rect = cell_display_rect;
-            cell_display_title_rect.height = 1;
-            cell_display_rect.height = cell_display_rect.height.saturating_sub(1);
-            cell_display_rect.y += 1;
-            cell_display_rect.width = cell_display_rect.width.saturating_sub(1);
-            cell_display_rect.x += 1;
-            let display_title = if self.focusing_editor {
-                "Editor"
-            } else {
-                "Reader"
-            };
-            f.render_widget(
-                Paragraph::new(display_title)
-                    .bg(DEFAULT_APP_COLORS.header_bg)
-                    .fg(DEFAULT_APP_COLORS.header_fg)
-                    .centered(),
-                cell_display_title_rect,
-            );
-            cell_display.render(f, cell_display_rect, Block::new());
-            table.render(f, table_rect, Block::new());
-        } else {
-            table.render(f, main_rect, main_block);
-        }
-    }
-}
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+    io,
+    path::PathBuf,
+    sync::{
+        Arc,
+        mpsc::{self, Receiver, TryRecvError},
+    },
+    thread,
+    time::Instant,
+};
+
+use super::*;
+use crate::{
+    autofill::{AutoFillFn, value_autofill_factory},
+    component::{
+        add_component::AddComponent,
+        column_picker::ColumnPickerPopup,
+        command_list::{CommandListComponent, EditCommand},
+        filter_bar::FilterBar,
+        input_popup::InputPopup,
+        popup::PopUpComponent,
+        selected_table::TableMetadata,
+        table_diff::TableDiff,
+        table_display::{MultiTableSelection, SortDirection},
+    },
+    config::{DEFAULT_UNDO_DEPTH, NOTIFICATION_DURATION, STATUS_MESSAGE_DURATION},
+    connection::{ColumnInfo, Connection, Table, escape_like, quote_identifier, quote_table},
+    export::write_csv,
+    history::{Change, RowSnapshot, UndoStack},
+    import::import_csv,
+    value::{DisplayFormatFn, Value, ValueType},
+};
+use editable_text::EditableText;
+use table_display::TableDisplay;
+
+use ratatui::{
+    crossterm::event::KeyModifiers,
+    layout::{Constraint, Direction, Layout, Margin},
+    text::Line,
+    widgets::{Clear, Paragraph, Wrap},
+};
+use rusqlite::{params_from_iter, types::Value as RsqValue};
+
+#[derive(PartialEq)]
+enum FocusArea {
+    Commands,
+    Main,
+    /// Editing the raw SQL query used for the table, in place of the table
+    QueryEditor,
+}
+
+/// What the value entered into the input popup is used for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum InputPurpose {
+    /// Directly replaces the value of the selected cell
+    ModifyCell,
+    /// Filters the table to rows whose column at the index contains the value
+    SearchColumn(usize),
+    /// Filters the table using the value as a WHERE or GROUP BY clause
+    Filter,
+    /// Exports the shown table as CSV to the file at the value's path
+    ExportCsv,
+    /// Inserts the rows of the CSV file at the value's path into the table
+    ImportCsv,
+}
+
+/// What is being confirmed by the confirmation popup
+#[derive(Debug, Clone, PartialEq)]
+enum ConfirmPurpose {
+    /// Deletes every selected row
+    DeleteRows,
+    /// Sets the cell at (y, x) to the value
+    ModifyCell(usize, usize, Value),
+    /// Reorders the selected rows into the order they were selected in
+    ReorderRows,
+    /// Only reports how many rows were imported, with nothing to confirm
+    ImportSummary,
+}
+
+/// Most columns the table can be sorted by at once
+const MAX_SORT_COLUMNS: usize = 3;
+/// Frames of the spinner shown while a query is running, one per render
+const SPINNER_FRAMES: [&str; 8] = ["⣾", "⣽", "⣻", "⢿", "⡿", "⣟", "⣯", "⣷"];
+
+/// Turns a value which couldn't be parsed as its column's type into a status
+/// message explaining why, passing along any other error
+fn reject_unparsable(err: AppError) -> Result<Vec<Action>, AppError> {
+    match err {
+        AppError::ParseColumnError(err) => Ok(vec![
+            Action::VeryLoudWrongBuzzer,
+            Action::StatusMessage(err.to_string()),
+        ]),
+        err => Err(err),
+    }
+}
+
+pub struct DatabaseComp {
+    add_component: Option<AddComponent>,
+    /// Whether the loading spinner is animated, rather than shown as text
+    animations: bool,
+    autofill_funcs: HashMap<&'static str, AutoFillFn>,
+    /// Whether changes are held in a transaction until committed or rolled
+    /// back together, rather than being saved as they're made
+    batch_mode: bool,
+    /// Formats the displayed values of the table's columns
+    display_format_funcs: HashMap<&'static str, DisplayFormatFn>,
+    /// Bar filtering the table as a search term is typed, if it is open
+    filter_bar: Option<FilterBar>,
+    /// Add component which was hidden by switching to another edit command,
+    /// kept so its partially filled fields can be restored
+    cached_add_component: Option<AddComponent>,
+    cell_display: Option<EditableText>,
+    column_info: Vec<ColumnInfo>,
+    /// Popup choosing which columns of the table are shown, if it is open
+    column_picker: Option<ColumnPickerPopup>,
+    command_list: CommandListComponent,
+    confirm_popup: Option<(ConfirmPurpose, PopUpComponent)>,
+    /// Comparison of the row before and after the change being confirmed,
+    /// shown above the confirmation popup when modifying a cell
+    confirm_diff: Option<TableDiff>,
+    /// Whether the shown add component duplicates a row without the
+    /// add command being selected
+    duplicating_row: bool,
+    connection: Arc<Connection>,
+    /// Explains why nothing is shown when there's no table or it has no rows
+    empty_state_message: String,
+    /// Error shown over the main section, alongside when it was shown
+    error_overlay: Option<(String, Instant)>,
+    /// Receives the result of the export being written on another thread,
+    /// alongside the path and number of rows it's writing
+    pending_export: Option<(Receiver<io::Result<()>>, PathBuf, usize)>,
+    focus: FocusArea,
+    focusing_editor: bool,
+    /// Number of ticks passed so far, used to animate the spinner
+    frame_count: usize,
+    has_rowid: bool,
+    /// Changes made to the database which can be undone and redone
+    history: UndoStack,
+    input_popup: Option<(InputPurpose, InputPopup)>,
+    /// Whether the table's query is still running, showing a spinner in
+    /// place of the table
+    is_loading: bool,
+    max_selections: usize,
+    /// Column holding the display order of the rows, used when reordering
+    order_column: Option<String>,
+    pinned_keys: Vec<Vec<Value>>,
+    query: Option<String>,
+    /// Values bound to the positional args of the stored query
+    query_params: Vec<RsqValue>,
+    /// Editor for the raw SQL query, shown while focusing the query editor
+    query_editor: Option<EditableText>,
+    /// Whether the shown rows are the result of a query typed into the query
+    /// editor rather than rows of the stored table, whose columns needn't
+    /// match the stored column info and so can't be changed
+    raw_result: bool,
+    /// Columns the table is sorted by, applied on top of the stored query
+    /// from the primary sort to the last tie-breaker
+    sort_state: Vec<(usize, SortDirection)>,
+    table: TableDisplay,
+    table_name: String,
+    /// Number of rows in the whole table, regardless of any filter
+    total_row_count: Option<usize>,
+    uses_rows: bool,
+    /// Autofill functions completing from the values already within each
+    /// column which has no autofill function of its own
+    value_autofill_funcs: HashMap<String, AutoFillFn>,
+}
+
+impl DatabaseComp {
+    /// Creates a new database viewing component with its table data
+    /// uninstantiated. To query the table initially,
+    /// `BaseDatabaseComponent.filter` must be called.
+    pub fn new(
+        connection: Arc<Connection>,
+        table_name: &str,
+        max_selections: usize,
+        uses_rows: bool,
+    ) -> Result<Self, AppError> {
+        Ok(Self {
+            add_component: None,
+            animations: true,
+            autofill_funcs: HashMap::with_capacity(0),
+            batch_mode: false,
+            display_format_funcs: HashMap::with_capacity(0),
+            filter_bar: None,
+            cached_add_component: None,
+            cell_display: None,
+            column_info: Vec::new(),
+            column_picker: None,
+            command_list: CommandListComponent::new(Vec::new()),
+            confirm_popup: None,
+            confirm_diff: None,
+            duplicating_row: false,
+            connection,
+            empty_state_message: "No tables configured".to_string(),
+            error_overlay: None,
+            pending_export: None,
+            focus: FocusArea::Main,
+            focusing_editor: false,
+            frame_count: 0,
+            has_rowid: true,
+            history: UndoStack::new(DEFAULT_UNDO_DEPTH),
+            input_popup: None,
+            is_loading: false,
+            max_selections,
+            order_column: None,
+            pinned_keys: Vec::new(),
+            query: None,
+            query_params: Vec::new(),
+            query_editor: None,
+            raw_result: false,
+            sort_state: Vec::new(),
+            table: TableDisplay::new_empty(uses_rows, max_selections),
+            table_name: table_name.to_owned(),
+            total_row_count: None,
+            uses_rows,
+            value_autofill_funcs: HashMap::with_capacity(0),
+        })
+    }
+
+    /// Sets the message shown in place of the table when it has no rows
+    pub fn set_empty_state(&mut self, msg: &str) {
+        self.empty_state_message = msg.to_string();
+    }
+
+    /// Sets whether the loading spinner is animated
+    pub fn set_animations(&mut self, animations: bool) {
+        self.animations = animations;
+    }
+
+    /// Sets how many changes to the database can be undone
+    pub fn set_undo_depth(&mut self, depth: usize) {
+        self.history.set_max_depth(depth);
+    }
+
+    /// Reverses the most recent change made to the database, which is
+    /// skipped if it was made to a table other than the shown one
+    pub fn undo(&mut self) -> Result<Vec<Action>, AppError> {
+        if !self.history.undo(&self.connection, &self.table_name)? {
+            return Ok(vec![Action::Noop]);
+        }
+        self.refresh_preserving_position()?;
+        Ok(vec![Action::StatusMessage(
+            "Undid the last change".to_string(),
+        )])
+    }
+
+    /// Makes the most recently undone change again, which is skipped if it
+    /// was made to a table other than the shown one
+    pub fn redo(&mut self) -> Result<Vec<Action>, AppError> {
+        if !self.history.redo(&self.connection, &self.table_name)? {
+            return Ok(vec![Action::Noop]);
+        }
+        self.refresh_preserving_position()?;
+        Ok(vec![Action::StatusMessage(
+            "Redid the last change".to_string(),
+        )])
+    }
+
+    /// Returns true if changes are being held until the batch is committed
+    pub fn in_batch_mode(&self) -> bool {
+        self.batch_mode
+    }
+
+    /// Begins holding changes in a batch, or commits the open batch
+    fn toggle_batch_mode(&mut self) -> Result<Vec<Action>, AppError> {
+        if self.batch_mode {
+            return self.commit_batch();
+        }
+        self.connection.begin()?;
+        self.batch_mode = true;
+        Ok(vec![Action::StatusMessage(
+            "Batch mode on: ctrl+b or ctrl+enter commits the changes, ctrl+esc discards them"
+                .to_string(),
+        )])
+    }
+
+    /// Saves every change made during the batch to the database
+    fn commit_batch(&mut self) -> Result<Vec<Action>, AppError> {
+        if !self.batch_mode {
+            return Ok(vec![Action::VeryLoudWrongBuzzer]);
+        }
+        self.connection.commit()?;
+        self.batch_mode = false;
+        self.refresh_preserving_position()?;
+        Ok(vec![Action::StatusMessage(
+            "Committed the batch".to_string(),
+        )])
+    }
+
+    /// Discards every change made during the batch
+    fn rollback_batch(&mut self) -> Result<Vec<Action>, AppError> {
+        if !self.batch_mode {
+            return Ok(vec![Action::VeryLoudWrongBuzzer]);
+        }
+        self.connection.rollback()?;
+        self.batch_mode = false;
+        // the history can't tell which changes were made during the batch,
+        // and those no longer exist to be undone
+        self.history.clear();
+        self.refresh_preserving_position()?;
+        Ok(vec![Action::StatusMessage(
+            "Discarded the changes made during the batch".to_string(),
+        )])
+    }
+
+    /// Returns true if keys are being typed into some text, such as the
+    /// cell editor, the query editor, the add component, or an input
+    pub fn is_taking_text(&self) -> bool {
+        self.focusing_editor
+            || self.query_editor.is_some()
+            || self.add_component.is_some()
+            || self.filter_bar.is_some()
+            || self.input_popup.is_some()
+    }
+
+    /// Returns the edit command currently selected, if any
+    pub fn selected_command(&self) -> Option<EditCommand> {
+        self.command_list.selected()
+    }
+
+    /// Returns true if the database was opened without allowing writes, in
+    /// which case the edit commands are hidden and changes are refused
+    pub fn is_read_only(&self) -> bool {
+        self.connection.is_read_only()
+    }
+
+    /// Returns the digits of the row number being typed to jump to, if any
+    pub fn jump_buffer(&self) -> Option<&str> {
+        self.table.jump_buffer()
+    }
+
+    /// Returns the (y, x) coordinate of the highlit cell, alongside the
+    /// number of rows and columns within the shown table
+    pub fn table_position(&self) -> (Option<(usize, usize)>, (usize, usize)) {
+        let table = &self.table.table;
+        (
+            self.table.highlit_cell(),
+            (table.rows.len(), table.columns.len()),
+        )
+    }
+
+    /// Sets whether the edit commands are displayed with their icons
+    pub fn set_show_command_icons(&mut self, show_icons: bool) {
+        self.command_list.set_show_icons(show_icons);
+    }
+
+    /// Updates the passed components of the app to display the passed table
+    /// and its associated edit commands.
+    pub fn change_table_used(&mut self, table: &TableMetadata) -> Result<(), AppError> {
+        let columns = self.connection.get_columns(table.table_name)?;
+        let mut commands = table.available_commands(columns.len());
+        if self.is_read_only() {
+            // only offer the commands which can't change the table, as views do
+            commands.retain(|command| *command == EditCommand::Search);
+            commands.insert(0, EditCommand::Filter);
+        }
+        self.command_list.change_commands(commands);
+        self.autofill_funcs = table.resolve_autofill_funcs(&self.connection);
+        // columns without their own autofill complete from their values
+        self.value_autofill_funcs = columns
+            .into_iter()
+            .filter(|column| !self.autofill_funcs.contains_key(column.as_str()))
+            .map(|column| {
+                let autofill =
+                    value_autofill_factory(self.connection.clone(), table.table_name, &column);
+                (column, autofill)
+            })
+            .collect();
+        self.display_format_funcs = table.display_format_funcs.clone();
+        self.order_column = table.resolve_order_column(&self.connection)?;
+        self.unfocus_editor();
+        self.confirm_popup = None;
+        self.confirm_diff = None;
+        self.filter_bar = None;
+        self.table.reset_selections();
+        // the fields of the add component belong to the previous table
+        self.add_component = None;
+        self.cached_add_component = None;
+        self.duplicating_row = false;
+        if let Some(command) = self.command_list.selected() {
+            self.set_max_selections(command.num_selections());
+        }
+        self.change_stored_table(table.table_name)?;
+        // initially there is no filtering query, so just refresh and select all
+        self.refresh()?;
+        self.set_empty_state("This table has no rows");
+        // now that the table is setup, make the reader show cell (0, 0)
+        self.update_cell_display();
+        Ok(())
+    }
+
+    /// Refreshes the table as with [`DatabaseComp::refresh`], then highlights
+    /// the same cell as before, clamped to the bounds of the new table
+    pub fn refresh_preserving_position(&mut self) -> Result<(), AppError> {
+        let highlit = self.table.highlit_cell();
+        self.refresh()?;
+        if let Some((y, x)) = highlit {
+            self.table.jump_to_row(y);
+            self.table
+                .highlight_column(x.min(self.table.columns().len().saturating_sub(1)));
+        }
+        self.update_cell_display();
+        Ok(())
+    }
+
+    /// Calls the previously stored query again if there is one present,
+    /// otherwise simply queries to select all rows from the table
+    pub fn refresh(&mut self) -> Result<(), AppError> {
+        let (query, selections_opt): (&String, Option<&[MultiTableSelection]>) =
+            if let Some(stored_query) = self.query.as_ref() {
+                // as refresh is calling the stored query and not a new one
+                // we can guarantee that the selections should stay the same
+                // as we update selections within any modifying function
+
+                // TODO: DETERMINE HOW I WANT THIS TO BE DONE AS THE ADD COMPONENT
+                //       HAS NO NOTION OF WHAT SELECTIONS ARE PRESENT AND SO CANNOT
+                //       SHIFT ANY ONES WHICH OCCUR AFTER IT AS OF RIGHT NOW.
+                //       MAYBE ADD AN ACTION TO SHIFT THE SELECTIONS WHICH OCCUR AFTER
+                //       THE INDEX RETURNED BY THE CALL TO INSERT (doesn't work with ORDER BY)
+                // let prev_selections = Some(self.table.selections());
+                // (stored_query, prev_selections)
+                (stored_query, None)
+            } else {
+                // reset the query to the default one, and do not carry over selections
+                self.query = Some(format!("SELECT * FROM {};", quote_table(&self.table_name)));
+                self.query_params.clear();
+                self.raw_result = false;
+                (self.query.as_ref().unwrap(), None)
+            };
+        // queries currently block, so nothing is rendered until it finishes
+        self.is_loading = true;
+        let result = self.connection.query(
+            &self.sorted_query(query),
+            params_from_iter(self.query_params.iter()),
+        );
+        self.is_loading = false;
+        let mut new_table = self.create_table_display(result?);
+        if let Some(selections) = selections_opt {
+            // if there are selections to carry over, select each one with the new table
+            selections
+                .iter()
+                .for_each(|selection| new_table.select(*selection));
+        }
+        self.table = new_table;
+        self.total_row_count = Some(self.connection.get_row_count(&self.table_name)?);
+        self.apply_pins();
+        Ok(())
+    }
+
+    /// Creates the display for a newly queried table, matching the
+    /// selection rules, sort, and formats of the current one
+    fn create_table_display(&self, table: Table) -> TableDisplay {
+        let mut new_table = TableDisplay::from_table(table, self.uses_rows, self.max_selections);
+        new_table.set_require_unique_rows(self.requires_unique_rows());
+        if self.command_list.selected() == Some(EditCommand::Delete) {
+            // deleting is destructive, so guard against accidental selections
+            new_table = new_table.with_require_double_enter();
+        }
+        new_table.set_sort_columns(self.sort_state.clone());
+        new_table.set_column_width_overrides(self.table.column_width_overrides().to_vec());
+        new_table.set_hidden_columns(self.table.hidden_columns().clone());
+        new_table.set_display_formats(self.display_format_funcs.clone());
+        new_table
+    }
+
+    /// Wraps the passed query to order its rows by the sorted columns,
+    /// leaving it unchanged if the table isn't sorted
+    fn sorted_query(&self, query: &str) -> String {
+        if self.sort_state.is_empty() {
+            return query.to_owned();
+        }
+        let order: Vec<String> = self
+            .sort_state
+            .iter()
+            .map(|(x, direction)| {
+                format!(
+                    "\"{}\" {}",
+                    self.column_info[*x].name.replace('"', "\"\""),
+                    direction.keyword()
+                )
+            })
+            .collect();
+        format!(
+            "SELECT * FROM ({}) ORDER BY {};",
+            query.trim_end().trim_end_matches(';'),
+            order.join(", ")
+        )
+    }
+
+    /// Sorts the table by the highlit column alone, cycling through
+    /// ascending, descending, then the unsorted order of the stored query.
+    /// If additive, the column is instead added as a tie-breaker after the
+    /// columns already sorted by, or cycled if it already is one
+    fn cycle_sort(&mut self, additive: bool) -> Result<Vec<Action>, AppError> {
+        let Some(x) = self
+            .table
+            .highlit_col_name()
+            .and_then(|name| self.column_info.iter().position(|info| info.name == name))
+        else {
+            return Ok(vec![Action::VeryLoudWrongBuzzer]);
+        };
+        let sorted_ind = self
+            .sort_state
+            .iter()
+            .position(|(sorted_x, _)| *sorted_x == x);
+        match sorted_ind {
+            Some(ind) if additive || self.sort_state.len() == 1 => {
+                match self.sort_state[ind].1.cycle() {
+                    Some(direction) => self.sort_state[ind].1 = direction,
+                    None => {
+                        self.sort_state.remove(ind);
+                    }
+                }
+            }
+            None if additive => {
+                if self.sort_state.len() >= MAX_SORT_COLUMNS {
+                    return Ok(vec![Action::VeryLoudWrongBuzzer]);
+                }
+                self.sort_state.push((x, SortDirection::Asc));
+            }
+            _ => self.sort_state = vec![(x, SortDirection::Asc)],
+        }
+        self.refresh()?;
+        // keep the column highlit so it can be cycled again
+        self.table.highlight_column(x);
+        self.update_cell_display();
+        Ok(vec![Action::Noop])
+    }
+
+    /// Sorts the table by the column at the passed index, as pressing s
+    /// while the column is highlit does
+    fn sort_by_column(&mut self, x: usize) -> Result<Vec<Action>, AppError> {
+        if x >= self.column_info.len() {
+            return Ok(vec![Action::VeryLoudWrongBuzzer]);
+        }
+        self.table.highlight_column(x);
+        self.cycle_sort(false)
+    }
+
+    /// Runs an action chosen from the command palette, focusing the table
+    /// so any popup it opens receives the keys which follow
+    pub fn run_command(&mut self, action: Action) -> Result<Vec<Action>, AppError> {
+        self.focus_last();
+        match action {
+            Action::ExportCsv => Ok(self.open_export_popup()),
+            Action::OpenFilter => Ok(self.open_filter()),
+            Action::SortColumn(x) => self.sort_by_column(x),
+            unhandled => Err(AppError::unhandled_action(unhandled, self.name())),
+        }
+    }
+
+    /// Describes how many rows are shown, alongside the total number of rows
+    /// in the table if a filter hides some of them
+    fn row_count_text(&self) -> Option<String> {
+        let shown = self.table.rows().len();
+        match self.total_row_count {
+            Some(total) if total != shown => Some(format!("Showing {} / {} rows", shown, total)),
+            _ => Some(format!("{} rows", shown)),
+        }
+    }
+
+    /// Collects the values of the primary key columns within the passed row
+    fn pk_values(&self, row: usize) -> Vec<Value> {
+        self.column_info
+            .iter()
+            .filter(|info| info.is_primary_key)
+            .filter_map(|info| self.table.table.row_get(row, &info.name).cloned())
+            .collect()
+    }
+
+    /// Moves the rows which are pinned, if they are still present,
+    /// to the top of the displayed table
+    fn apply_pins(&mut self) {
+        if self.pinned_keys.is_empty() {
+            return;
+        }
+        let num_rows = self.table.rows().len();
+        let pinned_rows: Vec<usize> = self
+            .pinned_keys
+            .iter()
+            .filter_map(|key| (0..num_rows).find(|row| self.pk_values(*row) == *key))
+            .collect();
+        self.table.pin_rows(&pinned_rows);
+    }
+
+    /// Pins the highlit row to the top of the table, or unpins it if it
+    /// is already pinned
+    fn toggle_pin(&mut self) -> Result<Vec<Action>, AppError> {
+        let Some((row, _)) = self.table.highlit_cell() else {
+            return Ok(vec![Action::VeryLoudWrongBuzzer]);
+        };
+        let key = self.pk_values(row);
+        if key.is_empty() {
+            // rows can't be found again after a refresh without a primary key
+            return Ok(vec![Action::VeryLoudWrongBuzzer]);
+        }
+        if let Some(ind) = self.pinned_keys.iter().position(|pinned| *pinned == key) {
+            self.pinned_keys.remove(ind);
+        } else {
+            self.pinned_keys.push(key);
+        }
+        // requery so unpinned rows return to their original position
+        self.refresh()?;
+        self.update_cell_display();
+        Ok(vec![Action::Noop])
+    }
+
+    /// Creates a string denoting the positional arguments which specify
+    /// the primary keys for the table in the format of
+    ///
+    /// ```text
+    /// "COL_NAME = ?IND AND COL_NAME = ?IND AND ..."
+    /// ```
+    ///
+    /// alongside the list of Rusqlite Values for the passed row which
+    /// may be bound to the positional args in a prepared statement.
+    ///
+    /// If the table has no primary key, every column is used to identify the
+    /// row instead, only matching the first such row if the table has a rowid.
+    fn pk_positional_args(&self, row: usize, start_offset: usize) -> (String, Vec<RsqValue>) {
+        let has_primary_key = self.has_primary_key();
+        let (pos, params): (Vec<String>, Vec<RsqValue>) = self
+            .column_info
+            .iter()
+            .enumerate()
+            .filter_map(|(ind, info)| {
+                if info.is_primary_key || !has_primary_key {
+                    // as the column name is taken directly from pragma_table_info,
+                    // the column should be present within the columns
+                    // create positional argument in the form of "COL_NAME = ?IND",
+                    // using IS when matching every column so NULLs compare equal
+                    Some((
+                        format!(
+                            "{} {} ?{}",
+                            quote_identifier(&info.name),
+                            if has_primary_key { "=" } else { "IS" },
+                            ind + start_offset + 1
+                        ),
+                        self.table
+                            .table
+                            .row_get(row, &info.name)
+                            .expect("Somehow pragma_table_info has a bad column name")
+                            .into(),
+                    ))
+                } else {
+                    None
+                }
+            })
+            .unzip();
+        let pos = pos.join(" AND ");
+        if !has_primary_key && self.has_rowid {
+            // several rows may share every value, so only use the first of them
+            (
+                format!(
+                    "rowid IN (SELECT rowid FROM {} WHERE {} LIMIT 1)",
+                    quote_table(&self.table_name),
+                    pos
+                ),
+                params,
+            )
+        } else {
+            (pos, params)
+        }
+    }
+
+    /// Captures the values of the passed row, so the row can be found again
+    /// when undoing a change to it
+    fn row_snapshot(&self, row: usize) -> RowSnapshot {
+        RowSnapshot {
+            values: self
+                .table
+                .columns()
+                .iter()
+                .cloned()
+                .zip(self.table.rows()[row].iter().cloned())
+                .collect(),
+            primary_key: self.primary_key_columns(),
+        }
+    }
+
+    /// Returns the names of the stored table's primary key columns
+    fn primary_key_columns(&self) -> Vec<String> {
+        self.column_info
+            .iter()
+            .filter(|info| info.is_primary_key)
+            .map(|info| info.name.clone())
+            .collect()
+    }
+
+    /// Returns true if the stored table has at least one primary key column
+    fn has_primary_key(&self) -> bool {
+        self.column_info.iter().any(|info| info.is_primary_key)
+    }
+
+    /// Warns that rows are being identified by all of their columns when
+    /// the stored table has no primary key
+    fn missing_pk_warning(&self) -> Vec<Action> {
+        if self.has_primary_key() {
+            vec![Action::Noop]
+        } else {
+            vec![Action::StatusMessage(
+                "Warning: no primary key, using all columns for identification".to_string(),
+            )]
+        }
+    }
+
+    /// Refuses a change to the shown rows while they're the result of a raw
+    /// query, returning the actions explaining why, or None if the rows
+    /// belong to the stored table and may be changed
+    fn refuse_raw_result_change(&self) -> Option<Vec<Action>> {
+        self.raw_result.then(|| {
+            vec![
+                Action::VeryLoudWrongBuzzer,
+                Action::StatusMessage(format!(
+                    "The result of a raw query can't be changed, select from {} \
+                     with ctrl+e to change it",
+                    self.table_name
+                )),
+            ]
+        })
+    }
+
+    /// Deletes every selected row from the table within the database in a
+    /// single transaction, then refreshes the table.
+    /// Returns the number of rows which were removed
+    pub fn delete(&mut self) -> Result<usize, AppError> {
+        // only allow removal of a row, not a cell
+        assert!(self.uses_rows);
+
+        let mut rows = self.selected_rows();
+        if rows.is_empty() {
+            return Ok(0);
+        }
+        // delete from the highest index to the lowest to keep earlier indices valid
+        rows.sort_unstable_by(|a, b| b.cmp(a));
+        let snapshots = rows.iter().map(|row| self.row_snapshot(*row)).collect();
+
+        self.connection.begin_transaction()?;
+        let mut deleted = 0;
+        for row in rows.iter().copied() {
+            let (pos, params) = self.pk_positional_args(row, 0);
+            // DELETE FROM table WHERE col_name1 = value1 AND col_name2 = value2;
+            let query = format!(
+                "DELETE FROM {} WHERE {};",
+                quote_table(&self.table_name),
+                pos
+            );
+            match self.connection.delete(&query, params_from_iter(params)) {
+                Ok(count) => deleted += count,
+                Err(err) => {
+                    // don't leave some of the rows deleted
+                    self.connection.rollback_transaction()?;
+                    return Err(err);
+                }
+            }
+        }
+        self.connection.commit_transaction()?;
+        self.history.push(Change::Delete {
+            table: self.table_name.clone(),
+            rows: snapshots,
+        });
+        if self.batch_mode {
+            // the batch is refreshed once committed, so only drop the deleted
+            // rows from the shown table until then
+            let mut table = self.table.table.clone();
+            for row in rows {
+                table.rows.remove(row);
+            }
+            self.table = self.create_table_display(table);
+            self.apply_pins();
+        } else {
+            // refresh the database and update the command list
+            self.refresh()?;
+        }
+        Ok(deleted)
+    }
+
+    /// Returns each selected row alongside its current value of the order
+    /// column and the value it's given when reordered. The rows' values are
+    /// handed out from lowest to highest in the order the rows were selected
+    fn reorder_moves(&self, order_column: &str) -> Vec<(usize, Value, Value)> {
+        let rows = self.selected_rows();
+        let x = self.table.table.column_index(order_column);
+        // reordering selects whole rows, so each selection gives its values
+        let current: Vec<Value> = self
+            .table
+            .export_selection()
+            .into_iter()
+            .map(|values| {
+                x.and_then(|x| values.get(x).cloned())
+                    .unwrap_or(Value::Null)
+            })
+            .collect();
+        let mut positions = current.clone();
+        positions.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+        rows.into_iter()
+            .zip(current)
+            .zip(positions)
+            .map(|((row, old), new)| (row, old, new))
+            .collect()
+    }
+
+    /// Opens the popup previewing how the selected rows will be reordered
+    fn open_reorder_popup(&mut self) -> Vec<Action> {
+        if let Some(actions) = self.refuse_raw_result_change() {
+            return actions;
+        }
+        let Some(order_column) = self.order_column.clone() else {
+            return vec![
+                Action::VeryLoudWrongBuzzer,
+                Action::ShowError(format!(
+                    "Reordering needs a column holding the order of the rows, such as \
+                     order_index, or an INTEGER column outside the primary key, \
+                     which {} doesn't have",
+                    self.table_name
+                )),
+            ];
+        };
+        let moves = self.reorder_moves(&order_column);
+        if moves.len() < 2 {
+            return vec![Action::VeryLoudWrongBuzzer];
+        }
+        let preview: Vec<String> = moves
+            .iter()
+            .map(|(_, old, new)| format!("{} → {}", old, new))
+            .collect();
+        self.confirm_popup = Some((
+            ConfirmPurpose::ReorderRows,
+            PopUpComponent::new(
+                format!(
+                    "Reorder {} rows? {}: {}",
+                    moves.len(),
+                    order_column,
+                    preview.join(", ")
+                ),
+                vec!["Yes".to_string(), "No".to_string()],
+                None,
+            ),
+        ));
+        vec![Action::Noop]
+    }
+
+    /// Rearranges the selected rows to be displayed in the order they were
+    /// selected, by handing out their current values of the order column
+    /// from lowest to highest in that order, all in a single transaction
+    fn reorder_rows(&mut self) -> Result<Vec<Action>, AppError> {
+        let Some(order_column) = self.order_column.clone() else {
+            return Err(AppError::MissingOrderColumn);
+        };
+        let moves = self.reorder_moves(&order_column);
+        if moves.len() < 2 {
+            return Ok(vec![Action::VeryLoudWrongBuzzer]);
+        }
+
+        self.connection.begin_transaction()?;
+        for (row, _, position) in &moves {
+            let (pos, params) = self.pk_positional_args(*row, 1);
+            // UPDATE table SET "order_col" = value WHERE pk_name = pk_val;
+            // quoted as names such as order are keywords
+            let query = format!(
+                "UPDATE {} SET {} = ?1 WHERE {};",
+                quote_table(&self.table_name),
+                quote_identifier(&order_column),
+                pos
+            );
+            if let Err(err) = self.connection.modify(
+                &query,
+                params_from_iter(std::iter::once(position.into()).chain(params)),
+            ) {
+                // don't leave the rows partially reordered
+                self.connection.rollback_transaction()?;
+                return Err(err);
+            }
+        }
+        self.connection.commit_transaction()?;
+        if self.batch_mode {
+            // the batch is refreshed once committed, so only update the
+            // shown values of the order column until then
+            if let Some(x) = self.table.table.column_index(&order_column) {
+                for (row, _, position) in moves {
+                    self.table.set_value(row, x, position);
+                }
+            }
+        } else {
+            self.refresh()?;
+        }
+        Ok(self.missing_pk_warning())
+    }
+
+    /// Returns the indices of the rows which are currently selected
+    fn selected_rows(&self) -> Vec<usize> {
+        self.table
+            .selections()
+            .iter()
+            .filter_map(|selection| match selection {
+                MultiTableSelection::Row(row) => Some(*row),
+                MultiTableSelection::Cell(_) => None,
+            })
+            .collect()
+    }
+
+    /// Opens the popup confirming the deletion of the selected rows
+    fn open_delete_popup(&mut self) -> Vec<Action> {
+        if let Some(actions) = self.refuse_raw_result_change() {
+            return actions;
+        }
+        let num_rows = self.selected_rows().len();
+        if num_rows == 0 || self.is_read_only() {
+            return vec![Action::VeryLoudWrongBuzzer];
+        }
+        self.confirm_popup = Some((
+            ConfirmPurpose::DeleteRows,
+            PopUpComponent::new(
+                format!(
+                    "Delete {} row{}?",
+                    num_rows,
+                    if num_rows == 1 { "" } else { "s" }
+                ),
+                vec!["Yes".to_string(), "No".to_string()],
+                // default to No so an accidental Enter doesn't delete anything
+                Some(1),
+            ),
+        ));
+        vec![Action::Noop]
+    }
+
+    fn handle_confirm_popup_key(&mut self, key: KeyEvent) -> Result<Vec<Action>, AppError> {
+        let Some((_, popup)) = &mut self.confirm_popup else {
+            return Ok(vec![Action::Noop]);
+        };
+        let actions = popup.handle_key_event(key)?;
+        match actions[..] {
+            [Action::NotifyCompletion] => {
+                // index 0 is the yes choice
+                let confirmed = popup.get_choice() == 0;
+                let (purpose, _) = self.confirm_popup.take().unwrap();
+                self.confirm_diff = None;
+                match purpose {
+                    ConfirmPurpose::DeleteRows if confirmed => {
+                        let deleted = self.delete()?;
+                        let mut actions = self.missing_pk_warning();
+                        actions.push(Action::StatusMessage(format!(
+                            "Deleted {} row{}",
+                            deleted,
+                            if deleted == 1 { "" } else { "s" }
+                        )));
+                        Ok(actions)
+                    }
+                    ConfirmPurpose::ReorderRows if confirmed => self.reorder_rows(),
+                    ConfirmPurpose::ModifyCell(y, x, new_val) if confirmed => {
+                        self.apply_modify(y, x, new_val)?;
+                        self.update_cell_display();
+                        Ok(vec![Action::Noop])
+                    }
+                    _ => {
+                        // the editor may still show the rejected value
+                        self.update_cell_display();
+                        Ok(vec![Action::Noop])
+                    }
+                }
+            }
+            [Action::Quit] => {
+                // close the popup, keeping any selected rows
+                self.confirm_popup = None;
+                self.confirm_diff = None;
+                self.update_cell_display();
+                Ok(vec![Action::Noop])
+            }
+            _ => Ok(actions),
+        }
+    }
+
+    /// Filters the table's retrieved rows depending on the passed filter.
+    /// Filters should take the form of "WHERE ..." or "GROUP BY ...",
+    /// as those keywords are not included in the default filter.
+    /// Passing an empty filter will simply select all rows from the table.
+    fn filter(&mut self, filter: &str) -> Result<(), AppError> {
+        self.filter_with_params(filter, Vec::new())
+    }
+
+    /// Filters the table's retrieved rows as [`Self::filter`] does, binding
+    /// the passed params to the filter's positional args
+    fn filter_with_params(&mut self, filter: &str, params: Vec<RsqValue>) -> Result<(), AppError> {
+        let query = format!(
+            "SELECT * FROM {} {};",
+            quote_table(&self.table_name),
+            filter
+        );
+        let table = self
+            .connection
+            .query(&self.sorted_query(&query), params_from_iter(params.iter()))?;
+        // store the unsorted query so the sort can be changed on top of it
+        self.query = Some(query);
+        self.query_params = params;
+        self.raw_result = false;
+        self.table = self.create_table_display(table);
+        if filter.trim().is_empty() {
+            self.set_empty_state("This table has no rows");
+        } else {
+            self.set_empty_state("No rows match the filter");
+        }
+        self.total_row_count = Some(self.connection.get_row_count(&self.table_name)?);
+        self.apply_pins();
+        Ok(())
+    }
+
+    /// Filters the table to the rows where the column holds the value, as
+    /// done when following a foreign key to the row it references
+    pub fn filter_to_value(&mut self, column: &str, value: &Value) -> Result<(), AppError> {
+        self.filter(&format!(
+            "WHERE \"{}\" = {}",
+            column.replace('"', "\"\""),
+            value.to_sql_literal()
+        ))
+    }
+
+    /// Creates the metadata for a table which isn't listed, so that it can
+    /// still be shown when a foreign key references it
+    pub fn metadata_for(&self, table_name: &str) -> Result<TableMetadata, AppError> {
+        TableMetadata::from_connection(&self.connection, table_name)
+    }
+
+    /// Returns the action opening the table referenced by the highlit
+    /// cell's column, or buzzes if the column isn't a foreign key
+    fn navigate_to_relation(&self) -> Vec<Action> {
+        let Some((y, x)) = self.table.highlit_cell() else {
+            return vec![Action::VeryLoudWrongBuzzer];
+        };
+        let column = &self.table.columns()[x];
+        let Some(foreign_key) = self
+            .column_info
+            .iter()
+            .find(|info| &info.name == column)
+            .and_then(|info| info.foreign_key.clone())
+        else {
+            return vec![
+                Action::VeryLoudWrongBuzzer,
+                Action::StatusMessage(format!("{} doesn't reference another table", column)),
+            ];
+        };
+        let value = self.table.rows()[y][x].clone();
+        if value == Value::Null {
+            return vec![
+                Action::VeryLoudWrongBuzzer,
+                Action::StatusMessage("NULL doesn't reference any row".to_string()),
+            ];
+        }
+        vec![Action::NavigateToRelation {
+            table: foreign_key.table,
+            column: foreign_key.column,
+            value,
+        }]
+    }
+
+    /// Filters the table to the rows where any text column contains the
+    /// term, highlighting the cells which matched it. Tables without text
+    /// columns are searched through every column instead
+    fn filter_by_term(&mut self, term: &str) -> Result<(), AppError> {
+        let text_columns: Vec<&str> = self
+            .column_info
+            .iter()
+            .filter(|info| info.data_type == ValueType::Text)
+            .map(|info| info.name.as_str())
+            .collect();
+        let columns: Vec<String> = if text_columns.is_empty() {
+            self.column_info
+                .iter()
+                .map(|info| info.name.clone())
+                .collect()
+        } else {
+            text_columns.iter().map(|name| name.to_string()).collect()
+        };
+        if term.is_empty() {
+            self.filter("")?;
+        } else {
+            // the term is bound rather than placed within the query, with the
+            // wildcards of LIKE escaped so they're matched literally
+            let pattern = format!("%{}%", escape_like(term));
+            let conditions: Vec<String> = columns
+                .iter()
+                .map(|column| format!("{} LIKE ?1 ESCAPE '\\'", quote_identifier(column)))
+                .collect();
+            self.filter_with_params(
+                &format!("WHERE {}", conditions.join(" OR ")),
+                vec![RsqValue::Text(pattern)],
+            )?;
+        }
+
+        // LIKE ignores the case of ASCII letters, so match the cells likewise
+        let term = term.to_lowercase();
+        let mut backgrounds = HashMap::new();
+        if !term.is_empty() {
+            let indices: Vec<usize> = columns
+                .iter()
+                .filter_map(|column| self.table.table.column_index(column))
+                .collect();
+            for (y, row) in self.table.rows().iter().enumerate() {
+                for x in &indices {
+                    if row[*x].to_string().to_lowercase().contains(&term) {
+                        backgrounds.insert((y, *x), DEFAULT_APP_COLORS.selection_one_bg);
+                    }
+                }
+            }
+        }
+        self.table.set_cell_backgrounds(backgrounds);
+        if let Some(bar) = &mut self.filter_bar {
+            bar.set_result_count(self.table.rows().len());
+        }
+        self.update_cell_display();
+        Ok(())
+    }
+
+    /// Closes the filter bar, showing every row of the table again
+    fn end_filter(&mut self) -> Result<(), AppError> {
+        self.filter_bar = None;
+        self.query = None;
+        self.refresh_preserving_position()
+    }
+
+    /// Updates the currently selected cell to have the value currently stored
+    /// in the editor, if that value is valid. Changes to more than whitespace
+    /// are held back until accepted in the confirmation popup.
+    /// Requires there only be 1 selected cell.
+    /// Returns true if the cell was updated or awaits confirmation, false if not,
+    /// or an [`AppError::ParseColumnError`] if the value doesn't fit the column
+    pub fn submit_modify(&mut self) -> Result<bool, AppError> {
+        assert!(
+            self.cell_display.is_some(),
+            "Trying to submit modification from an editor which doesn't exist"
+        );
+        if self.is_read_only() {
+            return Ok(false);
+        }
+        let table = &self.table;
+        let [MultiTableSelection::Cell((y, x))] = table.selections() else {
+            panic!("Trying to edit a whole row or multiple cells at once");
+        };
+        let (y, x) = (*y, *x);
+
+        let editor = self.cell_display.as_ref().unwrap();
+        if self.column_info[x].is_not_null && editor.is_empty() {
+            // there is a required field that is empty, so don't allow change
+            return Ok(false);
+        }
+        // validate the column has a proper value
+        let new_val = Value::parse_column(&self.column_info[x].data_type, &editor.text())?;
+        let old_val = &table.rows()[y][x];
+        // do nothing if the value wasn't changed
+        if new_val == *old_val {
+            return Ok(true);
+        }
+
+        let (old_text, new_text) = (old_val.to_string(), new_val.to_string());
+        let strip_whitespace = |s: &str| -> String { s.split_whitespace().collect() };
+        if strip_whitespace(&old_text) == strip_whitespace(&new_text) {
+            // whitespace-only changes are trivial, so skip the confirmation
+            self.apply_modify(y, x, new_val)?;
+        } else {
+            // show the whole row before and after the change
+            let before = Table::new(table.columns().to_vec(), vec![table.rows()[y].clone()]);
+            let mut after = before.clone();
+            after.rows[0][x] = new_val.clone();
+            self.confirm_diff = Some(TableDiff::new(before, after));
+            self.confirm_popup = Some((
+                ConfirmPurpose::ModifyCell(y, x, new_val),
+                PopUpComponent::new(
+                    format!("Change '{}' to '{}'?", old_text, new_text),
+                    vec!["Yes".to_string(), "No".to_string()],
+                    None,
+                ),
+            ));
+        }
+        Ok(true)
+    }
+
+    /// Sets the cell at (y, x) to the passed value in the database, updating
+    /// the content of the stored cell instead of refreshing the whole table
+    fn apply_modify(&mut self, y: usize, x: usize, new_val: Value) -> Result<(), AppError> {
+        let (pos, params) = self.pk_positional_args(y, 1);
+        // UPDATE table SET col_name = value WHERE pk_name = pk_val;
+        let query = format!(
+            "UPDATE {} SET {} = ?1 WHERE {};",
+            quote_table(&self.table_name),
+            quote_identifier(&self.table.columns()[x]),
+            pos
+        );
+        self.connection.modify(
+            &query,
+            params_from_iter(std::iter::once((&new_val).into()).chain(params)),
+        )?;
+        self.history.push(Change::Modify {
+            table: self.table_name.clone(),
+            row: self.row_snapshot(y),
+            column: self.table.columns()[x].clone(),
+            new: new_val.clone(),
+        });
+        self.table.set_value(y, x, new_val);
+        Ok(())
+    }
+
+    /// Shifts focus to the next focusable component.
+    /// Returns true if at the end of its selection of focusable components
+    /// and its containing component should move to its next component,
+    /// false if this was able to change focus
+    pub fn next_focus(&mut self) -> bool {
+        match self.focus {
+            FocusArea::Commands => {
+                self.focus = FocusArea::Main;
+                false
+            }
+            FocusArea::Main | FocusArea::QueryEditor => true,
+        }
+    }
+
+    /// Shifts focus to the previous focusable component.
+    /// Returns true if at the end of its selection of focusable components
+    /// and its containing component should move to its previous component,
+    /// false if this was able to change focus
+    pub fn prev_focus(&mut self) -> bool {
+        match self.focus {
+            // the edit commands are hidden while read-only
+            FocusArea::Main | FocusArea::QueryEditor if self.is_read_only() => true,
+            FocusArea::Main | FocusArea::QueryEditor => {
+                self.close_query_editor();
+                self.focus = FocusArea::Commands;
+                false
+            }
+            FocusArea::Commands => true,
+        }
+    }
+
+    /// Returns the names of the focused components nested within this one,
+    /// starting with the currently selected edit command when the main
+    /// section is focused
+    pub fn focus_path(&self) -> Vec<&'static str> {
+        let mut path = Vec::new();
+        match self.focus {
+            FocusArea::Commands => path.push(self.command_list.name()),
+            FocusArea::QueryEditor => path.push("Query"),
+            FocusArea::Main => {
+                if let Some(command) = self.command_list.selected() {
+                    path.push(command.into());
+                }
+                if let Some(add_comp) = &self.add_component {
+                    path.extend(add_comp.focus_path());
+                } else if let Some((_, popup)) = &self.input_popup {
+                    path.push(popup.name());
+                } else if let Some(bar) = &self.filter_bar {
+                    path.push(bar.name());
+                } else if let Some((_, popup)) = &self.confirm_popup {
+                    path.push(popup.name());
+                } else if let Some(editor) = &self.cell_display {
+                    path.push(editor.name());
+                }
+            }
+        }
+        path
+    }
+
+    pub fn focus_first(&mut self) {
+        self.close_query_editor();
+        self.focus = if self.is_read_only() {
+            FocusArea::Main
+        } else {
+            FocusArea::Commands
+        };
+    }
+
+    pub fn focus_last(&mut self) {
+        self.close_query_editor();
+        self.focus = FocusArea::Main;
+    }
+
+    /// Updates the number of selections to hold the new max number.
+    /// Truncates the list, removing the more recent selections, if new_max is
+    /// less than the current max selections.
+    fn set_max_selections(&mut self, new_max: usize) {
+        if self.max_selections == new_max {
+            return;
+        }
+        self.table.set_max_selections(new_max);
+        self.max_selections = new_max;
+    }
+
+    /// Updates the selection type to be the new type.
+    /// Removes selections of the old type if it is changed.
+    fn set_selection_type(&mut self, use_rows: bool) {
+        if self.uses_rows == use_rows {
+            return;
+        }
+        self.table.set_selection_type(use_rows);
+        self.uses_rows = use_rows;
+    }
+
+    /// Returns true if the selected edit command needs each of its
+    /// selections to be within a different row
+    fn requires_unique_rows(&self) -> bool {
+        self.command_list
+            .selected()
+            .is_some_and(|command| command.requires_unique_rows())
+    }
+
+    /// Changes the table stored to be the passed one, and reverts the
+    /// stored query to the default one.
+    fn change_stored_table(&mut self, table_name: &str) -> Result<(), AppError> {
+        if table_name != self.table_name {
+            self.table_name = table_name.to_owned();
+            self.query = None;
+            self.raw_result = false;
+            self.close_query_editor();
+            self.sort_state.clear();
+            self.table.reset_column_widths();
+            self.table.set_hidden_columns(HashSet::new());
+            self.column_picker = None;
+            self.pinned_keys.clear();
+            // update column info
+            self.column_info = self.connection.get_column_info(table_name)?;
+            self.has_rowid = self.connection.table_has_rowid(table_name)?;
+        }
+        Ok(())
+    }
+
+    /// Hides/Shows the add component depending on the newly selected command,
+    /// focuses the main section (table), and ensures the editor is not selected.
+    /// Should only be called if the edit command changed to something different
+    fn handle_edit_command_change(&mut self) {
+        self.duplicating_row = false;
+        if let Some(command) = self.command_list.selected() {
+            match command {
+                EditCommand::Add => {
+                    // restore the previously filled fields if there are any
+                    if let Some(add_comp) = self.cached_add_component.take() {
+                        self.add_component = Some(add_comp);
+                    } else {
+                        match AddComponent::new(Arc::clone(&self.connection), &self.table_name) {
+                            Err(err) => panic!("{:?}", err),
+                            Ok(add_comp) => self.add_component = Some(add_comp),
+                        }
+                    }
+                }
+                EditCommand::Filter => {
+                    // hide the add component, keeping its state, while filtering
+                    if let Some(add_comp) = self.add_component.take() {
+                        self.cached_add_component = Some(add_comp);
+                    }
+                    self.open_filter();
+                }
+                EditCommand::Import => {
+                    // likewise while importing
+                    if let Some(add_comp) = self.add_component.take() {
+                        self.cached_add_component = Some(add_comp);
+                    }
+                    self.input_popup = Some((
+                        InputPurpose::ImportCsv,
+                        InputPopup::new(
+                            "Import from CSV file".to_string(),
+                            &format!("{}.csv", self.table_name),
+                        ),
+                    ));
+                }
+                _ => {
+                    // keep the add component's state in case the switch was accidental
+                    if let Some(add_comp) = self.add_component.take() {
+                        self.cached_add_component = Some(add_comp);
+                    }
+                    self.set_max_selections(command.num_selections());
+                    self.set_selection_type(command.uses_rows());
+                    self.table
+                        .set_require_unique_rows(command.requires_unique_rows());
+                    self.table
+                        .set_require_double_enter(command == EditCommand::Delete);
+                }
+            }
+            // change the focused element to be the table now
+            self.focus = FocusArea::Main;
+            self.unfocus_editor();
+            // remove all selections
+            self.table.reset_selections();
+        }
+    }
+
+    /// Runs upon handling a SelectionChanged Action
+    fn handle_table_selection(&mut self) -> Result<Vec<Action>, AppError> {
+        let command = self
+            .command_list
+            .selected()
+            .expect("Should be unable to change selection without an edit mode selected");
+        match command {
+            EditCommand::Delete => {
+                // rows are only deleted once confirmed through the delete popup
+                Ok(Vec::new())
+            }
+            EditCommand::Modify => {
+                if let Some(actions) = self.refuse_raw_result_change() {
+                    self.table.reset_selections();
+                    return Ok(actions);
+                }
+                self.focusing_editor = true;
+                if let Some(editor) = &mut self.cell_display {
+                    editor.toggle_focus();
+                }
+                Ok(Vec::new())
+            }
+            _ => Ok(Vec::new()), // do nothing for most
+        }
+    }
+
+    /// Selects the passed row or cell of the shown table, as pressing enter
+    /// on it does
+    pub fn select(&mut self, selection: MultiTableSelection) {
+        self.table.select(selection);
+    }
+
+    /// Replaces the text of the editor showing the highlit cell, as typing
+    /// into it does, so it can be submitted with [`Self::submit_modify`]
+    pub fn set_editor_text(&mut self, text: &str) {
+        self.cell_display = Some(EditableText::from(text));
+    }
+
+    // Runs when the highlit cell within the table changes
+    fn update_cell_display(&mut self) {
+        if let Some(highlit_cell) = self.table.highlit_cell_value() {
+            let col_name = self
+                .table
+                .highlit_col_name()
+                .expect("Cell is highlit but no column name was available");
+            let autofill = self
+                .autofill_funcs
+                .get(col_name)
+                .or_else(|| self.value_autofill_funcs.get(col_name))
+                .cloned();
+            self.cell_display = Some(EditableText::new(&highlit_cell, autofill));
+        }
+    }
+
+    /// Opens the input popup to directly edit the highlit cell, selecting
+    /// that cell so the edit can be submitted like one made in the editor
+    fn open_direct_edit(&mut self) -> Vec<Action> {
+        if let Some(actions) = self.refuse_raw_result_change() {
+            return actions;
+        }
+        let table = &mut self.table;
+        let Some((y, x)) = table.highlit_cell() else {
+            return vec![Action::VeryLoudWrongBuzzer];
+        };
+        let prompt = format!("New value for {}", table.columns()[x]);
+        let value = table.rows()[y][x].to_string();
+        table.reset_selections();
+        table.select((y, x).into());
+        self.input_popup = Some((InputPurpose::ModifyCell, InputPopup::new(prompt, &value)));
+        vec![Action::Noop]
+    }
+
+    /// Shows an add component prefilled with the values of the first selected
+    /// row, or the highlit row if none are selected, without changing the
+    /// selected edit command
+    fn duplicate_highlit_row(&mut self) -> Result<Vec<Action>, AppError> {
+        if !self.command_list.has_command(&EditCommand::Add) {
+            return Ok(vec![Action::VeryLoudWrongBuzzer]);
+        }
+        if let Some(actions) = self.refuse_raw_result_change() {
+            return Ok(actions);
+        }
+        let table = &self.table;
+        // duplicate the first selected row, or the highlit one without any
+        let values = if self.selected_rows().is_empty() {
+            let Some((y, _)) = table.highlit_cell() else {
+                return Ok(vec![Action::VeryLoudWrongBuzzer]);
+            };
+            table.rows()[y].clone()
+        } else {
+            table.export_selection().swap_remove(0)
+        };
+        let row: Vec<(String, Value)> = table.columns().iter().cloned().zip(values).collect();
+        self.add_component = Some(AddComponent::from_row(
+            Arc::clone(&self.connection),
+            &self.table_name,
+            &row,
+        )?);
+        self.duplicating_row = true;
+        self.focus = FocusArea::Main;
+        Ok(vec![Action::StatusMessage(
+            "Duplicating row — press Submit to confirm".to_string(),
+        )])
+    }
+
+    /// Opens the input popup to search within the highlit column
+    fn open_column_search(&mut self) -> Vec<Action> {
+        let Some((_, x)) = self.table.highlit_cell() else {
+            return vec![Action::VeryLoudWrongBuzzer];
+        };
+        let prompt = format!("Search {} for", self.table.columns()[x]);
+        self.input_popup = Some((InputPurpose::SearchColumn(x), InputPopup::new(prompt, "")));
+        vec![Action::Noop]
+    }
+
+    /// Filters the table to the rows whose column at the passed index contains
+    /// the search term, or shows every row again if the term is empty
+    fn submit_column_search(&mut self, x: usize, term: &str) -> Result<Vec<Action>, AppError> {
+        if term.is_empty() {
+            self.filter("")?;
+        } else {
+            // the term is bound rather than placed within the query, with the
+            // wildcards of LIKE escaped so they're matched literally
+            let column = &self.table.columns()[x];
+            self.filter_with_params(
+                &format!("WHERE {} LIKE ?1 ESCAPE '\\'", quote_identifier(column)),
+                vec![RsqValue::Text(format!("%{}%", escape_like(term)))],
+            )?;
+        }
+        self.close_input_popup();
+        Ok(vec![Action::Noop])
+    }
+
+    /// Opens the input popup asking for the path to export the table to
+    fn open_export_popup(&mut self) -> Vec<Action> {
+        if self.pending_export.is_some() {
+            return vec![Action::StatusMessage(
+                "Wait for the current export to finish".to_string(),
+            )];
+        }
+        self.input_popup = Some((
+            InputPurpose::ExportCsv,
+            InputPopup::new(
+                "Export to CSV file".to_string(),
+                &format!("{}.csv", self.table_name),
+            ),
+        ));
+        vec![Action::Noop]
+    }
+
+    /// Writes the shown columns and rows as CSV to the file at the passed
+    /// path. The file is written on another thread, and its result is
+    /// notified once a tick finds it has finished
+    fn export_csv(&mut self, path: &str) -> Vec<Action> {
+        let path = PathBuf::from(path.trim());
+        if path.as_os_str().is_empty() {
+            return vec![Action::VeryLoudWrongBuzzer];
+        }
+        let table = self.table.table.clone();
+        let num_rows = table.rows.len();
+        let (sender, receiver) = mpsc::channel();
+        let thread_path = path.clone();
+        thread::spawn(move || {
+            // the receiver is only gone if the component was, so ignore it
+            let _ = sender.send(write_csv(&thread_path, &table));
+        });
+        self.pending_export = Some((receiver, path, num_rows));
+        vec![Action::Noop]
+    }
+
+    /// Returns the actions notifying the result of the pending export if it
+    /// has finished
+    fn poll_export(&mut self) -> Vec<Action> {
+        let Some((receiver, path, num_rows)) = &self.pending_export else {
+            return Vec::new();
+        };
+        let actions = match receiver.try_recv() {
+            Err(TryRecvError::Empty) => return Vec::new(),
+            Ok(Ok(())) => vec![Action::ShowNotification(
+                format!("Exported {} rows to {}", num_rows, path.display()),
+                NOTIFICATION_DURATION,
+            )],
+            Ok(Err(err)) => vec![
+                Action::VeryLoudWrongBuzzer,
+                Action::ShowError(format!("Failed to export to {}: {}", path.display(), err)),
+            ],
+            Err(TryRecvError::Disconnected) => vec![Action::ShowError(format!(
+                "Export to {} stopped before finishing",
+                path.display()
+            ))],
+        };
+        self.pending_export = None;
+        actions
+    }
+
+    /// Closes the input popup, dropping any selections made for it
+    fn close_input_popup(&mut self) {
+        self.input_popup = None;
+        self.table.reset_selections();
+        self.update_cell_display();
+        // the filter and import commands only open their popup, so return to
+        // the previous command
+        if matches!(
+            self.command_list.selected(),
+            Some(EditCommand::Filter | EditCommand::Import)
+        ) {
+            self.command_list.revert_selection();
+            self.handle_edit_command_change();
+        }
+    }
+
+    /// Passes the key on to the table, handling any changes it makes to
+    /// the highlight or selections
+    fn handle_table_key(&mut self, key: KeyEvent) -> Result<Vec<Action>, AppError> {
+        let mut actions = self.table.handle_key_event(key)?;
+        // handle any changes of highlight or selection in the table within this component
+        let mut highlight_changed = false;
+        let mut selection_changed = false;
+        actions.retain(|a| match a {
+            Action::HighlightChanged => {
+                highlight_changed = true;
+                false
+            }
+            Action::SelectionChanged => {
+                selection_changed = true;
+                false
+            }
+            // the table already cleared its selections
+            Action::DeselectAll => false,
+            _ => true,
+        });
+        if highlight_changed {
+            self.update_cell_display();
+        }
+        if selection_changed {
+            actions.extend(self.handle_table_selection()?);
+        }
+        Ok(actions)
+    }
+
+    /// Opens the popup choosing which of the table's columns are shown
+    fn open_column_picker(&mut self) -> Vec<Action> {
+        if self.table.columns().is_empty() {
+            return vec![Action::VeryLoudWrongBuzzer];
+        }
+        self.column_picker = Some(ColumnPickerPopup::new(
+            self.table.columns(),
+            self.table.hidden_columns(),
+        ));
+        vec![Action::Noop]
+    }
+
+    fn handle_column_picker_key(&mut self, key: KeyEvent) -> Result<Vec<Action>, AppError> {
+        let Some(picker) = &mut self.column_picker else {
+            return Ok(vec![Action::Noop]);
+        };
+        let actions = picker.handle_key_event(key)?;
+        match actions[..] {
+            [Action::NotifyCompletion] => {
+                self.table.set_hidden_columns(picker.hidden_columns());
+                self.column_picker = None;
+                // the highlight may have moved off a column which was hidden
+                self.update_cell_display();
+                Ok(vec![Action::Noop])
+            }
+            [Action::Quit] => {
+                self.column_picker = None;
+                Ok(vec![Action::Noop])
+            }
+            _ => Ok(actions),
+        }
+    }
+
+    /// Opens the input popup to filter the rows of the table
+    fn open_filter(&mut self) -> Vec<Action> {
+        self.input_popup = Some((
+            InputPurpose::Filter,
+            InputPopup::new(
+                "Filter rows (WHERE ... or GROUP BY ...)".to_string(),
+                "WHERE ",
+            ),
+        ));
+        vec![Action::Noop]
+    }
+
+    /// Submits the value entered within the direct edit popup by placing it
+    /// within the cell display before submitting the modification
+    fn submit_direct_edit(&mut self, value: &str) -> Result<Vec<Action>, AppError> {
+        self.cell_display = Some(EditableText::from(value));
+        match self.submit_modify() {
+            Ok(true) => {
+                self.close_input_popup();
+                Ok(self.missing_pk_warning())
+            }
+            Ok(false) => Ok(vec![Action::VeryLoudWrongBuzzer]),
+            Err(err) => reject_unparsable(err),
+        }
+    }
+
+    fn handle_input_popup_key(&mut self, key: KeyEvent) -> Result<Vec<Action>, AppError> {
+        let Some((purpose, popup)) = &mut self.input_popup else {
+            return Ok(vec![Action::Noop]);
+        };
+        let purpose = *purpose;
+        let actions = popup.handle_key_event(key)?;
+        match actions[..] {
+            [Action::NotifyCompletion] => {
+                let value = popup.value();
+                match purpose {
+                    InputPurpose::ModifyCell => self.submit_direct_edit(&value),
+                    InputPurpose::SearchColumn(x) => self.submit_column_search(x, &value),
+                    InputPurpose::Filter => {
+                        self.filter(&value)?;
+                        self.close_input_popup();
+                        Ok(vec![Action::Noop])
+                    }
+                    InputPurpose::ExportCsv => {
+                        self.close_input_popup();
+                        Ok(self.export_csv(&value))
+                    }
+                    InputPurpose::ImportCsv => {
+                        self.close_input_popup();
+                        self.import_csv(&value)
+                    }
+                }
+            }
+            [Action::Quit] => {
+                self.close_input_popup();
+                Ok(vec![Action::Noop])
+            }
+            _ => Ok(actions),
+        }
+    }
+
+    /// Replaces the table with an editor holding the stored query
+    fn open_query_editor(&mut self) -> Vec<Action> {
+        let query = match &self.query {
+            // the editor's query is run without params, so show their values
+            Some(query) => self
+                .connection
+                .expand_query(query, &self.query_params)
+                .unwrap_or_else(|_| query.clone()),
+            None => format!("SELECT * FROM {};", quote_table(&self.table_name)),
+        };
+        let mut editor = EditableText::from(query.as_str());
+        editor.toggle_focus();
+        self.query_editor = Some(editor);
+        self.focus = FocusArea::QueryEditor;
+        vec![Action::Noop]
+    }
+
+    /// Hides the query editor, showing the table again
+    fn close_query_editor(&mut self) {
+        if self.query_editor.take().is_some() {
+            self.focus = FocusArea::Main;
+        }
+    }
+
+    fn handle_query_editor_key(&mut self, key: KeyEvent) -> Result<Vec<Action>, AppError> {
+        let Some(editor) = &mut self.query_editor else {
+            return Ok(vec![Action::Noop]);
+        };
+        match key.code {
+            KeyCode::Esc => {
+                self.close_query_editor();
+                Ok(vec![Action::Noop])
+            }
+            KeyCode::Enter if key.modifiers == KeyModifiers::CONTROL => {
+                let query = editor.text();
+                Ok(self.submit_raw_query(&query))
+            }
+            // for terminals which can't tell ctrl+enter apart from enter
+            KeyCode::F(5) => {
+                let query = editor.text();
+                Ok(self.submit_raw_query(&query))
+            }
+            _ => editor.handle_key_event(key),
+        }
+    }
+
+    /// Runs the query typed into the query editor, closing the editor once
+    /// it has run or showing why it couldn't be run
+    fn submit_raw_query(&mut self, query: &str) -> Vec<Action> {
+        match self.run_raw_query(query) {
+            Ok(true) => {
+                self.close_query_editor();
+                vec![Action::Noop]
+            }
+            Ok(false) => self.handle_actions(vec![
+                Action::VeryLoudWrongBuzzer,
+                Action::ShowError(
+                    "Only queries which read from the database can be run".to_string(),
+                ),
+            ]),
+            // keep the editor open so the query can be fixed
+            Err(err) => self.handle_actions(vec![
+                Action::VeryLoudWrongBuzzer,
+                Action::ShowError(err.to_string()),
+            ]),
+        }
+    }
+
+    /// Shows the result of a query typed into the query editor. Queries on
+    /// the stored table are run as a filter over it, and any others are run
+    /// as they are. Returns false without running the query if it would
+    /// write to the database
+    fn run_raw_query(&mut self, query: &str) -> Result<bool, AppError> {
+        let query = query.trim().trim_end_matches(';');
+        if !self.connection.is_read_only_query(query)? {
+            return Ok(false);
+        }
+        // the stored table may be typed as it is named or quoted as in the
+        // default query
+        let prefixes = [
+            format!("SELECT * FROM {}", quote_table(&self.table_name)),
+            format!("SELECT * FROM {}", self.table_name),
+        ];
+        if let Some(filter) = prefixes.iter().find_map(|prefix| {
+            query
+                .get(..prefix.len())
+                .filter(|start| start.eq_ignore_ascii_case(prefix))
+                .and_then(|_| query.get(prefix.len()..))
+                .filter(|rest| rest.is_empty() || rest.starts_with(char::is_whitespace))
+        }) {
+            self.filter(filter.trim())?;
+            return Ok(true);
+        }
+        let query = format!("{};", query);
+        let table = self.connection.query(&query, [])?;
+        // the sorted columns may not exist within the query's result
+        self.sort_state.clear();
+        self.query = Some(query);
+        self.query_params.clear();
+        self.raw_result = true;
+        self.table = self.create_table_display(table);
+        self.set_empty_state("No rows match the query");
+        self.apply_pins();
+        Ok(true)
+    }
+
+    fn unfocus_editor(&mut self) {
+        self.update_cell_display();
+        self.focusing_editor = false;
+    }
+
+    /// Whether unmodified arrow keys move through the reader's content
+    /// instead of the table, which then needs shift held to be moved
+    /// through. Holds whenever the reader is shown but not being edited
+    fn reading_mode(&self) -> bool {
+        self.cell_display.is_some() && !self.focusing_editor
+    }
+
+    /// Returns the SQL which produced the shown rows, including the sort
+    /// applied over the stored query, with its parameters expanded
+    fn shown_query(&self) -> Option<&str> {
+        self.table.table.query.as_deref()
+    }
+
+    /// Copies the SQL which produced the shown rows, as given by
+    /// [`Self::shown_query`], to the system clipboard
+    fn copy_query(&self) -> Vec<Action> {
+        let Some(query) = self.shown_query() else {
+            return vec![Action::VeryLoudWrongBuzzer];
+        };
+        match arboard::Clipboard::new().and_then(|mut clipboard| clipboard.set_text(query)) {
+            Ok(()) => vec![Action::StatusMessage(
+                "Copied query to clipboard".to_string(),
+            )],
+            Err(err) => vec![
+                Action::VeryLoudWrongBuzzer,
+                Action::StatusMessage(format!("Failed to copy query: {}", err)),
+            ],
+        }
+    }
+
+    /// Returns a snapshot of the row of the stored table with the passed
+    /// rowid, or None if there's no such row
+    fn inserted_row_snapshot(&self, rowid: i64) -> Result<Option<RowSnapshot>, AppError> {
+        let inserted = self.connection.query_by_rowid(&self.table_name, rowid)?;
+        Ok(inserted.rows.first().map(|inserted_row| RowSnapshot {
+            // skip the rowid, which only the query selected
+            values: inserted.columns[1..]
+                .iter()
+                .cloned()
+                .zip(inserted_row[1..].iter().cloned())
+                .collect(),
+            primary_key: self.primary_key_columns(),
+        }))
+    }
+
+    /// Inserts the rows of the CSV file at the passed path into the stored
+    /// table, then shows how many were imported. The file's header names
+    /// the column of each field, and empty fields are left to their column's
+    /// default. Rows which can't be parsed or inserted are skipped
+    fn import_csv(&mut self, path: &str) -> Result<Vec<Action>, AppError> {
+        let records = match import_csv(path.trim()) {
+            Ok(records) => records,
+            Err(err) => {
+                return Ok(self.handle_actions(vec![
+                    Action::VeryLoudWrongBuzzer,
+                    Action::ShowError(format!("Failed to read {}: {}", path.trim(), err)),
+                ]));
+            }
+        };
+        let Some((header, rows)) = records.split_first() else {
+            return Ok(self.handle_actions(vec![
+                Action::VeryLoudWrongBuzzer,
+                Action::ShowError(format!("{} holds no rows", path.trim())),
+            ]));
+        };
+        // column names are case insensitive, as within SQLite
+        let mut columns = Vec::with_capacity(header.len());
+        for name in header {
+            match self
+                .column_info
+                .iter()
+                .find(|info| info.name.eq_ignore_ascii_case(name.trim()))
+            {
+                Some(info) => columns.push(info),
+                None => {
+                    return Ok(self.handle_actions(vec![
+                        Action::VeryLoudWrongBuzzer,
+                        Action::ShowError(format!(
+                            "{} has no column named {}",
+                            self.table_name, name
+                        )),
+                    ]));
+                }
+            }
+        }
+
+        let mut rowids = Vec::new();
+        let mut failed = 0;
+        self.connection.begin_transaction()?;
+        for row in rows {
+            if row.len() != columns.len() {
+                failed += 1;
+                continue;
+            }
+            let parsed: Result<Vec<(&str, Value)>, _> = std::iter::zip(&columns, row)
+                .filter(|(_, field)| !field.is_empty())
+                .map(|(info, field)| {
+                    Value::parse_column(&info.data_type, field)
+                        .map(|value| (info.name.as_str(), value))
+                })
+                .collect();
+            let Ok(parsed) = parsed else {
+                failed += 1;
+                continue;
+            };
+            let (names, values): (Vec<String>, Vec<Value>) = parsed
+                .into_iter()
+                .map(|(name, value)| (quote_identifier(name), value))
+                .unzip();
+            let query = if names.is_empty() {
+                format!(
+                    "INSERT INTO {} DEFAULT VALUES;",
+                    quote_table(&self.table_name)
+                )
+            } else {
+                let pos: Vec<String> = (1..=values.len()).map(|ind| format!("?{}", ind)).collect();
+                format!(
+                    "INSERT INTO {} ({}) VALUES ({});",
+                    quote_table(&self.table_name),
+                    names.join(", "),
+                    pos.join(", ")
+                )
+            };
+            // rows breaking a constraint fail without undoing the others
+            match self
+                .connection
+                .insert(&query, params_from_iter(values.iter().map(RsqValue::from)))
+            {
+                Ok(rowid) => rowids.push(rowid),
+                Err(_) => failed += 1,
+            }
+        }
+        self.connection.commit_transaction()?;
+
+        // the import is undone as a whole, which needs the rows' rowids
+        if self.has_rowid && !rowids.is_empty() {
+            let mut inserted = Vec::with_capacity(rowids.len());
+            for rowid in &rowids {
+                inserted.extend(self.inserted_row_snapshot(*rowid)?);
+            }
+            self.history.push(Change::Insert {
+                table: self.table_name.clone(),
+                rows: inserted,
+            });
+        }
+        self.refresh()?;
+        self.confirm_popup = Some((
+            ConfirmPurpose::ImportSummary,
+            PopUpComponent::new(
+                format!(
+                    "{} row{} imported, {} row{} failed",
+                    rowids.len(),
+                    if rowids.len() == 1 { "" } else { "s" },
+                    failed,
+                    if failed == 1 { "" } else { "s" }
+                ),
+                vec!["OK".to_string()],
+                None,
+            ),
+        ));
+        Ok(vec![Action::Noop])
+    }
+
+    /// Refreshes the table, then highlights the row with the passed rowid
+    /// if it is shown within the refreshed table. The inserted row is
+    /// recorded so that it can be undone
+    fn select_inserted_row(&mut self, rowid: i64) -> Result<(), AppError> {
+        self.refresh()?;
+        if !self.has_rowid {
+            // the returned rowid is meaningless for a WITHOUT ROWID table
+            return Ok(());
+        }
+        let Some(inserted) = self.inserted_row_snapshot(rowid)? else {
+            return Ok(());
+        };
+        let inserted_row: Vec<Value> = inserted
+            .values
+            .iter()
+            .map(|(_, value)| value.clone())
+            .collect();
+        self.history.push(Change::Insert {
+            table: self.table_name.clone(),
+            rows: vec![inserted],
+        });
+        if let Some(y) = self
+            .table
+            .rows()
+            .iter()
+            .position(|row| *row == inserted_row)
+        {
+            self.table.jump_to_row(y);
+        }
+        self.update_cell_display();
+        Ok(())
+    }
+
+    fn handle_actions(&mut self, actions: Vec<Action>) -> Vec<Action> {
+        // handle the actions which may be returned by the add component or the commandlist
+        let mut actions = actions;
+        let mut inserted_rowid = None;
+        let mut ending_filter = false;
+        // loops over the actions in order, removing any which return false (which are handled),
+        // returning the list of actions which weren't handled
+        actions.retain(|action| match action {
+            Action::ChangeEditCommand => {
+                self.handle_edit_command_change();
+                false
+            }
+            Action::RevertCommandSelection => {
+                // the command was never changed when duplicating a row
+                if !std::mem::take(&mut self.duplicating_row) {
+                    self.command_list.revert_selection();
+                }
+                false
+            }
+            Action::RevertToMain => {
+                // the row was submitted or cancelled, so its fields aren't needed again
+                self.add_component = None;
+                self.cached_add_component = None;
+                false
+            }
+            Action::SelectInsertedRow(rowid) => {
+                // the table needs refreshing, which can't be done while retaining
+                inserted_rowid = Some(*rowid);
+                false
+            }
+            Action::ResizeColumn(delta) => {
+                self.table.resize_highlit_column(*delta);
+                false
+            }
+            Action::ShowError(message) => {
+                self.error_overlay = Some((message.clone(), Instant::now()));
+                false
+            }
+            Action::BeginFilter => {
+                self.filter_bar = Some(FilterBar::new());
+                false
+            }
+            Action::EndFilter => {
+                // likewise the table is refreshed once the bar is closed
+                ending_filter = true;
+                false
+            }
+            _ => true,
+        });
+        if ending_filter && let Err(err) = self.end_filter() {
+            actions.push(Action::StatusMessage(err.to_string()));
+        }
+        if let Some(rowid) = inserted_rowid
+            && let Err(err) = self.select_inserted_row(rowid)
+        {
+            // show the failure like the app does for a failed refresh
+            actions.push(Action::StatusMessage(err.to_string()));
+        }
+        actions
+    }
+}
+
+impl Component for DatabaseComp {
+    fn name(&self) -> &'static str {
+        "Database"
+    }
+
+    fn commands(&self) -> Vec<(String, Action)> {
+        let mut commands = vec![
+            ("Refresh".to_string(), Action::Refresh),
+            ("Export CSV".to_string(), Action::ExportCsv),
+            ("Filter…".to_string(), Action::OpenFilter),
+        ];
+        commands.extend(
+            self.column_info
+                .iter()
+                .enumerate()
+                .map(|(x, info)| (format!("Sort by {}", info.name), Action::SortColumn(x))),
+        );
+        commands
+    }
+
+    fn handle_resize(&mut self, width: u16, height: u16) {
+        // pass the resize on to every component which is kept, including the
+        // hidden ones, so none of them are stale once shown
+        self.command_list.handle_resize(width, height);
+        self.table.handle_resize(width, height);
+        if let Some(cell_display) = &mut self.cell_display {
+            cell_display.handle_resize(width, height);
+        }
+        if let Some(add_comp) = &mut self.add_component {
+            add_comp.handle_resize(width, height);
+        }
+        if let Some(add_comp) = &mut self.cached_add_component {
+            add_comp.handle_resize(width, height);
+        }
+    }
+
+    fn handle_event(&mut self, event: Action) -> Result<Vec<Action>, AppError> {
+        match self.focus {
+            FocusArea::Commands => {
+                let actions = self.command_list.handle_event(event)?;
+                Ok(self.handle_actions(actions))
+            }
+            FocusArea::QueryEditor => match event {
+                Action::KeyEvent(key_event) => {
+                    // any key dismisses a shown error
+                    self.error_overlay = None;
+                    self.handle_query_editor_key(key_event)
+                }
+                Action::OtherEvent(other_event) => self.handle_other_event(other_event),
+                _ => Ok(vec![Action::Noop]),
+            },
+            FocusArea::Main => {
+                // handle the add component if there is one showing
+                if let Some(add_comp) = &mut self.add_component {
+                    let actions = add_comp.handle_event(event)?;
+                    return Ok(self.handle_actions(actions));
+                }
+                match event {
+                    Action::Noop => Ok(vec![Action::Noop]),
+                    Action::Quit => Ok(vec![Action::Quit]),
+                    Action::KeyEvent(key_event) => {
+                        if self.input_popup.is_some() {
+                            self.handle_input_popup_key(key_event)
+                        } else if self.column_picker.is_some() {
+                            let actions = self.handle_column_picker_key(key_event)?;
+                            Ok(self.handle_actions(actions))
+                        } else if let Some(bar) = &mut self.filter_bar {
+                            let term = bar.term();
+                            let actions = bar.handle_key_event(key_event)?;
+                            // filter again whenever the term changes
+                            let new_term = bar.term();
+                            if new_term != term {
+                                self.filter_by_term(&new_term)?;
+                            }
+                            Ok(self.handle_actions(actions))
+                        } else if self.confirm_popup.is_some() {
+                            self.handle_confirm_popup_key(key_event)
+                        } else if self.reading_mode()
+                            && key_event.modifiers == KeyModifiers::NONE
+                            && matches!(
+                                key_event.code,
+                                KeyCode::Left | KeyCode::Right | KeyCode::Up | KeyCode::Down
+                            )
+                            && let Some(reader) = &mut self.cell_display
+                        {
+                            reader.handle_key_event(key_event)
+                        } else if !self.focusing_editor {
+                            let actions = self.handle_key_event(key_event)?;
+                            Ok(self.handle_actions(actions))
+                        } else {
+                            match key_event.code {
+                                KeyCode::Esc => {
+                                    self.unfocus_editor();
+                                    self.table.reset_selections();
+                                    Ok(vec![Action::Noop])
+                                }
+                                KeyCode::Enter => match self.submit_modify() {
+                                    Ok(true) => {
+                                        self.unfocus_editor();
+                                        self.table.reset_selections();
+                                        Ok(self.missing_pk_warning())
+                                    }
+                                    Ok(false) => Ok(vec![Action::VeryLoudWrongBuzzer]),
+                                    Err(err) => reject_unparsable(err),
+                                },
+                                _ => {
+                                    if let Some(editor) = &mut self.cell_display {
+                                        editor.handle_key_event(key_event)
+                                    } else {
+                                        panic!("Somehow focusing editor without editor present");
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    Action::OtherEvent(other_event) => self.handle_other_event(other_event),
+                    // Action::Filter(filter) => {
+                    //     self.filter(&filter)?;
+                    //     Ok(vec![Action::Noop])
+                    // }
+                    unhandled => Err(AppError::unhandled_action(unhandled, self.name())),
+                }
+            }
+        }
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent) -> Result<Vec<Action>, AppError> {
+        // the row number being typed to jump to takes every key until done
+        if self.table.jump_buffer().is_some() {
+            return self.handle_table_key(key);
+        }
+        // ctrl+d duplicates the highlit row into a new add component
+        if key.code == KeyCode::Char('d') && key.modifiers == KeyModifiers::CONTROL {
+            return self.duplicate_highlit_row();
+        }
+        // ctrl+b begins or commits a batch of changes, which ctrl+enter also
+        // commits and ctrl+esc discards
+        if key.code == KeyCode::Char('b') && key.modifiers == KeyModifiers::CONTROL {
+            return self.toggle_batch_mode();
+        }
+        if self.batch_mode && key.modifiers == KeyModifiers::CONTROL {
+            match key.code {
+                KeyCode::Enter => return self.commit_batch(),
+                KeyCode::Esc => return self.rollback_batch(),
+                _ => {}
+            }
+        }
+        // ctrl+e replaces the table with an editor for its raw query
+        if key.code == KeyCode::Char('e') && key.modifiers == KeyModifiers::CONTROL {
+            return Ok(self.open_query_editor());
+        }
+        // x exports the shown table to a CSV file
+        if key.code == KeyCode::Char('x') && key.modifiers == KeyModifiers::NONE {
+            return Ok(self.open_export_popup());
+        }
+        // ctrl+f opens the filter
+        if key.code == KeyCode::Char('f') && key.modifiers == KeyModifiers::CONTROL {
+            return Ok(self.open_filter());
+        }
+        // f follows the highlit foreign key to the row it references
+        if key.code == KeyCode::Char('f') && key.modifiers == KeyModifiers::NONE {
+            return Ok(self.navigate_to_relation());
+        }
+        // / opens the bar filtering the table as a search term is typed
+        if key.code == KeyCode::Char('/') && key.modifiers == KeyModifiers::NONE {
+            return Ok(vec![Action::BeginFilter]);
+        }
+        // ctrl+p pins or unpins the highlit row
+        if key.code == KeyCode::Char('p') && key.modifiers == KeyModifiers::CONTROL {
+            return self.toggle_pin();
+        }
+        // v opens the popup choosing which columns are shown
+        if key.code == KeyCode::Char('v') && key.modifiers == KeyModifiers::NONE {
+            return Ok(self.open_column_picker());
+        }
+        // ctrl+q copies the query used for the shown table
+        if key.code == KeyCode::Char('q') && key.modifiers == KeyModifiers::CONTROL {
+            return Ok(self.copy_query());
+        }
+        // s cycles the sort of the highlit column, and S adds it as a
+        // tie-breaker to the columns already sorted by
+        if key.code == KeyCode::Char('s') && key.modifiers == KeyModifiers::NONE {
+            return self.cycle_sort(false);
+        }
+        if key.code == KeyCode::Char('S') {
+            return self.cycle_sort(true);
+        }
+        // e directly edits the highlit cell when modifying
+        if key.code == KeyCode::Char('e')
+            && self.command_list.selected() == Some(EditCommand::Modify)
+        {
+            return Ok(self.open_direct_edit());
+        }
+        // enter searches the highlit column when searching, as no
+        // selections are made for it
+        if key.code == KeyCode::Enter && self.command_list.selected() == Some(EditCommand::Search) {
+            return Ok(self.open_column_search());
+        }
+        // d asks to delete the selected rows when deleting
+        if key.code == KeyCode::Char('d')
+            && self.command_list.selected() == Some(EditCommand::Delete)
+        {
+            return Ok(self.open_delete_popup());
+        }
+        // r asks to reorder the selected rows when reordering
+        if key.code == KeyCode::Char('r')
+            && self.command_list.selected() == Some(EditCommand::Reorder)
+        {
+            return Ok(self.open_reorder_popup());
+        }
+        self.handle_table_key(key)
+    }
+
+    fn handle_tick(&mut self) -> Vec<Action> {
+        // advance the spinner
+        self.frame_count = self.frame_count.wrapping_add(1);
+        // hide the error once it has been shown for long enough
+        if self
+            .error_overlay
+            .as_ref()
+            .is_some_and(|(_, shown_at)| shown_at.elapsed() >= STATUS_MESSAGE_DURATION)
+        {
+            self.error_overlay = None;
+        }
+        let actions = self.poll_export();
+        self.handle_actions(actions)
+    }
+
+    fn render(&mut self, f: &mut Frame, rect: Rect, block: Block) {
+        // split the passed rect for the edits commands and the table itself
+        let [commands_rect, main_rect, ..] = *Layout::default()
+            .margin(0)
+            .direction(Direction::Vertical)
+            .constraints([
+                // 3 pixels of height for the list of commands, which is hidden
+                // while read-only
+                Constraint::Length(if self.is_read_only() { 0 } else { 3 }),
+                Constraint::Min(7), // At least 7 pixels of height for the rest
+            ])
+            .split(rect)
+        else {
+            panic!("Not enough size to create the necessary rects");
+        };
+
+        if self.table_name.is_empty() {
+            // no table has been chosen to query yet
+            f.render_widget(
+                Paragraph::new(self.empty_state_message.as_str())
+                    .centered()
+                    .block(block),
+                rect,
+            );
+            return;
+        }
+
+        // the filter bar takes the bottom line of the main area while open
+        let (main_rect, filter_rect) = if self.filter_bar.is_some() {
+            let [main_rect, filter_rect] =
+                Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).areas(main_rect);
+            (main_rect, Some(filter_rect))
+        } else {
+            (main_rect, None)
+        };
+
+        let row_count = self.row_count_text();
+        let read_only = self.is_read_only();
+        let table = &mut self.table;
+        // uses the passed block for the potentially focused component as
+        // the block will be unfocused if this component is not focused
+        let (commands_block, mut main_block) = match self.focus {
+            FocusArea::Commands => (block, DEFAULT_APP_COLORS.default_block()),
+            FocusArea::Main | FocusArea::QueryEditor => (DEFAULT_APP_COLORS.default_block(), block),
+        };
+        if self.add_component.is_none()
+            && let Some(row_count) = row_count
+        {
+            main_block =
+                main_block.title_bottom(Line::from(format!(" {} ", row_count)).right_aligned());
+        }
+        if !read_only {
+            self.command_list.render(f, commands_rect, commands_block);
+        }
+        if self.is_loading {
+            // show a spinner in place of the table until its query finishes,
+            // which is left static when animations are turned off
+            let loading = if self.animations {
+                let spinner = SPINNER_FRAMES[self.frame_count % SPINNER_FRAMES.len()];
+                format!("{} Loading…", spinner)
+            } else {
+                "Loading…".to_string()
+            };
+            f.render_widget(
+                Paragraph::new(loading)
+                    .centered()
+                    .fg(DEFAULT_APP_COLORS.main_fg)
+                    .bg(DEFAULT_APP_COLORS.main_bg)
+                    .block(main_block),
+                main_rect,
+            );
+        } else if let Some(editor) = &mut self.query_editor {
+            // the query editor takes the whole main area in place of the table
+            let title = Line::from(" Query (ctrl+enter or F5 to run, esc to cancel) ").centered();
+            editor.render(f, main_rect, main_block.title(title));
+        } else if let Some(add_comp) = &mut self.add_component {
+            // render the add component if it is shown
+            add_comp.render(f, main_rect, main_block);
+        } else if table.rows().is_empty() {
+            // explain why there is nothing to show instead of an empty table
+            f.render_widget(
+                Paragraph::new(self.empty_state_message.as_str())
+                    .centered()
+                    .fg(DEFAULT_APP_COLORS.main_fg)
+                    .bg(DEFAULT_APP_COLORS.main_bg)
+                    .block(main_block),
+                main_rect,
+            );
+        } else if let Some(cell_display) = &mut self.cell_display {
+            // split the main_rect to show the cell display
+            let [table_rect, mut cell_display_rect, ..] = *Layout::default()
+                .margin(1) // 1 margin to account for border
+                .direction(Direction::Horizontal)
+                .constraints([
+                    Constraint::Percentage(75), // table takes up 75% of main area
+                    Constraint::Min(8),         // cell display requires at least 8 cols width
+                ])
+                .split(main_rect)
+            else {
+                panic!("Not enough size to create the necessary rects");
+            };
+            // render the main border block separately
+            f.render_widget(main_block.bg(DEFAULT_APP_COLORS.main_bg), main_rect);
+            // allot space for the title of the cell display
+            let mut cell_display_title_rect = cell_display_rect;
+            cell_display_title_rect.height = 1;
+            cell_display_rect.height = cell_display_rect.height.saturating_sub(1);
+            cell_display_rect.y += 1;
+            cell_display_rect.width = cell_display_rect.width.saturating_sub(1);
+            cell_display_rect.x += 1;
+            let display_title = if self.focusing_editor {
+                "Editor"
+            } else {
+                "Reader"
+            };
+            f.render_widget(
+                Paragraph::new(display_title)
+                    .bg(DEFAULT_APP_COLORS.header_bg)
+                    .fg(DEFAULT_APP_COLORS.header_fg)
+                    .centered(),
+                cell_display_title_rect,
+            );
+            cell_display.render(f, cell_display_rect, Block::new());
+            table.render(f, table_rect, Block::new());
+        } else {
+            table.render(f, main_rect, main_block);
+        }
+
+        if let Some(bar) = &mut self.filter_bar
+            && let Some(filter_rect) = filter_rect
+        {
+            bar.render(f, filter_rect, Block::new());
+        }
+
+        // overlay the input popup over the main section if it is shown
+        if let Some((_, popup)) = &mut self.input_popup {
+            popup.render(
+                f,
+                main_rect.inner(Margin {
+                    horizontal: main_rect.width / 5,
+                    vertical: main_rect.height / 3,
+                }),
+                DEFAULT_APP_COLORS.default_block(),
+            );
+        }
+        // likewise overlay the column picker
+        if let Some(picker) = &mut self.column_picker {
+            picker.render(
+                f,
+                main_rect.inner(Margin {
+                    horizontal: main_rect.width / 4,
+                    vertical: main_rect.height / 6,
+                }),
+                DEFAULT_APP_COLORS.default_block(),
+            );
+        }
+        // overlay an error until it fades or a key is pressed
+        if let Some((message, _)) = &self.error_overlay {
+            let error_rect = main_rect.inner(Margin {
+                horizontal: main_rect.width / 6,
+                vertical: main_rect.height / 3,
+            });
+            f.render_widget(Clear, error_rect);
+            f.render_widget(
+                Paragraph::new(message.as_str())
+                    .wrap(Wrap { trim: true })
+                    .centered()
+                    .fg(DEFAULT_APP_COLORS.error_fg)
+                    .bg(DEFAULT_APP_COLORS.alt_bg)
+                    .block(DEFAULT_APP_COLORS.default_block().title(" Error ")),
+                error_rect,
+            );
+        }
+        // likewise overlay the confirmation popup
+        if let Some((_, popup)) = &mut self.confirm_popup {
+            let popup_rect = main_rect.inner(Margin {
+                horizontal: main_rect.width / 5,
+                vertical: main_rect.height / 3,
+            });
+            popup.render(f, popup_rect, DEFAULT_APP_COLORS.default_block());
+            // show the diff within the space above the popup
+            if let Some(diff) = &mut self.confirm_diff {
+                let diff_rect = Rect {
+                    y: main_rect.y + 1,
+                    height: popup_rect.y.saturating_sub(main_rect.y + 1),
+                    ..popup_rect
+                };
+                // borders, the header, and a row take up at least 6 lines
+                if diff_rect.height >= 6 {
+                    f.render_widget(Clear, diff_rect);
+                    diff.render(f, diff_rect, DEFAULT_APP_COLORS.default_block());
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::{Terminal, backend::TestBackend};
+
+    use super::*;
+
+    const SCHEMA: &str = "CREATE TABLE people (id INTEGER PRIMARY KEY, name TEXT, age INTEGER);
+        INSERT INTO people (name, age) VALUES ('ann', 31), ('bob', 42), ('cat', 27);";
+
+    /// Schema of a table without a primary key whose names need quoting
+    const AWKWARD_SCHEMA: &str = "CREATE TABLE \"my table\" (\"group\" TEXT, \"order\" INTEGER);
+        INSERT INTO \"my table\" VALUES ('a', 1), ('b', 2), ('c', 3);";
+
+    /// Creates the component showing the passed table of an in-memory
+    /// database built from the schema, focusing the table rather than the
+    /// command list
+    fn table_component(schema: &str, table: &str) -> DatabaseComp {
+        let connection = Connection::open_in_memory().unwrap();
+        connection.create_test_table(schema).unwrap();
+        let connection = Arc::new(connection);
+        let metadata = TableMetadata::from_connection(&connection, table).unwrap();
+        let mut component = DatabaseComp::new(connection, "", 2, false).unwrap();
+        component.change_table_used(&metadata).unwrap();
+        component.focus_last();
+        component
+    }
+
+    fn people_component() -> DatabaseComp {
+        table_component(SCHEMA, "people")
+    }
+
+    /// Returns the orders stored in the awkwardly named table, by rowid
+    fn stored_orders(component: &DatabaseComp) -> Vec<Value> {
+        component
+            .connection
+            .query("SELECT \"order\" FROM \"my table\" ORDER BY rowid;", [])
+            .unwrap()
+            .rows
+            .into_iter()
+            .map(|mut row| row.remove(0))
+            .collect()
+    }
+
+    fn press(component: &mut DatabaseComp, code: KeyCode) -> Vec<Action> {
+        component
+            .handle_event(Action::KeyEvent(KeyEvent::new(code, KeyModifiers::NONE)))
+            .unwrap()
+    }
+
+    /// Renders the component while its query is still loading, returning
+    /// the rendered text
+    fn render_loading(component: &mut DatabaseComp) -> String {
+        component.is_loading = true;
+        let mut terminal = Terminal::new(TestBackend::new(60, 20)).unwrap();
+        terminal
+            .draw(|f| component.render(f, f.area(), Block::bordered()))
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+        buffer.content().iter().map(|cell| cell.symbol()).collect()
+    }
+
+    /// Returns the names of the people stored in the database, by their id
+    fn stored_names(component: &DatabaseComp) -> Vec<Value> {
+        component
+            .connection
+            .query("SELECT name FROM people ORDER BY id;", [])
+            .unwrap()
+            .rows
+            .into_iter()
+            .map(|mut row| row.remove(0))
+            .collect()
+    }
+
+    #[test]
+    fn submit_modify_confirms_before_changing_the_cell() {
+        let mut component = people_component();
+        component.table.select(MultiTableSelection::Cell((1, 1)));
+        component.cell_display = Some(EditableText::from("bobby"));
+        assert!(component.submit_modify().unwrap());
+        // nothing changes until the confirmation is accepted
+        assert_eq!(stored_names(&component)[1], Value::Text("bob".to_string()));
+        assert!(component.confirm_popup.is_some());
+
+        press(&mut component, KeyCode::Enter);
+        assert!(component.confirm_popup.is_none());
+        assert_eq!(
+            stored_names(&component)[1],
+            Value::Text("bobby".to_string())
+        );
+        assert_eq!(
+            component.table.rows()[1][1],
+            Value::Text("bobby".to_string())
+        );
+    }
+
+    #[test]
+    fn modifies_cells_of_tables_with_awkward_names() {
+        let mut component = table_component(AWKWARD_SCHEMA, "my table");
+        component.table.select(MultiTableSelection::Cell((1, 1)));
+        component.cell_display = Some(EditableText::from("20"));
+        assert!(component.submit_modify().unwrap());
+        press(&mut component, KeyCode::Enter);
+        assert_eq!(
+            stored_orders(&component),
+            [Value::Integer(1), Value::Integer(20), Value::Integer(3)]
+        );
+    }
+
+    #[test]
+    fn shown_query_includes_the_sort() {
+        let mut component = people_component();
+        component.sort_by_column(2).unwrap();
+        assert_eq!(
+            component.shown_query(),
+            Some("SELECT * FROM (SELECT * FROM \"main\".\"people\") ORDER BY \"age\" ASC;")
+        );
+    }
+
+    #[test]
+    fn search_terms_are_bound_rather_than_pasted() {
+        let mut component = people_component();
+        component.filter_by_term("o'b%").unwrap();
+        assert!(component.table.rows().is_empty());
+
+        component.filter_by_term("B").unwrap();
+        assert_eq!(component.table.rows().len(), 1);
+        // the bound term is kept for refreshes and shown when editing
+        component.refresh().unwrap();
+        assert_eq!(component.table.rows().len(), 1);
+        component.open_query_editor();
+        let editor = component.query_editor.as_ref().unwrap();
+        assert!(editor.text().contains("LIKE '%B%'"));
+
+        component.submit_column_search(1, "'").unwrap();
+        assert!(component.table.rows().is_empty());
+    }
+
+    #[test]
+    fn submit_modify_refuses_unparsable_values() {
+        let mut component = people_component();
+        component.table.select(MultiTableSelection::Cell((0, 2)));
+        component.cell_display = Some(EditableText::from("thirty"));
+        assert!(component.submit_modify().is_err());
+        assert!(component.confirm_popup.is_none());
+    }
+
+    #[test]
+    fn delete_removes_the_selected_rows() {
+        let mut component = people_component();
+        component.set_selection_type(true);
+        component.set_max_selections(2);
+        component.table.select(MultiTableSelection::Row(0));
+        component.table.select(MultiTableSelection::Row(2));
+        assert_eq!(component.delete().unwrap(), 2);
+        assert_eq!(stored_names(&component), [Value::Text("bob".to_string())]);
+    }
+
+    #[test]
+    fn deletes_reorders_and_filters_tables_with_awkward_names() {
+        let mut component = table_component(AWKWARD_SCHEMA, "my table");
+        component.set_selection_type(true);
+        component.set_max_selections(2);
+        component.table.select(MultiTableSelection::Row(2));
+        component.table.select(MultiTableSelection::Row(0));
+        component.reorder_rows().unwrap();
+        assert_eq!(
+            stored_orders(&component),
+            [Value::Integer(3), Value::Integer(2), Value::Integer(1)]
+        );
+
+        component.filter("WHERE \"group\" = 'b'").unwrap();
+        assert_eq!(component.table.rows().len(), 1);
+
+        component.table.select(MultiTableSelection::Row(0));
+        assert_eq!(component.delete().unwrap(), 1);
+        assert_eq!(
+            stored_orders(&component),
+            [Value::Integer(3), Value::Integer(1)]
+        );
+    }
+
+    #[test]
+    fn imports_into_tables_with_awkward_names() {
+        let mut component = table_component(AWKWARD_SCHEMA, "my table");
+        let path = std::env::temp_dir().join(format!("cli-import-{}.csv", std::process::id()));
+        std::fs::write(&path, "group,order\nd,4\n").unwrap();
+        component.import_csv(path.to_str().unwrap()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(stored_orders(&component).last(), Some(&Value::Integer(4)));
+    }
+
+    #[test]
+    fn deleting_within_a_batch_only_drops_the_shown_rows() {
+        let mut component = people_component();
+        component.toggle_batch_mode().unwrap();
+        component.set_selection_type(true);
+        component.table.select(MultiTableSelection::Row(1));
+        assert_eq!(component.delete().unwrap(), 1);
+        let names: Vec<&Value> = component.table.rows().iter().map(|row| &row[1]).collect();
+        assert_eq!(
+            names,
+            [
+                &Value::Text("ann".to_string()),
+                &Value::Text("cat".to_string())
+            ]
+        );
+        // the rows are only refreshed, and the count updated, once committed
+        assert_eq!(component.total_row_count, Some(3));
+
+        component.rollback_batch().unwrap();
+        assert_eq!(stored_names(&component).len(), 3);
+        assert_eq!(component.table.rows().len(), 3);
+    }
+
+    #[test]
+    fn loading_spinner_is_static_without_animations() {
+        let mut component = people_component();
+        component.set_animations(false);
+        let rendered = render_loading(&mut component);
+        assert!(rendered.contains("Loading…"));
+        assert!(!SPINNER_FRAMES.iter().any(|frame| rendered.contains(frame)));
+    }
+
+    #[test]
+    fn loading_spinner_is_shown_with_animations() {
+        let mut component = people_component();
+        let rendered = render_loading(&mut component);
+        assert!(rendered.contains(&format!("{} Loading…", SPINNER_FRAMES[0])));
+    }
+
+    #[test]
+    fn commands_sort_by_each_column() {
+        let mut component = people_component();
+        let commands = component.commands();
+        assert!(commands.contains(&("Export CSV".to_string(), Action::ExportCsv)));
+        assert!(commands.contains(&("Filter…".to_string(), Action::OpenFilter)));
+        assert!(commands.contains(&("Sort by age".to_string(), Action::SortColumn(2))));
+
+        component.run_command(Action::SortColumn(2)).unwrap();
+        assert_eq!(component.sort_state, vec![(2, SortDirection::Asc)]);
+        let ages: Vec<&Value> = component.table.rows().iter().map(|row| &row[2]).collect();
+        assert_eq!(
+            ages,
+            [
+                &Value::Integer(27),
+                &Value::Integer(31),
+                &Value::Integer(42)
+            ]
+        );
+    }
+
+    #[test]
+    fn v_opens_the_column_picker_while_the_reader_is_shown() {
+        let mut component = people_component();
+        assert!(component.cell_display.is_some());
+        press(&mut component, KeyCode::Char('v'));
+        assert!(component.column_picker.is_some());
+    }
+
+    #[test]
+    fn column_picker_hides_the_unchecked_columns() {
+        let mut component = people_component();
+        press(&mut component, KeyCode::Char('v'));
+        press(&mut component, KeyCode::Down);
+        press(&mut component, KeyCode::Char(' '));
+        press(&mut component, KeyCode::Enter);
+        assert!(component.column_picker.is_none());
+        assert_eq!(component.table.hidden_columns(), &HashSet::from([1]));
+    }
+
+    #[test]
+    fn raw_query_results_refuse_changes() {
+        let mut component = people_component();
+        component.open_query_editor();
+        component.query_editor = Some(EditableText::from("SELECT age FROM people"));
+        press(&mut component, KeyCode::F(5));
+        assert!(component.query_editor.is_none());
+        assert_eq!(component.table.columns(), ["age"]);
+
+        component.set_selection_type(true);
+        component.table.select(MultiTableSelection::Row(0));
+        assert!(
+            component
+                .open_delete_popup()
+                .contains(&Action::VeryLoudWrongBuzzer)
+        );
+        assert!(component.confirm_popup.is_none());
+        assert!(
+            component
+                .open_direct_edit()
+                .contains(&Action::VeryLoudWrongBuzzer)
+        );
+        assert!(component.input_popup.is_none());
+
+        // filtering the stored table again allows changes
+        component.filter("").unwrap();
+        component.table.select(MultiTableSelection::Row(0));
+        component.open_delete_popup();
+        assert!(component.confirm_popup.is_some());
+    }
+}
@@ -1,236 +1,659 @@
-use ratatui::{
-    crossterm::event::KeyModifiers,
-    style::Styled,
-    text::{Line, Text},
-    widgets::Clear,
-};
-use unicode_width::UnicodeWidthStr;
-
-use crate::{
-    autofill::AutoFillFn,
-    wrap::{compute_character_width, wrap},
-};
-
-use super::*;
-
-#[derive(Default)]
-pub struct EditableText {
-    autofill_func: Option<AutoFillFn>,
-    autofill_text: Option<String>,
-    chars: Vec<char>,
-    cursor_offset: u16,
-    focused: bool,
-    insert_ind: usize,
-}
-
-impl EditableText {
-    pub fn new(base_content: &str, autofill_func: Option<AutoFillFn>) -> Self {
-        // input begins with base_content
-        let chars: Vec<char> = base_content.chars().collect();
-        let insert_ind = chars.len();
-        Self {
-            autofill_func,
-            autofill_text: None,
-            chars,
-            cursor_offset: base_content.width() as u16,
-            focused: false,
-            insert_ind,
-        }
-    }
-
-    /// Collects the stored collection of UTF-32 characters into a UTF-8 String
-    pub fn text(&self) -> String {
-        self.chars.iter().collect()
-    }
-
-    /// Returns true if there are no UTF-32 characters present in the input
-    pub fn is_empty(&self) -> bool {
-        self.chars.is_empty()
-    }
-
-    pub fn toggle_focus(&mut self) {
-        self.focused = !self.focused;
-        self.autofill_text = None;
-    }
-
-    pub fn render_with_style<S: Into<Style>>(
-        &mut self,
-        f: &mut Frame,
-        rect: Rect,
-        _block: Block,
-        style: S,
-    ) {
-        // clear previous text off the screen
-        f.render_widget(Clear, rect);
-
-        // get the lines of text to display and wrap them in the current rect
-        let content = self.text();
-        let mut lines = wrap(&content, rect.width);
-
-        // update the cursor position and other things required when focusing
-        if self.focused {
-            let line_widths = compute_line_widths(lines.as_slice());
-            // set the cursor to the intended position
-            let (rel_x, rel_y) =
-                compute_cursor_position(self.cursor_offset, line_widths.as_slice());
-            if let Some((x, y)) = cursor_within_rect(rel_x, rel_y, rect.width, rect.height) {
-                f.set_cursor_position((x + rect.x, y + rect.y));
-            }
-            if let Some(autofill) = &self.autofill_text {
-                if lines.is_empty() {
-                    // simply wrap and render the autofill content
-                    let autofill = wrap(autofill, rect.width);
-                    f.render_widget(
-                        Text::from_iter(autofill)
-                            .style(Style::new().fg(DEFAULT_APP_COLORS.selection_one_bg)),
-                        rect,
-                    );
-                } else {
-                    let final_line = lines.pop().unwrap();
-                    let combined = format!("{}{}", final_line, autofill);
-                    let autofill_lines = wrap(&combined, rect.width);
-                    let (orig, auto) = autofill_lines[0].split_at(final_line.len());
-                    let style: Style = style.into();
-                    let line = Line::from(vec![
-                        orig.set_style(style),
-                        auto.set_style(style.fg(DEFAULT_APP_COLORS.selection_one_bg)),
-                    ]);
-                    f.render_widget(
-                        Text::from_iter(
-                            lines
-                                .into_iter()
-                                .map(|s| Line::from(s).style(style))
-                                .chain(std::iter::once(line))
-                                .chain(autofill_lines.iter().skip(1).map(|s| {
-                                    Line::from(s.clone())
-                                        .style(style.fg(DEFAULT_APP_COLORS.selection_one_bg))
-                                })),
-                        )
-                        .style(style),
-                        rect,
-                    );
-                }
-                // don't allow further rendering as it would overwrite this change
-                return;
-            }
-        }
-        f.render_widget(Text::from_iter(lines).style(style), rect);
-    }
-}
-
-impl Component for EditableText {
-    fn handle_key_event(&mut self, key: KeyEvent) -> Result<Vec<Action>, Box<dyn Error>> {
-        // ignore key releases
-        if key.kind == KeyEventKind::Release {
-            return Ok(vec![Action::Noop]);
-        }
-
-        match key {
-            // as shift+enter doesn't work, ALT+\ is the key combo used for newlines
-            KeyEvent {
-                code: KeyCode::Char('\\'),
-                modifiers: KeyModifiers::ALT,
-                ..
-            } => {
-                let c = '\n';
-                self.chars.insert(self.insert_ind, c);
-                self.insert_ind += 1;
-                self.cursor_offset += 1;
-                return Ok(vec![Action::Noop]);
-            }
-            // have ctrl+space set the autofill suggestion string
-            KeyEvent {
-                code: KeyCode::Char(' '),
-                modifiers: KeyModifiers::CONTROL,
-                ..
-            } => {
-                self.autofill_text = if let Some(func) = &self.autofill_func {
-                    let text = self.text();
-                    func(text.as_str())
-                } else {
-                    None
-                };
-                return Ok(vec![Action::Noop]);
-            }
-            KeyEvent {
-                code: KeyCode::Tab,
-                modifiers: KeyModifiers::NONE,
-                ..
-            } => {
-                if let Some(autofill) = self.autofill_text.take() {
-                    // accept the autofill suggestion
-                    self.chars.extend(autofill.chars());
-                    self.cursor_offset += autofill.width() as u16;
-                    self.insert_ind = self.chars.len();
-                }
-                return Ok(vec![Action::Noop]);
-            }
-            _ => {}
-        }
-
-        match key.code {
-            KeyCode::Char(c) => {
-                self.chars.insert(self.insert_ind, c);
-                self.insert_ind += 1;
-                self.cursor_offset += compute_character_width(c);
-                // hide the autofill suggestion
-                self.autofill_text = None;
-            }
-            KeyCode::Backspace | KeyCode::Delete => {
-                if !self.chars.is_empty() && self.insert_ind > 0 {
-                    let c = self.chars.remove(self.insert_ind - 1);
-                    self.insert_ind -= 1;
-                    self.cursor_offset -= if c == '\n' {
-                        1
-                    } else {
-                        compute_character_width(c)
-                    };
-                    // hide the autofill suggestion
-                    self.autofill_text = None;
-                }
-            }
-            KeyCode::Left => {
-                if !self.chars.is_empty() && self.insert_ind > 0 {
-                    self.insert_ind -= 1;
-                    let c = self.chars[self.insert_ind];
-                    self.cursor_offset = if c == '\n' {
-                        self.cursor_offset.saturating_sub(1)
-                    } else {
-                        self.cursor_offset
-                            .saturating_sub(compute_character_width(c))
-                    };
-                }
-            }
-            KeyCode::Right => {
-                if self.insert_ind < self.chars.len() {
-                    let c = self.chars[self.insert_ind];
-                    self.insert_ind += 1;
-                    self.cursor_offset += if c == '\n' {
-                        1
-                    } else {
-                        compute_character_width(c)
-                    };
-                }
-            }
-            _ => {}
-        }
-        Ok(vec![Action::Noop])
-    }
-
-    fn render(&mut self, f: &mut Frame, rect: Rect, block: Block) {
-        self.render_with_style(
-            f,
-            rect,
-            block,
-            Style::new()
-                .fg(DEFAULT_APP_COLORS.main_fg)
-                .bg(DEFAULT_APP_COLORS.main_bg),
-        )
-    }
-}
-
-impl From<&str> for EditableText {
-    fn from(value: &str) -> Self {
-        Self::new(value, None)
-    }
-}
+use ratatui::{
+    crossterm::event::KeyModifiers,
+    style::Styled,
+    text::{Line, Text},
+    widgets::{Clear, Scrollbar, ScrollbarOrientation, ScrollbarState},
+};
+use unicode_width::UnicodeWidthStr;
+
+use crate::{
+    autofill::AutoFillFn,
+    wrap::{compute_character_width, compute_tab_width, expand_tabs, wrap},
+};
+
+use super::*;
+
+/// Most edits which can be undone within a single editor
+const MAX_UNDO_DEPTH: usize = 100;
+
+/// Content and insertion point of the text before an edit, which undoing
+/// the edit restores
+#[derive(Debug, Clone, Default)]
+struct TextSnapshot {
+    chars: Vec<char>,
+    insert_ind: usize,
+}
+
+#[derive(Default)]
+pub struct EditableText {
+    autofill_func: Option<AutoFillFn>,
+    autofill_text: Option<String>,
+    chars: Vec<char>,
+    cursor_offset: u16,
+    focused: bool,
+    insert_ind: usize,
+    select_all_pending: bool,
+    selection_start: Option<usize>,
+    /// Number of wrapped lines scrolled past at the top of the view
+    v_scroll_offset: u16,
+    /// Cursor offset the view was last scrolled to show, so the view only
+    /// follows the cursor when it moves
+    scrolled_cursor_offset: Option<u16>,
+    /// Snapshots taken before each edit, the most recent last
+    undo_history: Vec<TextSnapshot>,
+    /// Snapshots taken before each undo, cleared by any new edit
+    redo_history: Vec<TextSnapshot>,
+}
+
+impl EditableText {
+    pub fn new(base_content: &str, autofill_func: Option<AutoFillFn>) -> Self {
+        // input begins with base_content, without any null bytes as
+        // SQLite text values cannot contain them
+        let base_content = base_content.replace('\0', "");
+        let chars: Vec<char> = base_content.chars().collect();
+        let insert_ind = chars.len();
+        let mut text = Self {
+            autofill_func,
+            autofill_text: None,
+            chars,
+            cursor_offset: 0,
+            focused: false,
+            insert_ind,
+            select_all_pending: false,
+            selection_start: None,
+            v_scroll_offset: 0,
+            scrolled_cursor_offset: None,
+            undo_history: Vec::new(),
+            redo_history: Vec::new(),
+        };
+        // the content may contain tabs, whose widths depend on their position
+        text.recompute_cursor_offset();
+        // begin viewing the start of the content rather than following the
+        // cursor to its end
+        text.scrolled_cursor_offset = Some(text.cursor_offset);
+        text
+    }
+
+    /// Collects the stored collection of UTF-32 characters into a UTF-8 String
+    pub fn text(&self) -> String {
+        self.chars.iter().collect()
+    }
+
+    /// Returns true if there are no UTF-32 characters present in the input
+    pub fn is_empty(&self) -> bool {
+        self.chars.is_empty()
+    }
+
+    /// Selects the entire content, moving the insertion point to its end.
+    /// The next typed character or deletion replaces everything selected
+    pub fn select_all(&mut self) {
+        self.selection_start = Some(0);
+        self.insert_ind = self.chars.len();
+        self.recompute_cursor_offset();
+        self.select_all_pending = true;
+    }
+
+    /// Removes all content, resetting the insertion point to the start
+    pub fn clear(&mut self) {
+        self.chars.clear();
+        self.insert_ind = 0;
+        self.cursor_offset = 0;
+        self.autofill_text = None;
+    }
+
+    fn snapshot(&self) -> TextSnapshot {
+        TextSnapshot {
+            chars: self.chars.clone(),
+            insert_ind: self.insert_ind,
+        }
+    }
+
+    /// Records the current content so the edit about to be made can be
+    /// undone, forgetting any undone edits as they can no longer be redone
+    fn record_edit(&mut self) {
+        if self.undo_history.len() >= MAX_UNDO_DEPTH {
+            self.undo_history.remove(0);
+        }
+        self.undo_history.push(self.snapshot());
+        self.redo_history.clear();
+    }
+
+    /// Replaces the content with the passed snapshot
+    fn restore(&mut self, snapshot: TextSnapshot) {
+        self.chars = snapshot.chars;
+        self.insert_ind = snapshot.insert_ind;
+        self.recompute_cursor_offset();
+        self.autofill_text = None;
+    }
+
+    /// Reverts the most recent edit, returning false if there was none
+    fn undo(&mut self) -> bool {
+        let Some(snapshot) = self.undo_history.pop() else {
+            return false;
+        };
+        self.redo_history.push(self.snapshot());
+        self.restore(snapshot);
+        true
+    }
+
+    /// Remakes the most recently undone edit, returning false if there
+    /// was none
+    fn redo(&mut self) -> bool {
+        let Some(snapshot) = self.redo_history.pop() else {
+            return false;
+        };
+        self.undo_history.push(self.snapshot());
+        self.restore(snapshot);
+        true
+    }
+
+    /// Copies the entire content to the system clipboard, then clears it.
+    /// The content is kept if it couldn't be copied so nothing is lost,
+    /// otherwise the cut can be undone like any other edit
+    fn cut(&mut self) -> Vec<Action> {
+        if self.chars.is_empty() {
+            return vec![Action::VeryLoudWrongBuzzer];
+        }
+        match arboard::Clipboard::new().and_then(|mut clipboard| clipboard.set_text(self.text())) {
+            Ok(()) => {
+                self.record_edit();
+                self.clear();
+                vec![Action::StatusMessage("Cut to clipboard".to_string())]
+            }
+            Err(err) => vec![
+                Action::VeryLoudWrongBuzzer,
+                Action::StatusMessage(format!("Failed to cut: {}", err)),
+            ],
+        }
+    }
+
+    /// Recomputes the display offset of the cursor from the characters which
+    /// come before the insertion point, expanding tabs to their tab stops
+    fn recompute_cursor_offset(&mut self) {
+        let mut offset = 0;
+        // column within the current line, which determines the width of tabs
+        let mut col = 0;
+        for c in &self.chars[..self.insert_ind] {
+            match c {
+                '\n' => {
+                    offset += 1;
+                    col = 0;
+                }
+                '\t' => {
+                    let width = compute_tab_width(col);
+                    offset += width;
+                    col += width;
+                }
+                _ => {
+                    let width = compute_character_width(*c);
+                    offset += width;
+                    col += width;
+                }
+            }
+        }
+        self.cursor_offset = offset;
+    }
+
+    /// Returns true if up or down held with the passed modifiers scroll the
+    /// view by a line, which holds for ctrl or, while the text is only being
+    /// read, no modifiers
+    fn scrolls_by_line(&self, modifiers: KeyModifiers) -> bool {
+        modifiers == KeyModifiers::CONTROL || (!self.focused && modifiers == KeyModifiers::NONE)
+    }
+
+    pub fn toggle_focus(&mut self) {
+        self.focused = !self.focused;
+        self.autofill_text = None;
+        // ensure no stale cursor position carries over between focuses,
+        // such as after accepting an autofill suggestion
+        self.insert_ind = self.insert_ind.min(self.chars.len());
+        self.recompute_cursor_offset();
+        // show where the cursor is once focused
+        if self.focused {
+            self.scrolled_cursor_offset = None;
+        }
+    }
+
+    pub fn render_with_style<S: Into<Style>>(
+        &mut self,
+        f: &mut Frame,
+        rect: Rect,
+        _block: Block,
+        style: S,
+    ) {
+        // clear previous text off the screen
+        f.render_widget(Clear, rect);
+
+        // get the lines of text to display and wrap them in the current rect,
+        // leaving the rightmost column for a scrollbar if they don't all fit
+        let text = self.text();
+        let content = expand_tabs(&text);
+        let mut lines = wrap(&content, rect.width);
+        let mut rect = rect;
+        let scrollbar_rect = rect;
+        if lines.len() > rect.height as usize {
+            rect.width = rect.width.saturating_sub(1);
+            lines = wrap(&content, rect.width);
+        }
+
+        // find the cursor's line within all of the content, not just the view
+        let line_widths = compute_line_widths(lines.as_slice());
+        let (rel_x, rel_y) =
+            compute_cursor_position(self.cursor_offset, line_widths.as_slice(), u16::MAX);
+        let cursor = cursor_within_rect(rel_x, rel_y, rect.width, u16::MAX);
+        let total_lines = cursor.map_or(lines.len(), |(_, y)| lines.len().max(y as usize + 1));
+        // follow the cursor whenever it moves, otherwise keep the scrolled view
+        if self.scrolled_cursor_offset != Some(self.cursor_offset)
+            && let Some((_, y)) = cursor
+        {
+            if y < self.v_scroll_offset {
+                self.v_scroll_offset = y;
+            } else if y >= self.v_scroll_offset + rect.height {
+                self.v_scroll_offset = y + 1 - rect.height;
+            }
+            self.scrolled_cursor_offset = Some(self.cursor_offset);
+        }
+        let max_scroll = total_lines.saturating_sub(rect.height as usize) as u16;
+        self.v_scroll_offset = self.v_scroll_offset.min(max_scroll);
+        if max_scroll > 0 {
+            let mut scroll_state = ScrollbarState::new(max_scroll as usize + 1)
+                .position(self.v_scroll_offset as usize);
+            f.render_stateful_widget(
+                Scrollbar::default()
+                    .orientation(ScrollbarOrientation::VerticalRight)
+                    .begin_symbol(None)
+                    .end_symbol(None)
+                    .style(DEFAULT_APP_COLORS.main_fg),
+                scrollbar_rect,
+                &mut scroll_state,
+            );
+        }
+        // only the lines within the view are shown, with the cursor relative to it
+        let mut lines: Vec<_> = lines
+            .into_iter()
+            .skip(self.v_scroll_offset as usize)
+            .collect();
+        let cursor = cursor.and_then(|(x, y)| {
+            let y = y.checked_sub(self.v_scroll_offset)?;
+            (y < rect.height).then_some((x, y))
+        });
+
+        // update the cursor position and other things required when focusing
+        if self.focused {
+            // set the cursor to the intended position
+            if let Some((x, y)) = cursor {
+                f.set_cursor_position((x + rect.x, y + rect.y));
+            }
+            if let Some(autofill) = &self.autofill_text {
+                if lines.is_empty() {
+                    // simply wrap and render the autofill content
+                    let autofill = wrap(autofill, rect.width);
+                    f.render_widget(
+                        Text::from_iter(autofill)
+                            .style(Style::new().fg(DEFAULT_APP_COLORS.selection_one_bg)),
+                        rect,
+                    );
+                } else {
+                    let final_line = lines.pop().unwrap();
+                    let combined = format!("{}{}", final_line, autofill);
+                    let autofill_lines = wrap(&combined, rect.width);
+                    let (orig, auto) = autofill_lines[0].split_at(final_line.len());
+                    let style: Style = style.into();
+                    let line = Line::from(vec![
+                        orig.set_style(style),
+                        auto.set_style(style.fg(DEFAULT_APP_COLORS.selection_one_bg)),
+                    ]);
+                    f.render_widget(
+                        Text::from_iter(
+                            lines
+                                .into_iter()
+                                .map(|s| Line::from(s).style(style))
+                                .chain(std::iter::once(line))
+                                .chain(autofill_lines.iter().skip(1).map(|s| {
+                                    Line::from(s.clone())
+                                        .style(style.fg(DEFAULT_APP_COLORS.selection_one_bg))
+                                })),
+                        )
+                        .style(style),
+                        rect,
+                    );
+                }
+                // don't allow further rendering as it would overwrite this change
+                return;
+            }
+        }
+        let ghost_cursor = if !self.focused && self.insert_ind > 0 {
+            cursor
+        } else {
+            None
+        };
+        f.render_widget(Text::from_iter(lines).style(style), rect);
+        // show the reading position while unfocused by reversing the
+        // character the cursor would be on
+        if let Some((x, y)) = ghost_cursor
+            && let Some(cell) = f.buffer_mut().cell_mut((x + rect.x, y + rect.y))
+        {
+            cell.set_style(Style::new().reversed());
+        }
+    }
+}
+
+impl Component for EditableText {
+    fn name(&self) -> &'static str {
+        "Editor"
+    }
+
+    fn handle_resize(&mut self, _width: u16, _height: u16) {
+        self.recompute_cursor_offset();
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent) -> Result<Vec<Action>, AppError> {
+        // ignore key releases
+        if key.kind == KeyEventKind::Release {
+            return Ok(vec![Action::Noop]);
+        }
+
+        // a pending select all only applies to the key directly following it
+        let select_all_pending = std::mem::take(&mut self.select_all_pending);
+        self.selection_start = None;
+
+        match key {
+            // as shift+enter doesn't work, ALT+\ is the key combo used for newlines
+            KeyEvent {
+                code: KeyCode::Char('\\'),
+                modifiers: KeyModifiers::ALT,
+                ..
+            } => {
+                self.record_edit();
+                let c = '\n';
+                self.chars.insert(self.insert_ind, c);
+                self.insert_ind += 1;
+                self.cursor_offset += 1;
+                return Ok(vec![Action::Noop]);
+            }
+            // have ctrl+space set the autofill suggestion string
+            KeyEvent {
+                code: KeyCode::Char(' '),
+                modifiers: KeyModifiers::CONTROL,
+                ..
+            } => {
+                self.autofill_text = if let Some(func) = &self.autofill_func {
+                    let text = self.text();
+                    func(text.as_str(), self.insert_ind)
+                } else {
+                    None
+                };
+                return Ok(vec![Action::Noop]);
+            }
+            KeyEvent {
+                code: KeyCode::Char('a'),
+                modifiers: KeyModifiers::CONTROL,
+                ..
+            } => {
+                self.select_all();
+                return Ok(vec![Action::Noop]);
+            }
+            // ctrl+up and ctrl+down scroll the view without moving the cursor,
+            // as do up and down while the text is only being read
+            KeyEvent {
+                code: KeyCode::Up,
+                modifiers,
+                ..
+            } if self.scrolls_by_line(modifiers) => {
+                self.v_scroll_offset = self.v_scroll_offset.saturating_sub(1);
+                return Ok(vec![Action::Noop]);
+            }
+            KeyEvent {
+                code: KeyCode::Down,
+                modifiers,
+                ..
+            } if self.scrolls_by_line(modifiers) => {
+                // clamped to the content when rendering
+                self.v_scroll_offset = self.v_scroll_offset.saturating_add(1);
+                return Ok(vec![Action::Noop]);
+            }
+            // ctrl+z and ctrl+y undo and redo edits to the text
+            KeyEvent {
+                code: KeyCode::Char('z'),
+                modifiers: KeyModifiers::CONTROL,
+                ..
+            } => {
+                return Ok(if self.undo() {
+                    vec![Action::Noop]
+                } else {
+                    vec![Action::VeryLoudWrongBuzzer]
+                });
+            }
+            KeyEvent {
+                code: KeyCode::Char('y'),
+                modifiers: KeyModifiers::CONTROL,
+                ..
+            } => {
+                return Ok(if self.redo() {
+                    vec![Action::Noop]
+                } else {
+                    vec![Action::VeryLoudWrongBuzzer]
+                });
+            }
+            // ctrl+x cuts the whole content, as select all is the only selection
+            KeyEvent {
+                code: KeyCode::Char('x'),
+                modifiers: KeyModifiers::CONTROL,
+                ..
+            } => return Ok(self.cut()),
+            // ctrl+tab inserts a literal tab, as tab accepts autofills
+            KeyEvent {
+                code: KeyCode::Tab,
+                modifiers: KeyModifiers::CONTROL,
+                ..
+            } => {
+                self.record_edit();
+                if select_all_pending {
+                    // overwrite the selected content
+                    self.clear();
+                }
+                self.chars.insert(self.insert_ind, '\t');
+                self.insert_ind += 1;
+                self.recompute_cursor_offset();
+                self.autofill_text = None;
+                return Ok(vec![Action::Noop]);
+            }
+            KeyEvent {
+                code: KeyCode::Tab,
+                modifiers: KeyModifiers::NONE,
+                ..
+            } => {
+                if let Some(autofill) = self.autofill_text.take() {
+                    // accept the autofill suggestion
+                    self.record_edit();
+                    self.chars.extend(autofill.chars());
+                    self.cursor_offset += autofill.width() as u16;
+                    self.insert_ind = self.chars.len();
+                }
+                return Ok(vec![Action::Noop]);
+            }
+            _ => {}
+        }
+
+        match key.code {
+            KeyCode::Char('\0') => {
+                // some terminals send null bytes for certain key combinations,
+                // which can't be stored within SQLite text, so ignore them
+            }
+            KeyCode::Char(c) => {
+                self.record_edit();
+                if select_all_pending {
+                    // overwrite the selected content
+                    self.clear();
+                }
+                self.chars.insert(self.insert_ind, c);
+                self.insert_ind += 1;
+                self.cursor_offset += compute_character_width(c);
+                // hide the autofill suggestion
+                self.autofill_text = None;
+            }
+            KeyCode::Backspace | KeyCode::Delete if select_all_pending => {
+                self.record_edit();
+                self.clear();
+            }
+            KeyCode::Backspace | KeyCode::Delete
+                if !self.chars.is_empty() && self.insert_ind > 0 =>
+            {
+                self.record_edit();
+                let c = self.chars.remove(self.insert_ind - 1);
+                self.insert_ind -= 1;
+                if c == '\t' {
+                    // the width of a tab depends on the characters before it
+                    self.recompute_cursor_offset();
+                } else {
+                    self.cursor_offset -= if c == '\n' {
+                        1
+                    } else {
+                        compute_character_width(c)
+                    };
+                }
+                // hide the autofill suggestion
+                self.autofill_text = None;
+            }
+            KeyCode::Left if !self.chars.is_empty() && self.insert_ind > 0 => {
+                self.insert_ind -= 1;
+                let c = self.chars[self.insert_ind];
+                match c {
+                    '\n' => self.cursor_offset = self.cursor_offset.saturating_sub(1),
+                    // the width of a tab depends on the characters before it
+                    '\t' => self.recompute_cursor_offset(),
+                    _ => {
+                        self.cursor_offset = self
+                            .cursor_offset
+                            .saturating_sub(compute_character_width(c))
+                    }
+                }
+            }
+            KeyCode::Right if self.insert_ind < self.chars.len() => {
+                let c = self.chars[self.insert_ind];
+                self.insert_ind += 1;
+                match c {
+                    '\n' => self.cursor_offset += 1,
+                    '\t' => self.recompute_cursor_offset(),
+                    _ => self.cursor_offset += compute_character_width(c),
+                }
+            }
+            _ => {}
+        }
+        Ok(vec![Action::Noop])
+    }
+
+    fn render(&mut self, f: &mut Frame, rect: Rect, block: Block) {
+        self.render_with_style(
+            f,
+            rect,
+            block,
+            Style::new()
+                .fg(DEFAULT_APP_COLORS.main_fg)
+                .bg(DEFAULT_APP_COLORS.main_bg),
+        )
+    }
+}
+
+impl From<&str> for EditableText {
+    fn from(value: &str) -> Self {
+        Self::new(value, None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::{Terminal, backend::TestBackend, crossterm::event::KeyModifiers};
+
+    use super::*;
+
+    /// Renders the editor within a rect of the passed size, returning the
+    /// rendered lines, which include the scrollbar if one is shown
+    fn render_lines(editor: &mut EditableText, width: u16, height: u16) -> Vec<String> {
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        terminal
+            .draw(|f| editor.render(f, f.area(), Block::new()))
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+        (0..height)
+            .map(|y| {
+                (0..width)
+                    .map(|x| buffer[(x, y)].symbol())
+                    .collect::<String>()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn null_bytes_are_stripped_from_the_base_content() {
+        let editor = EditableText::new("ab\0c\0", None);
+        assert!(!editor.text().contains('\0'));
+        assert_eq!(editor.text(), "abc");
+    }
+
+    #[test]
+    fn typed_null_bytes_are_ignored() {
+        let mut editor = EditableText::from("ab");
+        editor.toggle_focus();
+        for c in ['\0', 'c'] {
+            editor
+                .handle_key_event(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE))
+                .unwrap();
+        }
+        assert_eq!(editor.text(), "abc");
+    }
+
+    #[test]
+    fn undo_and_redo_revert_and_remake_edits() {
+        let mut editor = EditableText::from("ab");
+        editor.toggle_focus();
+        for (code, modifiers) in [
+            (KeyCode::Char('c'), KeyModifiers::NONE),
+            (KeyCode::Backspace, KeyModifiers::NONE),
+            (KeyCode::Backspace, KeyModifiers::NONE),
+            (KeyCode::Char('z'), KeyModifiers::CONTROL),
+            (KeyCode::Char('z'), KeyModifiers::CONTROL),
+        ] {
+            editor
+                .handle_key_event(KeyEvent::new(code, modifiers))
+                .unwrap();
+        }
+        assert_eq!(editor.text(), "abc");
+        editor
+            .handle_key_event(KeyEvent::new(KeyCode::Char('y'), KeyModifiers::CONTROL))
+            .unwrap();
+        assert_eq!(editor.text(), "ab");
+        assert_eq!(editor.insert_ind, 2);
+    }
+
+    #[test]
+    fn a_cut_can_be_undone() {
+        let mut editor = EditableText::from("some text");
+        editor.toggle_focus();
+        let actions = editor
+            .handle_key_event(KeyEvent::new(KeyCode::Char('x'), KeyModifiers::CONTROL))
+            .unwrap();
+        // the clipboard may be unreachable where the tests run, which keeps
+        // the text as it was
+        let cut = actions.contains(&Action::StatusMessage("Cut to clipboard".to_string()));
+        assert_eq!(editor.is_empty(), cut);
+        editor
+            .handle_key_event(KeyEvent::new(KeyCode::Char('z'), KeyModifiers::CONTROL))
+            .unwrap();
+        assert_eq!(editor.text(), "some text");
+    }
+
+    #[test]
+    fn up_and_down_scroll_the_reader() {
+        let mut reader = EditableText::from("one\ntwo\nthree\nfour");
+        render_lines(&mut reader, 10, 2);
+        reader
+            .handle_key_event(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE))
+            .unwrap();
+        assert!(render_lines(&mut reader, 10, 2)[0].starts_with("two "));
+        reader
+            .handle_key_event(KeyEvent::new(KeyCode::Up, KeyModifiers::NONE))
+            .unwrap();
+        assert!(render_lines(&mut reader, 10, 2)[0].starts_with("one "));
+    }
+}
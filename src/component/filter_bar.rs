@@ -0,0 +1,91 @@
+use ratatui::{
+    layout::{Constraint, Layout},
+    widgets::Paragraph,
+};
+
+use super::{editable_text::EditableText, *};
+
+/// Single line bar which filters the shown table as its search term is typed,
+/// alongside how many rows the term matched
+pub struct FilterBar {
+    input: EditableText,
+    /// Number of rows matching the current term, if it has been filtered by
+    result_count: Option<usize>,
+}
+
+impl Default for FilterBar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FilterBar {
+    /// Creates the bar with an empty, already focused input
+    pub fn new() -> Self {
+        let mut input = EditableText::from("");
+        input.toggle_focus();
+        Self {
+            input,
+            result_count: None,
+        }
+    }
+
+    /// Returns the search term currently entered into the bar
+    pub fn term(&self) -> String {
+        self.input.text()
+    }
+
+    /// Sets the number of rows the current term matched
+    pub fn set_result_count(&mut self, count: usize) {
+        self.result_count = Some(count);
+    }
+}
+
+impl Component for FilterBar {
+    fn name(&self) -> &'static str {
+        "Filter"
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent) -> Result<Vec<Action>, AppError> {
+        // ignore key releases
+        if key.kind == KeyEventKind::Release {
+            return Ok(vec![Action::Noop]);
+        }
+
+        match key.code {
+            KeyCode::Esc => Ok(vec![Action::EndFilter]),
+            // the bar is a single line, so there's nothing to submit
+            KeyCode::Enter => Ok(vec![Action::Noop]),
+            _ => self.input.handle_key_event(key),
+        }
+    }
+
+    fn render(&mut self, f: &mut Frame, rect: Rect, _block: Block) {
+        let [prefix_rect, input_rect, count_rect] = Layout::horizontal([
+            Constraint::Length(2),
+            Constraint::Min(1),
+            Constraint::Length(14),
+        ])
+        .areas(rect);
+        f.render_widget(
+            Paragraph::new("/ ")
+                .fg(DEFAULT_APP_COLORS.header_fg)
+                .bg(DEFAULT_APP_COLORS.header_bg),
+            prefix_rect,
+        );
+        self.input.render(f, input_rect, Block::new());
+
+        let count = match self.result_count {
+            Some(0) => Paragraph::new("0 results ").fg(DEFAULT_APP_COLORS.error_fg),
+            Some(1) => Paragraph::new("1 result ").fg(DEFAULT_APP_COLORS.main_fg),
+            Some(count) => {
+                Paragraph::new(format!("{} results ", count)).fg(DEFAULT_APP_COLORS.main_fg)
+            }
+            None => Paragraph::new(""),
+        };
+        f.render_widget(
+            count.right_aligned().bg(DEFAULT_APP_COLORS.alt_bg),
+            count_rect,
+        );
+    }
+}
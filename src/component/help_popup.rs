@@ -0,0 +1,104 @@
+use ratatui::{
+    layout::{Constraint, Layout},
+    widgets::{
+        Clear, Row, Scrollbar, ScrollbarOrientation, ScrollbarState, Table as TuiTable,
+        TableState as TuiTableState,
+    },
+};
+
+use super::*;
+use crate::config::KEYBINDINGS;
+
+/// Popup listing every keybinding of the app alongside what it does,
+/// scrolled through with the up and down keys
+pub struct HelpPopup {
+    table_state: TuiTableState,
+    scroll_state: ScrollbarState,
+}
+
+impl Default for HelpPopup {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HelpPopup {
+    pub fn new() -> Self {
+        Self {
+            table_state: TuiTableState::new().with_selected(Some(0)),
+            scroll_state: ScrollbarState::new(KEYBINDINGS.len().saturating_sub(1)),
+        }
+    }
+}
+
+impl Component for HelpPopup {
+    fn name(&self) -> &'static str {
+        "Help"
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent) -> Result<Vec<Action>, AppError> {
+        // ignore key releases
+        if key.kind == KeyEventKind::Release {
+            return Ok(vec![Action::Noop]);
+        }
+
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('?') => Ok(vec![Action::Quit]), // close popup
+            KeyCode::Up => {
+                self.table_state.select_previous();
+                self.scroll_state.prev();
+                Ok(vec![Action::Noop])
+            }
+            KeyCode::Down => {
+                // the table state doesn't clamp to the last row until rendered
+                if self
+                    .table_state
+                    .selected()
+                    .is_some_and(|y| y + 1 < KEYBINDINGS.len())
+                {
+                    self.table_state.select_next();
+                    self.scroll_state.next();
+                }
+                Ok(vec![Action::Noop])
+            }
+            _ => Ok(vec![Action::Noop]),
+        }
+    }
+
+    fn render(&mut self, f: &mut Frame, rect: Rect, block: Block) {
+        // clear the rendered content behind the popup
+        f.render_widget(Clear, rect);
+        let inner = block.inner(rect);
+        f.render_widget(
+            block.title(" Keybindings ").bg(DEFAULT_APP_COLORS.alt_bg),
+            rect,
+        );
+
+        let [table_rect, scrollbar_rect] =
+            Layout::horizontal([Constraint::Fill(1), Constraint::Length(1)]).areas(inner);
+        let key_width = KEYBINDINGS
+            .iter()
+            .map(|(key, _)| key.width() as u16)
+            .max()
+            .unwrap_or(0);
+        let table = TuiTable::new(
+            KEYBINDINGS
+                .iter()
+                .map(|(key, description)| Row::new([*key, *description])),
+            [Constraint::Length(key_width), Constraint::Fill(1)],
+        )
+        .column_spacing(2)
+        .fg(DEFAULT_APP_COLORS.main_fg)
+        .row_highlight_style(Style::new().reversed());
+        f.render_stateful_widget(table, table_rect, &mut self.table_state);
+        f.render_stateful_widget(
+            Scrollbar::default()
+                .orientation(ScrollbarOrientation::VerticalRight)
+                .begin_symbol(None)
+                .end_symbol(None)
+                .style(DEFAULT_APP_COLORS.main_fg),
+            scrollbar_rect,
+            &mut self.scroll_state,
+        );
+    }
+}
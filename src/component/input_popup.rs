@@ -0,0 +1,69 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout},
+    widgets::{Clear, Paragraph},
+};
+
+use super::{editable_text::EditableText, *};
+
+/// Popup which prompts for a single text value using an [`EditableText`]
+pub struct InputPopup {
+    prompt: String,
+    input: EditableText,
+}
+
+impl InputPopup {
+    /// Creates a popup showing the passed prompt with an already focused
+    /// input which begins with the passed content
+    pub fn new(prompt: String, base_content: &str) -> Self {
+        let mut input = EditableText::from(base_content);
+        input.toggle_focus();
+        Self { prompt, input }
+    }
+
+    /// Returns the text currently entered into the popup
+    pub fn value(&self) -> String {
+        self.input.text()
+    }
+}
+
+impl Component for InputPopup {
+    fn name(&self) -> &'static str {
+        "Input"
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent) -> Result<Vec<Action>, AppError> {
+        // ignore key releases
+        if key.kind == KeyEventKind::Release {
+            return Ok(vec![Action::Noop]);
+        }
+
+        match key.code {
+            KeyCode::Esc => Ok(vec![Action::Quit]), // close popup
+            KeyCode::Enter => Ok(vec![Action::NotifyCompletion]), // notify container
+            _ => self.input.handle_key_event(key),
+        }
+    }
+
+    fn render(&mut self, f: &mut Frame, rect: Rect, block: Block) {
+        // clear the rendered content behind the popup
+        f.render_widget(Clear, rect);
+        // render the border, clearing the background behind it
+        let inner = block.inner(rect);
+        f.render_widget(block.bg(DEFAULT_APP_COLORS.alt_bg), rect);
+
+        let [prompt_rect, input_rect] = *Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Min(1)])
+            .split(inner)
+        else {
+            panic!("Not enough size to create the necessary rects");
+        };
+        f.render_widget(
+            Paragraph::new(Cow::from(&self.prompt))
+                .centered()
+                .fg(DEFAULT_APP_COLORS.main_fg),
+            prompt_rect,
+        );
+        self.input.render(f, input_rect, Block::new());
+    }
+}
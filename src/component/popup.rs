@@ -1,97 +1,179 @@
-use ratatui::{
-    layout::{Constraint, Direction, Layout},
-    widgets::{Clear, Paragraph},
-};
-
-use super::*;
-
-pub struct PopUpComponent {
-    prompt: String,
-    choices: Vec<String>,
-    highlit: u16,
-}
-
-impl PopUpComponent {
-    pub fn new(prompt: String, choices: Vec<String>, initial_ind: Option<u16>) -> Self {
-        Self {
-            prompt,
-            choices,
-            highlit: initial_ind.unwrap_or(0),
-        }
-    }
-
-    pub fn get_choice(&self) -> u16 {
-        self.highlit
-    }
-}
-
-impl Component for PopUpComponent {
-    fn handle_key_event(&mut self, key: KeyEvent) -> Result<Vec<Action>, Box<dyn Error>> {
-        // ignore key releases
-        if key.kind == KeyEventKind::Release {
-            return Ok(vec![Action::Noop]);
-        }
-
-        match key.code {
-            KeyCode::Esc => Ok(vec![Action::Quit]), // close popup
-            KeyCode::Enter => Ok(vec![Action::NotifyCompletion]), // notify container
-            KeyCode::Left => {
-                self.highlit = self.highlit.saturating_sub(1);
-                Ok(vec![Action::Noop])
-            }
-            KeyCode::Right => {
-                self.highlit = (self.highlit + 1).min(self.choices.len() as u16 - 1);
-                Ok(vec![Action::Noop])
-            }
-            _ => Ok(vec![Action::Noop]),
-        }
-    }
-
-    fn render(&mut self, f: &mut Frame, rect: Rect, block: Block) {
-        let [prompt_rect, choices_rect] = *Layout::default()
-            .margin(1)
-            .direction(Direction::Vertical)
-            .constraints([Constraint::Percentage(75), Constraint::Percentage(25)])
-            .split(rect)
-        else {
-            todo!()
-        };
-        let prompt = Paragraph::new(Cow::from(&self.prompt))
-            .centered()
-            .fg(DEFAULT_APP_COLORS.main_fg);
-        // generate the Rects that each option will use based on constraints
-        // derived from the width of each option
-        let choice_rects = Layout::default()
-            .margin(0)
-            .direction(Direction::Horizontal)
-            .constraints(self.choices.iter().map(|choice| {
-                Constraint::Min(choice.width().min(rect.width as usize / self.choices.len()) as u16)
-            }))
-            .split(choices_rect);
-        let choices: Vec<Paragraph> = self
-            .choices
-            .iter()
-            .enumerate()
-            .map(|(ind, choice)| {
-                let mut paragraph = Paragraph::new(Cow::from(choice))
-                    .centered()
-                    .fg(DEFAULT_APP_COLORS.main_fg)
-                    .bg(DEFAULT_APP_COLORS.main_bg);
-                if self.highlit == ind as u16 {
-                    paragraph = paragraph.reversed();
-                }
-                paragraph
-            })
-            .collect();
-        // clear the rendered content behind the popup
-        f.render_widget(Clear, rect);
-        // render the border, clearing the background behind it
-        f.render_widget(block.bg(DEFAULT_APP_COLORS.alt_bg), rect);
-        // render the prompt
-        f.render_widget(prompt, prompt_rect);
-        // render each choice
-        for (choice, choice_rect) in std::iter::zip(choices, choice_rects.iter()) {
-            f.render_widget(choice, *choice_rect);
-        }
-    }
-}
+use std::rc::Rc;
+
+use ratatui::{
+    layout::{Constraint, Direction, Layout},
+    text::{Line, Text},
+    widgets::{Clear, Paragraph},
+};
+
+use super::*;
+use crate::wrap::{truncate_with_ellipsis, wrap_with_max_lines};
+
+pub struct PopUpComponent {
+    prompt: String,
+    choices: Vec<String>,
+    highlit: u16,
+}
+
+impl PopUpComponent {
+    pub fn new(prompt: String, choices: Vec<String>, initial_ind: Option<u16>) -> Self {
+        Self {
+            prompt,
+            choices,
+            highlit: initial_ind.unwrap_or(0),
+        }
+    }
+
+    pub fn get_choice(&self) -> u16 {
+        self.highlit
+    }
+
+    /// Splits the passed area evenly between the choices so that none of
+    /// them overlap, whatever the width of their text
+    fn choice_rects(&self, choices_rect: Rect) -> Rc<[Rect]> {
+        Layout::default()
+            .margin(0)
+            .direction(Direction::Horizontal)
+            .constraints(
+                self.choices
+                    .iter()
+                    .map(|_| Constraint::Ratio(1, self.choices.len() as u32)),
+            )
+            .split(choices_rect)
+    }
+}
+
+impl Component for PopUpComponent {
+    fn name(&self) -> &'static str {
+        "Confirm"
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent) -> Result<Vec<Action>, AppError> {
+        // ignore key releases
+        if key.kind == KeyEventKind::Release {
+            return Ok(vec![Action::Noop]);
+        }
+
+        match key.code {
+            KeyCode::Esc => Ok(vec![Action::Quit]), // close popup
+            KeyCode::Enter => Ok(vec![Action::NotifyCompletion]), // notify container
+            KeyCode::Left => {
+                self.highlit = self.highlit.saturating_sub(1);
+                Ok(vec![Action::Noop])
+            }
+            KeyCode::Right => {
+                self.highlit = (self.highlit + 1).min(self.choices.len() as u16 - 1);
+                Ok(vec![Action::Noop])
+            }
+            _ => Ok(vec![Action::Noop]),
+        }
+    }
+
+    fn render(&mut self, f: &mut Frame, rect: Rect, block: Block) {
+        // wrap the prompt before laying out so it gets as many lines as it needs,
+        // leaving a line for the choices within the margin
+        let max_prompt_lines = rect.height.saturating_sub(3) as usize;
+        let prompt_lines =
+            wrap_with_max_lines(&self.prompt, rect.width.saturating_sub(2), max_prompt_lines);
+        let [prompt_rect, choices_rect] = *Layout::default()
+            .margin(1)
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(prompt_lines.len() as u16),
+                Constraint::Length(1),
+            ])
+            .split(rect)
+        else {
+            todo!()
+        };
+        let prompt = Paragraph::new(Text::from_iter(
+            prompt_lines
+                .into_iter()
+                .map(|line| Line::from(line.trim_end_matches('\n').to_string())),
+        ))
+        .centered()
+        .fg(DEFAULT_APP_COLORS.main_fg);
+        let choice_rects = self.choice_rects(choices_rect);
+        let choices: Vec<Paragraph> = std::iter::zip(&self.choices, choice_rects.iter())
+            .enumerate()
+            .map(|(ind, (choice, choice_rect))| {
+                // cut off choices too wide for their share of the width
+                let mut paragraph =
+                    Paragraph::new(truncate_with_ellipsis(choice, choice_rect.width))
+                        .centered()
+                        .fg(DEFAULT_APP_COLORS.main_fg)
+                        .bg(DEFAULT_APP_COLORS.main_bg);
+                if self.highlit == ind as u16 {
+                    paragraph = paragraph.reversed();
+                }
+                paragraph
+            })
+            .collect();
+        // clear the rendered content behind the popup
+        f.render_widget(Clear, rect);
+        // render the border, clearing the background behind it
+        f.render_widget(block.bg(DEFAULT_APP_COLORS.alt_bg), rect);
+        // render the prompt
+        f.render_widget(prompt, prompt_rect);
+        // render each choice
+        for (choice, choice_rect) in std::iter::zip(choices, choice_rects.iter()) {
+            f.render_widget(choice, *choice_rect);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::{Terminal, backend::TestBackend};
+
+    use super::*;
+
+    fn three_choices() -> PopUpComponent {
+        PopUpComponent::new(
+            "Pick one".to_string(),
+            ["Yes", "No", "Cancel"].map(String::from).to_vec(),
+            None,
+        )
+    }
+
+    #[test]
+    fn choice_rects_never_overlap() {
+        let popup = three_choices();
+        for width in [10, 40, 80] {
+            let area = Rect::new(0, 0, width, 1);
+            let rects = popup.choice_rects(area);
+            assert_eq!(rects.len(), 3);
+            for pair in rects.windows(2) {
+                assert!(!pair[0].intersects(pair[1]), "overlap at width {}", width);
+                assert!(pair[0].right() <= pair[1].left());
+            }
+            assert!(rects.iter().all(|rect| area.contains(rect.as_position())));
+            assert!(rects.last().unwrap().right() <= area.right());
+        }
+    }
+
+    #[test]
+    fn rendered_choices_fit_within_their_share() {
+        for width in [10, 40, 80] {
+            let mut terminal = Terminal::new(TestBackend::new(width, 5)).unwrap();
+            let mut popup = three_choices();
+            terminal
+                .draw(|f| popup.render(f, f.area(), Block::bordered()))
+                .unwrap();
+            // the choices are on the line above the bottom border
+            let buffer = terminal.backend().buffer();
+            let rects = popup.choice_rects(Rect::new(1, 3, width - 2, 1));
+            for (choice, rect) in std::iter::zip(&popup.choices, rects.iter()) {
+                let shown: String = (rect.left()..rect.right())
+                    .map(|x| buffer[(x, rect.y)].symbol())
+                    .collect();
+                assert_eq!(
+                    shown.trim(),
+                    truncate_with_ellipsis(choice, rect.width),
+                    "choice at width {}",
+                    width
+                );
+            }
+        }
+    }
+}
@@ -1,109 +1,398 @@
-use std::{borrow::Cow, collections::HashMap};
-
-use command_list::EditCommand;
-use ratatui::widgets::{List, ListItem, ListState};
-
-use crate::{autofill::AutoFillFn, config::editable_tables};
-
-use super::*;
-
-#[derive(Debug, Clone)]
-pub struct TableMetadata {
-    pub(crate) commands: Vec<EditCommand>,
-    pub(crate) display_name: &'static str,
-    pub(crate) table_name: &'static str,
-    pub(crate) autofill_funcs: HashMap<&'static str, AutoFillFn>,
-}
-
-impl std::fmt::Display for TableMetadata {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.display_name)
-    }
-}
-
-pub struct TableSelection {
-    allowed_tables: Vec<TableMetadata>,
-    selected_ind: usize,
-    state: ListState,
-}
-
-impl TableSelection {
-    pub fn new() -> Self {
-        Self {
-            allowed_tables: editable_tables(),
-            selected_ind: 0,
-            state: ListState::default().with_selected(Some(0)),
-        }
-    }
-
-    pub fn selected(&self) -> Option<&TableMetadata> {
-        if !self.allowed_tables.is_empty() {
-            Some(&self.allowed_tables[self.selected_ind])
-        } else {
-            None
-        }
-    }
-
-    fn scroll_up_by(&mut self, amount: u16) {
-        if let Some(x) = self.state.selected() {
-            if x == 0 {
-                self.state.select_last();
-                return;
-            }
-        }
-        self.state.scroll_up_by(amount);
-    }
-
-    fn scroll_down_by(&mut self, amount: u16) {
-        if let Some(x) = self.state.selected() {
-            if x == self.allowed_tables.len() - 1 {
-                self.state.select_first();
-                return;
-            }
-        }
-        self.state.scroll_down_by(amount);
-    }
-}
-
-impl Component for TableSelection {
-    fn handle_key_event(&mut self, key: KeyEvent) -> Result<Vec<Action>, Box<dyn Error>> {
-        let mut quit: bool = false;
-        match key.code {
-            KeyCode::Esc => quit = true, // terminate on encountering Esc
-            KeyCode::Enter => {
-                if let Some(x) = self.state.selected() {
-                    // TODO: change the table and actions to match the ones allowed by the selected item
-                    self.selected_ind = x;
-                    // notify the app to change the selected table and revert
-                    // to the main screen if on the add screen
-                    return Ok(vec![Action::ChangeSelectedTable, Action::RevertToMain]);
-                }
-            }
-            KeyCode::Up => self.scroll_up_by(1),
-            KeyCode::Down => self.scroll_down_by(1),
-            _ => {}
-        }
-        if quit {
-            Ok(vec![Action::Quit])
-        } else {
-            Ok(vec![Action::Noop])
-        }
-    }
-
-    fn render(&mut self, f: &mut Frame, rect: Rect, block: Block) {
-        let highlight_style = Style::new().reversed();
-        let tables = List::from_iter(self.allowed_tables.iter().enumerate().map(|(ind, tab)| {
-            let mut item = ListItem::new(Cow::from(tab.display_name));
-            if ind == self.selected_ind {
-                item = item.bg(DEFAULT_APP_COLORS.selection_one_bg);
-            }
-            item
-        }))
-        .fg(DEFAULT_APP_COLORS.main_fg)
-        .bg(DEFAULT_APP_COLORS.main_bg)
-        .highlight_style(highlight_style)
-        .direction(ratatui::widgets::ListDirection::TopToBottom)
-        .block(block);
-        f.render_stateful_widget(tables, rect, &mut self.state);
-    }
-}
+use std::collections::HashMap;
+
+use command_list::EditCommand;
+use ratatui::{
+    text::{Line, Text},
+    widgets::{List, ListItem, ListState},
+};
+
+use crate::{
+    autofill::{AutoFillFactory, AutoFillFn},
+    config::editable_tables,
+    connection::Connection,
+    value::DisplayFormatFn,
+};
+
+use super::*;
+
+#[derive(Clone)]
+pub struct TableMetadata {
+    pub(crate) commands: Vec<EditCommand>,
+    pub(crate) display_name: &'static str,
+    pub(crate) table_name: &'static str,
+    pub(crate) autofill_funcs: HashMap<&'static str, AutoFillFn>,
+    /// Creates autofill functions once a connection is available, such as
+    /// those suggesting values from the database, which take precedence
+    /// over the functions within `autofill_funcs`
+    pub(crate) autofill_factory: Option<AutoFillFactory>,
+    /// Formats the values of each column when they are displayed in the table
+    pub(crate) display_format_funcs: HashMap<&'static str, DisplayFormatFn>,
+    /// Tables with more columns than this offer a column-scoped search,
+    /// which is never offered if None
+    pub(crate) min_cols_for_search: Option<usize>,
+    /// Whether the table can't be changed, such as a view, which only allows
+    /// the edit commands which don't modify it
+    pub(crate) is_read_only: bool,
+    /// Column holding the display order of the rows for reordering, which
+    /// is detected from the table's columns if None
+    pub(crate) order_column: Option<String>,
+}
+
+impl TableMetadata {
+    /// Creates the metadata for a view, which is shown under its own name
+    /// and can only be filtered and searched
+    pub fn read_only_view(name: &'static str) -> Self {
+        Self {
+            commands: vec![EditCommand::Filter],
+            display_name: name,
+            table_name: name,
+            autofill_funcs: HashMap::with_capacity(0),
+            autofill_factory: None,
+            display_format_funcs: HashMap::with_capacity(0),
+            min_cols_for_search: Some(0),
+            is_read_only: true,
+            order_column: None,
+        }
+    }
+
+    /// Creates the metadata for a table of the database attached under the
+    /// passed schema name, which is shown under the table's own name and
+    /// can only be filtered and searched
+    pub fn attached_table(schema: &str, table: &'static str) -> Self {
+        Self {
+            display_name: table,
+            ..Self::read_only_view(format!("{}.{}", schema, table).leak())
+        }
+    }
+
+    /// Creates the metadata for a table which isn't configured, inspecting it
+    /// through the passed connection to choose which edit commands it offers.
+    /// Rows can only be modified or deleted if they can be told apart by a
+    /// primary key or rowid, and reordering needs an order column. The name
+    /// is leaked as the metadata lasts for the rest of the app's lifetime
+    pub fn from_connection(connection: &Connection, table_name: &str) -> Result<Self, AppError> {
+        let column_info = connection.get_column_info(table_name)?;
+        let identifiable = column_info.iter().any(|column| column.is_primary_key)
+            || connection.table_has_rowid(table_name)?;
+        let order_column = connection.detect_order_column(table_name)?;
+
+        let mut commands = Vec::new();
+        if identifiable {
+            commands.push(EditCommand::Modify);
+        }
+        commands.push(EditCommand::Add);
+        commands.push(EditCommand::Import);
+        if identifiable {
+            commands.push(EditCommand::Delete);
+        }
+        if order_column.is_some() {
+            commands.push(EditCommand::Reorder);
+        }
+
+        let name: &'static str = table_name.to_string().leak();
+        Ok(Self {
+            commands,
+            display_name: name,
+            table_name: name,
+            autofill_funcs: HashMap::with_capacity(0),
+            autofill_factory: None,
+            display_format_funcs: HashMap::with_capacity(0),
+            min_cols_for_search: Some(5),
+            is_read_only: false,
+            order_column,
+        })
+    }
+
+    /// Describes the table by both of its names, as the displayed name may
+    /// not match the one it is queried by
+    pub fn debug_string(&self) -> String {
+        format!("{}({})", self.display_name, self.table_name)
+    }
+
+    /// Returns the schema name of the attached database the table belongs
+    /// to, or None if it belongs to the main database
+    pub fn attached_schema(&self) -> Option<&'static str> {
+        self.table_name.split_once('.').map(|(schema, _)| schema)
+    }
+
+    /// Returns the autofill functions for each column of the table, calling
+    /// the autofill factory with the passed connection if there is one
+    pub fn resolve_autofill_funcs(
+        &self,
+        connection: &Connection,
+    ) -> HashMap<&'static str, AutoFillFn> {
+        let mut funcs = self.autofill_funcs.clone();
+        if let Some(factory) = self.autofill_factory {
+            funcs.extend(factory(connection));
+        }
+        funcs
+    }
+
+    /// Returns the column holding the display order of the table's rows,
+    /// detecting it through the passed connection if it isn't configured
+    pub fn resolve_order_column(
+        &self,
+        connection: &Connection,
+    ) -> Result<Option<String>, AppError> {
+        match &self.order_column {
+            Some(column) => Ok(Some(column.clone())),
+            None => connection.detect_order_column(self.table_name),
+        }
+    }
+
+    /// Returns the edit commands offered for the table, which includes
+    /// [`EditCommand::Search`] if the table has enough columns
+    pub fn available_commands(&self, num_columns: usize) -> Vec<EditCommand> {
+        let mut commands = self.commands.clone();
+        if self
+            .min_cols_for_search
+            .is_some_and(|min_cols| num_columns > min_cols)
+        {
+            commands.push(EditCommand::Search);
+        }
+        if self.is_read_only {
+            commands.retain(|command| matches!(command, EditCommand::Filter | EditCommand::Search));
+        }
+        commands
+    }
+}
+
+impl std::fmt::Debug for TableMetadata {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // autofill functions can't be printed, so only show their columns
+        f.debug_struct("TableMetadata")
+            .field("commands", &self.commands)
+            .field("display_name", &self.display_name)
+            .field("table_name", &self.table_name)
+            .field("autofill_funcs", &self.autofill_funcs.keys())
+            .field("autofill_factory", &self.autofill_factory.is_some())
+            .field("display_format_funcs", &self.display_format_funcs.keys())
+            .field("min_cols_for_search", &self.min_cols_for_search)
+            .field("is_read_only", &self.is_read_only)
+            .field("order_column", &self.order_column)
+            .finish()
+    }
+}
+
+impl std::fmt::Display for TableMetadata {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.display_name)
+    }
+}
+
+pub struct TableSelection {
+    allowed_tables: Vec<TableMetadata>,
+    area: Rect,
+    selected_ind: usize,
+    state: ListState,
+}
+
+impl Default for TableSelection {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TableSelection {
+    pub fn new() -> Self {
+        Self {
+            allowed_tables: editable_tables(),
+            area: Rect::default(),
+            selected_ind: 0,
+            state: ListState::default().with_selected(Some(0)),
+        }
+    }
+
+    /// Appends a read-only entry for each of the passed views. The names are
+    /// leaked as the entries last for the rest of the app's lifetime
+    pub fn add_views(&mut self, views: Vec<String>) {
+        self.allowed_tables.extend(
+            views
+                .into_iter()
+                .map(|view| TableMetadata::read_only_view(view.leak())),
+        );
+    }
+
+    /// Appends an entry for each of the passed tables from the database
+    /// attached under the passed schema name, which are grouped beneath it
+    /// when listed. The names are leaked as with [`TableSelection::add_views`]
+    pub fn add_attached_schema(&mut self, schema: &str, tables: Vec<String>) {
+        self.allowed_tables.extend(
+            tables
+                .into_iter()
+                .map(|table| TableMetadata::attached_table(schema, table.leak())),
+        );
+    }
+
+    /// Appends an entry for the passed table, such as one reached through
+    /// a foreign key which wasn't configured to be listed
+    pub fn add_table(&mut self, table: TableMetadata) {
+        self.allowed_tables.push(table);
+    }
+
+    pub fn selected(&self) -> Option<&TableMetadata> {
+        if !self.allowed_tables.is_empty() {
+            Some(&self.allowed_tables[self.selected_ind])
+        } else {
+            None
+        }
+    }
+
+    /// Selects the table with the passed name, returning false if there's
+    /// no such table
+    pub fn select_by_name(&mut self, name: &str) -> bool {
+        let Some(index) = self
+            .allowed_tables
+            .iter()
+            .position(|table| table.table_name == name)
+        else {
+            return false;
+        };
+        self.selected_ind = index;
+        self.state.select(Some(index));
+        true
+    }
+
+    fn scroll_up_by(&mut self, amount: u16) {
+        if let Some(x) = self.state.selected()
+            && x == 0
+        {
+            self.state.select_last();
+            return;
+        }
+        self.state.scroll_up_by(amount);
+    }
+
+    fn scroll_down_by(&mut self, amount: u16) {
+        if let Some(x) = self.state.selected()
+            && x == self.allowed_tables.len() - 1
+        {
+            self.state.select_first();
+            return;
+        }
+        self.state.scroll_down_by(amount);
+    }
+}
+
+impl Component for TableSelection {
+    fn name(&self) -> &'static str {
+        "Tables"
+    }
+
+    fn commands(&self) -> Vec<(String, Action)> {
+        self.allowed_tables
+            .iter()
+            .map(|table| {
+                (
+                    format!("Switch to {}", table.display_name),
+                    Action::OpenTable(table.table_name.to_string()),
+                )
+            })
+            .collect()
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent) -> Result<Vec<Action>, AppError> {
+        let mut quit: bool = false;
+        match key.code {
+            KeyCode::Esc => quit = true, // terminate on encountering Esc
+            KeyCode::Enter => {
+                if let Some(x) = self.state.selected() {
+                    // TODO: change the table and actions to match the ones allowed by the selected item
+                    self.selected_ind = x;
+                    // notify the app to change the selected table and revert
+                    // to the main screen if on the add screen
+                    return Ok(vec![Action::ChangeSelectedTable, Action::RevertToMain]);
+                }
+            }
+            KeyCode::Char(c) if c.is_ascii_digit() => {
+                // number keys jump directly to the first 9 tables
+                let index = (c.to_digit(10).unwrap() as usize).wrapping_sub(1);
+                if index < self.allowed_tables.len() {
+                    self.selected_ind = index;
+                    self.state.select(Some(index));
+                    return Ok(vec![Action::ChangeSelectedTable, Action::RevertToMain]);
+                }
+            }
+            KeyCode::Up => self.scroll_up_by(1),
+            KeyCode::Down => self.scroll_down_by(1),
+            _ => {}
+        }
+        if quit {
+            Ok(vec![Action::Quit])
+        } else {
+            Ok(vec![Action::Noop])
+        }
+    }
+
+    fn render(&mut self, f: &mut Frame, rect: Rect, block: Block) {
+        self.set_area(rect);
+        let highlight_style = Style::new().reversed();
+        let tables = List::from_iter(self.allowed_tables.iter().enumerate().map(|(ind, tab)| {
+            // prefix the tables which can be jumped to by number key
+            let name = if ind < 9 {
+                format!("{}. {}", ind + 1, tab.display_name)
+            } else {
+                tab.display_name.to_string()
+            };
+            let schema = tab.attached_schema();
+            let mut item = if let Some(schema) = schema {
+                // draw the tables of attached databases as branches beneath
+                // their schema, which heads the first of them
+                let schema_of = |ind: usize| self.allowed_tables.get(ind)?.attached_schema();
+                let branch = if schema_of(ind + 1) == Some(schema) {
+                    "├"
+                } else {
+                    "└"
+                };
+                let line = Line::from(format!(" {} {}", branch, name));
+                if ind == 0 || schema_of(ind - 1) != Some(schema) {
+                    ListItem::new(Text::from(vec![Line::from(format!("▾ {}", schema)), line]))
+                } else {
+                    ListItem::new(line)
+                }
+            } else {
+                ListItem::new(name)
+            };
+            if ind == self.selected_ind {
+                item = item.bg(DEFAULT_APP_COLORS.selection_one_bg);
+            }
+            item
+        }))
+        .fg(DEFAULT_APP_COLORS.main_fg)
+        .bg(DEFAULT_APP_COLORS.main_bg)
+        .highlight_style(highlight_style)
+        .direction(ratatui::widgets::ListDirection::TopToBottom)
+        .block(block);
+        f.render_stateful_widget(tables, rect, &mut self.state);
+    }
+}
+
+impl AreaComponent for TableSelection {
+    fn area(&self) -> Rect {
+        self.area
+    }
+
+    fn set_area(&mut self, rect: Rect) {
+        self.area = rect;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn commands_open_each_table_by_name() {
+        let mut tables = TableSelection::new();
+        tables.add_views(vec!["recent_orders".to_string()]);
+        let commands = tables.commands();
+        assert_eq!(commands.len(), tables.allowed_tables.len());
+        assert_eq!(
+            commands.last(),
+            Some(&(
+                "Switch to recent_orders".to_string(),
+                Action::OpenTable("recent_orders".to_string())
+            ))
+        );
+    }
+}
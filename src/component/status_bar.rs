@@ -0,0 +1,110 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Layout},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+
+use super::*;
+
+/// Single line component summarising the state of the app: the table and
+/// edit command in use, where the highlit cell is within the shown table,
+/// and which component is focused
+#[derive(Default)]
+pub struct StatusBar {
+    table_name: Option<String>,
+    mode: Option<String>,
+    /// (y, x) coordinate of the highlit cell, if the table has any cells
+    cell: Option<(usize, usize)>,
+    /// Number of rows and columns within the shown table
+    dimensions: (usize, usize),
+    focused: &'static str,
+    /// Whether changes are being held in a batch until committed
+    batch_mode: bool,
+    /// Digits of the row number being typed to jump to, shown in place of
+    /// the position while it's typed
+    jump_buffer: Option<String>,
+}
+
+impl StatusBar {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replaces the displayed state with the passed one
+    pub fn update(
+        &mut self,
+        table_name: Option<&str>,
+        mode: Option<String>,
+        cell: Option<(usize, usize)>,
+        dimensions: (usize, usize),
+        focused: &'static str,
+        batch_mode: bool,
+    ) {
+        self.table_name = table_name.map(str::to_owned);
+        self.mode = mode;
+        self.cell = cell;
+        self.dimensions = dimensions;
+        self.focused = focused;
+        self.batch_mode = batch_mode;
+    }
+
+    /// Shows the row number being typed to jump to in place of the
+    /// position, or the position again if None
+    pub fn set_jump_buffer(&mut self, jump_buffer: Option<&str>) {
+        self.jump_buffer = jump_buffer.map(str::to_owned);
+    }
+}
+
+impl Component for StatusBar {
+    fn name(&self) -> &'static str {
+        "Status"
+    }
+
+    fn render(&mut self, f: &mut Frame, rect: Rect, block: Block) {
+        f.render_widget(block.bg(DEFAULT_APP_COLORS.header_bg), rect);
+        let [left_rect, center_rect, right_rect] = Layout::horizontal([
+            Constraint::Fill(1),
+            Constraint::Fill(1),
+            Constraint::Fill(1),
+        ])
+        .areas(rect);
+
+        let table = match (&self.table_name, &self.mode) {
+            (Some(table), Some(mode)) => format!(" {} ({})", table, mode),
+            (Some(table), None) => format!(" {}", table),
+            (None, _) => String::new(),
+        };
+        // rows and columns are counted from 1 for display
+        let (num_rows, num_cols) = self.dimensions;
+        let position = match (&self.jump_buffer, self.cell) {
+            (Some(buffer), _) => format!("Go to: {}_", buffer),
+            (None, Some((y, x))) => {
+                format!("row {} / {}, col {} / {}", y + 1, num_rows, x + 1, num_cols)
+            }
+            (None, None) => format!("row - / {}, col - / {}", num_rows, num_cols),
+        };
+        let mut focused = vec![Span::raw(format!("[{}] ", self.focused.to_uppercase()))];
+        if self.batch_mode {
+            focused.insert(
+                0,
+                Span::styled(
+                    "[BATCH] ",
+                    Style::new().fg(DEFAULT_APP_COLORS.warning_fg).bold(),
+                ),
+            );
+        }
+
+        for (text, alignment, area) in [
+            (Line::from(table), Alignment::Left, left_rect),
+            (Line::from(position), Alignment::Center, center_rect),
+            (Line::from(focused), Alignment::Right, right_rect),
+        ] {
+            f.render_widget(
+                Paragraph::new(text)
+                    .alignment(alignment)
+                    .fg(DEFAULT_APP_COLORS.header_fg),
+                area,
+            );
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use std::collections::HashMap;
+
+use ratatui::layout::{Constraint, Direction, Layout};
+
+use super::{table_display::TableDisplay, *};
+use crate::connection::Table;
+
+/// Side by side comparison of a table before and after a change, where
+/// changed cells, added rows, and removed rows are each highlighted
+pub struct TableDiff {
+    before: TableDisplay,
+    after: TableDisplay,
+}
+
+impl TableDiff {
+    /// Compares the rows and cells of the tables by their positions, as the
+    /// rows of a table aren't guaranteed to have any other identifier
+    pub fn new(before: Table, after: Table) -> Self {
+        let mut changed_cells = HashMap::new();
+        for (y, (before_row, after_row)) in std::iter::zip(&before.rows, &after.rows).enumerate() {
+            for (x, (before_val, after_val)) in std::iter::zip(before_row, after_row).enumerate() {
+                if before_val != after_val {
+                    changed_cells.insert((y, x), DEFAULT_APP_COLORS.diff_changed_bg);
+                }
+            }
+        }
+        // rows past the end of the other table were either added or removed
+        let removed_rows = (after.rows.len()..before.rows.len())
+            .map(|y| (y, DEFAULT_APP_COLORS.diff_removed_bg))
+            .collect();
+        let added_rows = (before.rows.len()..after.rows.len())
+            .map(|y| (y, DEFAULT_APP_COLORS.diff_added_bg))
+            .collect();
+
+        let mut before = TableDisplay::from_table(before, false, 0);
+        before.set_cell_backgrounds(changed_cells.clone());
+        before.set_row_backgrounds(removed_rows);
+        let mut after = TableDisplay::from_table(after, false, 0);
+        after.set_cell_backgrounds(changed_cells);
+        after.set_row_backgrounds(added_rows);
+        Self { before, after }
+    }
+}
+
+impl Component for TableDiff {
+    fn name(&self) -> &'static str {
+        "Diff"
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent) -> Result<Vec<Action>, AppError> {
+        match key.code {
+            // move through both tables together so the same cells are shown
+            KeyCode::Up | KeyCode::Down | KeyCode::Left | KeyCode::Right => {
+                self.before.handle_key_event(key)?;
+                self.after.handle_key_event(key)
+            }
+            _ => Ok(vec![Action::Noop]),
+        }
+    }
+
+    fn render(&mut self, f: &mut Frame, rect: Rect, block: Block) {
+        let inner = block.inner(rect);
+        f.render_widget(block.bg(DEFAULT_APP_COLORS.main_bg), rect);
+        let [before_rect, after_rect] = *Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)])
+            .split(inner)
+        else {
+            panic!("Not enough size to create the necessary rects");
+        };
+        self.before.render(
+            f,
+            before_rect,
+            DEFAULT_APP_COLORS.default_block().title(" Before "),
+        );
+        self.after.render(
+            f,
+            after_rect,
+            DEFAULT_APP_COLORS.default_block().title(" After "),
+        );
+    }
+}
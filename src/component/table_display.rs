@@ -1,405 +1,1268 @@
-use std::{borrow::Cow, error::Error};
-
-use ratatui::{
-    text::Text,
-    widgets::{
-        Cell, Row, Scrollbar, ScrollbarState, Table as TuiTable, TableState as TuiTableState,
-    },
-};
-
-use super::*;
-
-use crate::{connection::Table, value::Value};
-
-const ROW_HEIGHT: usize = 2;
-
-/// Component which wraps over a [`crate::connection::Table`] and a ratatui
-/// Table widget in order to allow for selecting multiple items within a
-/// table and display them properly
-pub struct TableDisplay {
-    pub(crate) table: Table,
-    pub(crate) uses_rows: bool,
-    state: MultiTableState,
-    table_state: TuiTableState,
-    scroll_state: ScrollbarState,
-}
-
-impl TableDisplay {
-    pub fn from_table(
-        table: Table,
-        uses_rows: bool,
-        max_selections: usize,
-    ) -> Result<Self, Box<dyn Error>> {
-        let num_items = table.rows.len();
-        Ok(Self {
-            table,
-            uses_rows,
-            state: MultiTableState::new(max_selections),
-            table_state: TuiTableState::new().with_selected_cell(Some((0, 0))),
-            scroll_state: ScrollbarState::new((num_items.saturating_sub(1)) * ROW_HEIGHT),
-        })
-    }
-
-    pub fn highlit_cell_value(&self) -> Option<String> {
-        self.table_state.selected_cell().map(|(y, x)| {
-            // ensure clamping of values as the state doesn't update to proper
-            // selected row until rendering occurs, which is too late
-            let y = y.clamp(0, self.table.rows.len() - 1);
-            let x = x.clamp(0, self.table.columns.len() - 1);
-            self.table.rows[y][x].to_string()
-        })
-    }
-
-    pub fn highlit_col_name(&self) -> Option<String> {
-        self.table_state.selected_column().map(|x| {
-            // clamp value
-            let x = x.clamp(0, self.table.columns.len() - 1);
-            self.table.columns[x].clone()
-        })
-    }
-
-    /// Returns the MultiTable's current set of selections
-    pub fn selections(&self) -> &[MultiTableSelection] {
-        self.state.selections.as_slice()
-    }
-
-    /// Simple wrapped getter for the underlying table's columns.
-    /// Shorthand for calling TableDisplay.table.columns
-    pub fn columns(&self) -> &[String] {
-        self.table.columns.as_slice()
-    }
-
-    /// Simple wrapped getter for the underlying table's rows
-    /// Shorthand for calling TableDisplay.table.rows
-    pub fn rows(&self) -> &[Vec<Value>] {
-        self.table.rows.as_slice()
-    }
-
-    /// Clears all selections, leaving allocated capacity the same
-    pub fn reset_selections(&mut self) {
-        self.state.selections.clear();
-    }
-
-    /// Updates the number of selections to hold the new max number.
-    /// Truncates the list, removing the more recent selections, if new_max is
-    /// less than the current max selections.
-    pub fn set_max_selections(&mut self, new_max: usize) {
-        self.state.selections.truncate(new_max);
-        self.state.max_selections = new_max;
-    }
-
-    /// Updates the selection type to be the new type.
-    /// Removes selections of the old type if it is changed.
-    pub fn set_selection_type(&mut self, use_rows: bool) {
-        if use_rows != self.uses_rows {
-            // since we change the type, clear all selections
-            self.reset_selections();
-        }
-        self.uses_rows = use_rows;
-    }
-
-    /// Simple wrapper over the MultiTableState method of the same name,
-    /// used for setting selections separate from user action
-    pub fn select(&mut self, selection: MultiTableSelection) {
-        self.state.select(selection);
-    }
-
-    /// Moves the selected cell to the left by amount.
-    /// Wraps selection to the last column if we are at column 0.
-    /// Light wrapper of TableState's same-named function.
-    fn scroll_left_by(&mut self, amount: u16) {
-        // if self.uses_rows {
-        //     return;
-        // }
-        if let Some((_, x)) = self.table_state.selected_cell() {
-            if x == 0 {
-                self.table_state.select_last_column();
-                return;
-            }
-        }
-        self.table_state.scroll_left_by(amount);
-    }
-
-    /// Moves the selected cell to the right by amount.
-    /// Wraps selection to the first column if we are at the last one.
-    /// Light wrapper of TableState's same-named function.
-    fn scroll_right_by(&mut self, amount: u16) {
-        // if self.uses_rows {
-        //     return;
-        // }
-        if let Some((_, x)) = self.table_state.selected_cell() {
-            if x == self.table.columns.len() - 1 {
-                self.table_state.select_first_column();
-                return;
-            }
-        }
-        self.table_state.scroll_right_by(amount);
-    }
-
-    /// Moves the selected row/cell up by amount.
-    /// Wraps selection to the last row if we are at row 0.
-    /// Light wrapper of TableState's same-named function.
-    fn scroll_up_by(&mut self, amount: u16) {
-        if let Some(y) = self.table_state.selected() {
-            if y == 0 {
-                self.table_state.select_last();
-                self.scroll_state.last();
-                return;
-            }
-        }
-        self.table_state.scroll_up_by(amount);
-        self.scroll_state = self
-            .scroll_state
-            .position(self.table_state.selected().unwrap() * ROW_HEIGHT);
-    }
-
-    /// Moves the selected row/cell down by amount.
-    /// Wraps selection to the first row if we are at the last one.
-    /// Light wrapper of TableState's same-named function.
-    fn scroll_down_by(&mut self, amount: u16) {
-        if let Some(y) = self.table_state.selected() {
-            if y == self.table.rows.len() - 1 {
-                self.table_state.select_first();
-                self.scroll_state.first();
-                return;
-            }
-        }
-        self.table_state.scroll_down_by(amount);
-        self.scroll_state = self
-            .scroll_state
-            .position(self.table_state.selected().unwrap() * ROW_HEIGHT);
-    }
-}
-
-impl Component for TableDisplay {
-    fn handle_key_event(&mut self, key: KeyEvent) -> Result<Vec<Action>, Box<dyn Error>> {
-        // ignore key releases
-        if key.kind == KeyEventKind::Release {
-            return Ok(vec![Action::Noop]);
-        }
-
-        match key.code {
-            KeyCode::Esc => Ok(vec![Action::Quit]), // terminate on encountering Esc
-            KeyCode::Enter => {
-                let selection_opt: Option<MultiTableSelection> = if self.uses_rows {
-                    self.table_state.selected().map(|row| row.into())
-                } else {
-                    self.table_state.selected_cell().map(|cell| cell.into())
-                };
-                if let Some(selection) = selection_opt {
-                    // if selection was added, return SelectionChanged, else Noop
-                    if self.state.select(selection) {
-                        Ok(vec![Action::SelectionChanged])
-                    } else {
-                        Ok(vec![Action::Noop])
-                    }
-                } else {
-                    Ok(vec![Action::Noop])
-                }
-            }
-            KeyCode::Left => {
-                self.scroll_left_by(1);
-                Ok(vec![Action::HighlightChanged])
-            }
-            KeyCode::Right => {
-                self.scroll_right_by(1);
-                Ok(vec![Action::HighlightChanged])
-            }
-            KeyCode::Up => {
-                self.scroll_up_by(1);
-                Ok(vec![Action::HighlightChanged])
-            }
-            KeyCode::Down => {
-                self.scroll_down_by(1);
-                Ok(vec![Action::HighlightChanged])
-            }
-            _ => Ok(vec![Action::Noop]),
-        }
-    }
-
-    fn render(&mut self, f: &mut Frame, rect: Rect, block: Block) {
-        // map the column names into cells for the sake of the header row of the table
-        let columns = Row::from_iter(
-            self.table
-                .columns
-                .iter()
-                .map(|column| Text::from(Cow::from(column)).centered()),
-        );
-
-        // define the style for each row
-        let row_style = Style::default()
-            .fg(DEFAULT_APP_COLORS.main_fg)
-            .bg(DEFAULT_APP_COLORS.main_bg);
-
-        let selection_colors = DEFAULT_APP_COLORS.selection_colors();
-        // map the rows' cells into Ratatui rows for the sake of the display
-        let rows: Vec<Row> = self
-            .table
-            .rows
-            .iter()
-            .enumerate()
-            .map(|(y, row)| {
-                // determine the color to use for the current selection
-                let selected_style_base = Style::default().bold();
-                // determine if this row needs to be selected as it overrides cell styles
-                let row_selected_ind = if self.uses_rows {
-                    self.state.index_of(MultiTableSelection::Row(y))
-                } else {
-                    None
-                };
-                // update highlighting depending on selection style and selected items
-                Row::new(row.iter().enumerate().map(|(x, cell)| {
-                    let mut cur_cell_style = if row_selected_ind.is_none() {
-                        // current row is not selected, so column color is more complex
-                        if self.uses_rows
-                            && self.table_state.selected_cell().is_some_and(
-                                |(highlit_row, highlit_col)| y < highlit_row && highlit_col == x,
-                            )
-                        {
-                            // make highlit column have a special bg color
-                            Style::new().bg(DEFAULT_APP_COLORS.highlit_bg)
-                        } else if x % 2 == 0 {
-                            // alternate color as column is not highlit
-                            Style::new().bg(DEFAULT_APP_COLORS.alt_bg)
-                        } else {
-                            // just use no style as the row style acts as a default
-                            Style::new()
-                        }
-                    } else {
-                        // just use no style as the row style acts as a default
-                        Style::new()
-                    };
-                    if !self.uses_rows {
-                        // cell selection is used, so change style if this cell is selected
-                        if let Some(i) = self.state.index_of(MultiTableSelection::Cell((y, x))) {
-                            cur_cell_style = selected_style_base
-                                .bg(selection_colors[i % selection_colors.len()]);
-                        }
-                    }
-                    Cell::from(cell.to_string()).style(cur_cell_style)
-                }))
-                .style(if let Some(i) = row_selected_ind {
-                    selected_style_base.bg(selection_colors[i % selection_colors.len()])
-                } else {
-                    row_style
-                })
-                .height(ROW_HEIGHT as u16)
-            })
-            .collect();
-        // set up the styling of the table, its header, and its selections
-        let header_style = Style::default()
-            .fg(DEFAULT_APP_COLORS.header_fg)
-            .bg(DEFAULT_APP_COLORS.header_bg);
-        let highlight_style = Style::new().reversed();
-
-        let mut table = TuiTable::default()
-            .block(block)
-            .bg(DEFAULT_APP_COLORS.main_bg)
-            .highlight_symbol(
-                // each item in the vec is a line, so 2 lines in accordance with ROW_HEIGHT
-                Text::from(vec![" ╲ ".into(), " ╱ ".into()])
-                    .fg(DEFAULT_APP_COLORS.main_fg)
-                    .bold(),
-            );
-
-        if self.uses_rows {
-            table = table.row_highlight_style(highlight_style);
-        } else {
-            table = table.cell_highlight_style(highlight_style);
-        }
-        // make it have the desired columns and rows
-        table = table
-            .header(columns.style(header_style).height(1))
-            .rows(rows);
-        f.render_stateful_widget(table, rect, &mut self.table_state);
-
-        // render the scrollbar for the table
-        let mut scrollbar_rect = rect;
-        scrollbar_rect.y += 1;
-        scrollbar_rect.height = scrollbar_rect.height.saturating_sub(1);
-        f.render_stateful_widget(
-            Scrollbar::default()
-                .orientation(ratatui::widgets::ScrollbarOrientation::VerticalRight)
-                .begin_symbol(None)
-                .end_symbol(None)
-                .style(DEFAULT_APP_COLORS.main_fg),
-            scrollbar_rect,
-            &mut self.scroll_state,
-        );
-    }
-}
-
-/// A collection of multiple selections, up to the passed amount,
-/// defaulting to 1 max selection
-struct MultiTableState {
-    pub(crate) max_selections: usize,
-    pub(crate) selections: Vec<MultiTableSelection>,
-}
-
-/// Enum storing selections depending on whether the MultiTable selects rows
-/// or cells.
-///
-/// When storing cells, the values are stored in (y, x) order as it is in Ratatui
-#[derive(Debug, Eq, PartialEq, Clone, Copy)]
-pub enum MultiTableSelection {
-    /// Tuple storing a coordinate in (y, x) order
-    Cell((usize, usize)),
-    /// Offset of the row/the y value of any cell in a row
-    Row(usize),
-}
-
-impl From<(usize, usize)> for MultiTableSelection {
-    fn from(value: (usize, usize)) -> Self {
-        MultiTableSelection::Cell(value)
-    }
-}
-
-impl From<usize> for MultiTableSelection {
-    fn from(value: usize) -> Self {
-        MultiTableSelection::Row(value)
-    }
-}
-
-impl Default for MultiTableState {
-    fn default() -> Self {
-        Self {
-            max_selections: 1,
-            selections: Vec::with_capacity(1),
-        }
-    }
-}
-
-impl MultiTableState {
-    fn new(max_selections: usize) -> Self {
-        Self {
-            max_selections,
-            selections: Vec::with_capacity(max_selections),
-        }
-    }
-
-    /// Returns the index of the equivalent selection within the list of
-    /// selections if present, else None
-    fn index_of(&self, selection: MultiTableSelection) -> Option<usize> {
-        self.selections.iter().position(|item| *item == selection)
-    }
-
-    /// Adds the passed selection to the Vec of selections,
-    /// or removes it if it is already present
-    ///
-    /// Pushes new selections to the end of the list such that
-    /// older selections will be at the front of the list.
-    ///
-    /// Returns true if the selection was added, false if not
-    fn select(&mut self, selection: MultiTableSelection) -> bool {
-        // search for item in reverse under the naive, but somewhat true
-        // assumption that the selections which get removed most are those
-        // which have been more recently added
-        if let Some(ind) = self.selections.iter().rposition(|item| *item == selection) {
-            self.selections.remove(ind);
-        } else if self.selections.len() < self.max_selections {
-            self.selections.push(selection);
-            return true;
-        }
-        false
-    }
-}
+use std::{
+    collections::{HashMap, HashSet},
+    time::{Duration, Instant},
+};
+
+use ratatui::{
+    crossterm::event::KeyModifiers,
+    layout::{Constraint, Flex, Layout},
+    style::Color,
+    text::{Line, Span, Text},
+    widgets::{
+        Cell, Row, Scrollbar, ScrollbarState, Table as TuiTable, TableState as TuiTableState,
+    },
+};
+
+use super::*;
+
+use crate::{
+    connection::Table,
+    value::{DisplayFormatFn, Value},
+    wrap::{truncate_with_ellipsis, wrap, wrap_with_max_lines},
+};
+
+/// Height of each row before the table has been rendered
+const ROW_HEIGHT: usize = 2;
+/// The maximum number of lines a single row may take up
+const MAX_ROW_HEIGHT: usize = 8;
+/// Display width of the symbol drawn next to the highlit row
+const HIGHLIGHT_SYMBOL_WIDTH: u16 = 3;
+/// Narrowest and widest a column can be resized to
+const MIN_COLUMN_WIDTH: u16 = 3;
+const MAX_COLUMN_WIDTH: u16 = 255;
+/// How soon a second Enter must follow the first to confirm a selection
+const DOUBLE_ENTER_WINDOW: Duration = Duration::from_millis(500);
+/// How long each flash of a pending selection lasts
+const PENDING_PULSE_DURATION: Duration = Duration::from_millis(125);
+
+/// Direction the table is sorted by a column in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
+impl SortDirection {
+    /// Returns the direction which follows this one, where None means
+    /// the column is no longer sorted by
+    pub fn cycle(self) -> Option<SortDirection> {
+        match self {
+            Self::Asc => Some(Self::Desc),
+            Self::Desc => None,
+        }
+    }
+
+    /// Keyword used within an ORDER BY clause for the direction
+    pub fn keyword(self) -> &'static str {
+        match self {
+            Self::Asc => "ASC",
+            Self::Desc => "DESC",
+        }
+    }
+
+    /// Arrow marking the header of a column sorted in the direction
+    fn arrow(self) -> char {
+        match self {
+            Self::Asc => '▲',
+            Self::Desc => '▼',
+        }
+    }
+}
+
+/// Component which wraps over a [`crate::connection::Table`] and a ratatui
+/// Table widget in order to allow for selecting multiple items within a
+/// table and display them properly
+pub struct TableDisplay {
+    pub(crate) table: Table,
+    pub(crate) uses_rows: bool,
+    area: Rect,
+    /// Width given to each column when the table was last rendered
+    column_widths: Vec<u16>,
+    /// Width each column was resized to, or None to size it automatically
+    column_width_overrides: Vec<Option<u16>>,
+    /// Indices of the columns left out when rendering, which are skipped
+    /// over when moving the highlight
+    hidden_columns: HashSet<usize>,
+    /// Height of each row as last rendered, kept until the rows or the
+    /// widths of the columns change
+    last_rendered_heights: Vec<u16>,
+    /// Column widths the row heights were computed with, or None once the
+    /// rows have changed so the heights need computing again
+    heights_column_widths: Option<Vec<u16>>,
+    pending_jump: Option<usize>,
+    /// Digits of the row number typed after pressing g, or None if a row
+    /// number isn't being typed
+    jump_buffer: Option<String>,
+    /// Selection awaiting a second Enter, alongside when the first was pressed
+    pending_selection: Option<(MultiTableSelection, Instant)>,
+    pinned_count: usize,
+    /// Whether selections need Enter pressed twice in quick succession
+    require_double_enter: bool,
+    /// Formats the values of each column for display, leaving them unchanged
+    display_formats: HashMap<&'static str, DisplayFormatFn>,
+    /// Columns the table is sorted by, from the primary sort onwards
+    sort_columns: Vec<(usize, SortDirection)>,
+    /// Background colors of individual cells, keyed by their (y, x)
+    cell_backgrounds: HashMap<(usize, usize), Color>,
+    /// Background colors of whole rows, which cell backgrounds override
+    row_backgrounds: HashMap<usize, Color>,
+    state: MultiTableState,
+    table_state: TuiTableState,
+    scroll_state: ScrollbarState,
+}
+
+impl TableDisplay {
+    pub fn from_table(table: Table, uses_rows: bool, max_selections: usize) -> Self {
+        let num_items = table.rows.len();
+        let num_columns = table.columns.len();
+        Self {
+            table,
+            uses_rows,
+            area: Rect::default(),
+            cell_backgrounds: HashMap::new(),
+            column_widths: Vec::new(),
+            column_width_overrides: vec![None; num_columns],
+            hidden_columns: HashSet::new(),
+            display_formats: HashMap::new(),
+            last_rendered_heights: Vec::new(),
+            heights_column_widths: None,
+            pending_jump: None,
+            jump_buffer: None,
+            pending_selection: None,
+            pinned_count: 0,
+            require_double_enter: false,
+            row_backgrounds: HashMap::new(),
+            sort_columns: Vec::new(),
+            state: MultiTableState::new(max_selections),
+            table_state: TuiTableState::new().with_selected_cell(Some((0, 0))),
+            scroll_state: ScrollbarState::new((num_items.saturating_sub(1)) * ROW_HEIGHT),
+        }
+    }
+
+    /// Creates a display of a table without any columns or rows, shown
+    /// until a table is queried
+    pub fn new_empty(uses_rows: bool, max_selections: usize) -> Self {
+        Self::from_table(
+            Table::new(Vec::new(), Vec::new()),
+            uses_rows,
+            max_selections,
+        )
+    }
+
+    pub fn highlit_cell_value(&self) -> Option<String> {
+        // clamped as the state doesn't update to the proper selected row
+        // until rendering occurs, which is too late
+        self.highlit_cell()
+            .map(|(y, x)| self.table.rows[y][x].to_string())
+    }
+
+    /// Returns the (y, x) coordinate of the highlit cell, clamped to the
+    /// bounds of the table, or None if the table has no cells
+    pub fn highlit_cell(&self) -> Option<(usize, usize)> {
+        if self.table.rows.is_empty() || self.table.columns.is_empty() {
+            return None;
+        }
+        self.table_state.selected_cell().map(|(y, x)| {
+            (
+                y.min(self.table.rows.len() - 1),
+                x.min(self.table.columns.len() - 1),
+            )
+        })
+    }
+
+    /// Returns the index of the highlit column, clamped to the bounds of the
+    /// table, or None if the table has no columns
+    pub fn highlit_col_index(&self) -> Option<usize> {
+        let last_col = self.table.columns.len().checked_sub(1)?;
+        self.table_state.selected_column().map(|x| x.min(last_col))
+    }
+
+    pub fn highlit_col_name(&self) -> Option<&str> {
+        self.highlit_col_index()
+            .map(|x| self.table.columns[x].as_str())
+    }
+
+    /// Requires Enter to be pressed twice within [`DOUBLE_ENTER_WINDOW`] to
+    /// make a selection, preventing accidental selections
+    pub fn with_require_double_enter(mut self) -> Self {
+        self.require_double_enter = true;
+        self
+    }
+
+    /// Sets whether selections need Enter to be pressed twice
+    pub fn set_require_double_enter(&mut self, require_double_enter: bool) {
+        self.require_double_enter = require_double_enter;
+        self.pending_selection = None;
+    }
+
+    /// Returns true if the passed selection is awaiting a second Enter and
+    /// should currently be flashed, alternating every [`PENDING_PULSE_DURATION`]
+    fn pending_pulse_on(&self, selection: MultiTableSelection) -> bool {
+        self.pending_selection.is_some_and(|(pending, pressed_at)| {
+            let elapsed = pressed_at.elapsed();
+            pending == selection
+                && elapsed < DOUBLE_ENTER_WINDOW
+                && (elapsed.as_millis() / PENDING_PULSE_DURATION.as_millis()).is_multiple_of(2)
+        })
+    }
+
+    /// Moves the highlight to the column at the passed index, keeping the row
+    pub fn highlight_column(&mut self, x: usize) {
+        self.table_state.select_column(Some(x));
+    }
+
+    /// Sets the functions formatting the displayed values of each column,
+    /// which doesn't change the values themselves
+    pub fn set_display_formats(&mut self, display_formats: HashMap<&'static str, DisplayFormatFn>) {
+        self.display_formats = display_formats;
+        // formatted values may wrap onto a different number of lines
+        self.heights_column_widths = None;
+    }
+
+    /// Formats the passed value from the column at index x as it should be
+    /// displayed, using the column's format function if it has one
+    fn display_value(&self, x: usize, value: &Value) -> String {
+        self.display_formats
+            .get(self.table.columns[x].as_str())
+            .map_or_else(|| value.to_string(), |format| format(value))
+    }
+
+    /// Marks the headers of the columns the table is sorted by with an arrow
+    /// pointing up if the sort is ascending, or down if descending, which is
+    /// numbered by its precedence when sorting by several columns
+    pub fn set_sort_columns(&mut self, sort_columns: Vec<(usize, SortDirection)>) {
+        self.sort_columns = sort_columns;
+    }
+
+    /// Returns the width each column was resized to, or None for columns
+    /// which are sized automatically
+    pub fn column_width_overrides(&self) -> &[Option<u16>] {
+        &self.column_width_overrides
+    }
+
+    /// Resizes the columns to the passed widths, ignoring them if they
+    /// don't match the number of columns in the table
+    pub fn set_column_width_overrides(&mut self, overrides: Vec<Option<u16>>) {
+        if overrides.len() == self.table.columns.len() {
+            self.column_width_overrides = overrides;
+        }
+    }
+
+    /// Returns every column to being sized automatically
+    pub fn reset_column_widths(&mut self) {
+        self.column_width_overrides = vec![None; self.table.columns.len()];
+    }
+
+    /// Returns the indices of the columns which aren't rendered
+    pub fn hidden_columns(&self) -> &HashSet<usize> {
+        &self.hidden_columns
+    }
+
+    /// Hides the columns at the passed indices, ignoring them if any are
+    /// outside the table or they would leave no column shown. A hidden
+    /// highlit column moves the highlight to the nearest shown one
+    pub fn set_hidden_columns(&mut self, hidden: HashSet<usize>) {
+        let num_columns = self.table.columns.len();
+        if hidden.iter().any(|x| *x >= num_columns)
+            || (!hidden.is_empty() && hidden.len() >= num_columns)
+        {
+            return;
+        }
+        self.hidden_columns = hidden;
+        if let Some(x) = self.highlit_col_index()
+            && self.hidden_columns.contains(&x)
+        {
+            let visible = self.visible_columns();
+            let nearest = visible
+                .iter()
+                .find(|visible_x| **visible_x > x)
+                .or(visible.last());
+            self.table_state.select_column(nearest.copied());
+        }
+    }
+
+    /// Returns the indices of the columns which are rendered, in order
+    fn visible_columns(&self) -> Vec<usize> {
+        (0..self.table.columns.len())
+            .filter(|x| !self.hidden_columns.contains(x))
+            .collect()
+    }
+
+    /// Widens the highlit column by delta, or narrows it if negative,
+    /// starting from its current width if it hasn't been resized yet
+    pub fn resize_highlit_column(&mut self, delta: i16) {
+        let Some(x) = self.highlit_col_index() else {
+            return;
+        };
+        let width = self.column_width_overrides[x].unwrap_or(self.rendered_column_width(x));
+        let width = width
+            .saturating_add_signed(delta)
+            .clamp(MIN_COLUMN_WIDTH, MAX_COLUMN_WIDTH);
+        self.column_width_overrides[x] = Some(width);
+    }
+
+    /// Sets the background colors of individual cells, keyed by their (y, x),
+    /// which selections and the highlight still take precedence over
+    pub fn set_cell_backgrounds(&mut self, cell_backgrounds: HashMap<(usize, usize), Color>) {
+        self.cell_backgrounds = cell_backgrounds;
+    }
+
+    /// Sets the background colors of whole rows, keyed by their index
+    pub fn set_row_backgrounds(&mut self, row_backgrounds: HashMap<usize, Color>) {
+        self.row_backgrounds = row_backgrounds;
+    }
+
+    /// Returns the MultiTable's current set of selections
+    pub fn selections(&self) -> &[MultiTableSelection] {
+        self.state.selections.as_slice()
+    }
+
+    /// Returns the values of each current selection in the order they were
+    /// selected, where a selected row gives all of its values and a selected
+    /// cell gives a row containing only that cell's value
+    pub fn export_selection(&self) -> Vec<Vec<Value>> {
+        self.selections()
+            .iter()
+            .map(|selection| match *selection {
+                MultiTableSelection::Row(y) => self.table.rows[y].clone(),
+                MultiTableSelection::Cell((y, x)) => vec![self.table.rows[y][x].clone()],
+            })
+            .collect()
+    }
+
+    /// Simple wrapped getter for the underlying table's columns.
+    /// Shorthand for calling TableDisplay.table.columns
+    pub fn columns(&self) -> &[String] {
+        self.table.columns.as_slice()
+    }
+
+    /// Simple wrapped getter for the underlying table's rows
+    /// Shorthand for calling TableDisplay.table.rows
+    pub fn rows(&self) -> &[Vec<Value>] {
+        self.table.rows.as_slice()
+    }
+
+    /// Clears all selections, leaving allocated capacity the same
+    pub fn reset_selections(&mut self) {
+        self.state.deselect_all();
+    }
+
+    /// Updates the number of selections to hold the new max number.
+    /// Truncates the list, removing the more recent selections, if new_max is
+    /// less than the current max selections.
+    pub fn set_max_selections(&mut self, new_max: usize) {
+        self.state.selections.truncate(new_max);
+        self.state.max_selections = new_max;
+    }
+
+    /// Sets whether each selected cell must be within a different row,
+    /// removing the more recent selections which share a row if so
+    pub fn set_require_unique_rows(&mut self, require_unique_rows: bool) {
+        if require_unique_rows && !self.state.require_unique_rows {
+            let mut used_rows = Vec::with_capacity(self.state.selections.len());
+            self.state.selections.retain(|selection| match selection {
+                MultiTableSelection::Cell((y, _)) if used_rows.contains(y) => false,
+                MultiTableSelection::Cell((y, _)) => {
+                    used_rows.push(*y);
+                    true
+                }
+                MultiTableSelection::Row(_) => true,
+            });
+        }
+        self.state.require_unique_rows = require_unique_rows;
+    }
+
+    /// Updates the selection type to be the new type.
+    /// Removes selections of the old type if it is changed.
+    pub fn set_selection_type(&mut self, use_rows: bool) {
+        if use_rows != self.uses_rows {
+            // since we change the type, clear all selections
+            self.reset_selections();
+        }
+        self.uses_rows = use_rows;
+    }
+
+    /// Simple wrapper over the MultiTableState method of the same name,
+    /// used for setting selections separate from user action
+    pub fn select(&mut self, selection: MultiTableSelection) {
+        self.state.select(selection);
+    }
+
+    /// Moves the rows at the passed indices to the top of the table, in the
+    /// order they are passed, displaying them as pinned rows
+    pub fn pin_rows(&mut self, rows: &[usize]) {
+        let mut pinned: Vec<(usize, Vec<Value>)> = Vec::with_capacity(rows.len());
+        let mut unpinned: Vec<Vec<Value>> = Vec::with_capacity(self.table.rows.len());
+        for (y, row) in std::mem::take(&mut self.table.rows).into_iter().enumerate() {
+            if rows.contains(&y) {
+                pinned.push((y, row));
+            } else {
+                unpinned.push(row);
+            }
+        }
+        pinned.sort_by_key(|(y, _)| rows.iter().position(|row| row == y));
+        self.pinned_count = pinned.len();
+        self.table.rows = pinned
+            .into_iter()
+            .map(|(_, row)| row)
+            .chain(unpinned)
+            .collect();
+        self.heights_column_widths = None;
+    }
+
+    /// Replaces the value of the cell at (y, x), such as once it's been
+    /// changed within the database, without querying the table again
+    pub fn set_value(&mut self, y: usize, x: usize, value: Value) {
+        self.table.rows[y][x] = value;
+        self.heights_column_widths = None;
+    }
+
+    /// Computes the number of lines each row needs to display the wrapped
+    /// content of its tallest cell, where each column is as wide as given
+    /// by col_widths, clamped between 1 and [`MAX_ROW_HEIGHT`]
+    pub fn compute_row_heights(&self, col_widths: &[u16]) -> Vec<u16> {
+        self.table
+            .rows
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .enumerate()
+                    .filter(|(x, _)| !self.hidden_columns.contains(x))
+                    .map(|(x, value)| {
+                        let col_width = col_widths.get(x).copied().unwrap_or(1).max(1);
+                        wrap(&self.display_value(x, value), col_width).len()
+                    })
+                    .max()
+                    .unwrap_or(1)
+                    .clamp(1, MAX_ROW_HEIGHT) as u16
+            })
+            .collect()
+    }
+
+    /// Recomputes the height of each row if the rows or the widths of the
+    /// columns have changed since the heights were last computed, as
+    /// wrapping every cell is too slow to repeat on each frame
+    fn update_row_heights(&mut self) {
+        if self.heights_column_widths.as_ref() == Some(&self.column_widths)
+            && self.last_rendered_heights.len() == self.table.rows.len()
+        {
+            return;
+        }
+        self.last_rendered_heights = self.compute_row_heights(&self.column_widths);
+        self.heights_column_widths = Some(self.column_widths.clone());
+    }
+
+    /// Computes the width given to each column when rendered within the
+    /// passed area, as columns evenly split the space which remains after
+    /// the highlight symbol and the single space between each column
+    fn compute_column_widths(&self, area: Rect) -> Vec<u16> {
+        let [_, columns_area] = Layout::horizontal([
+            Constraint::Length(HIGHLIGHT_SYMBOL_WIDTH),
+            Constraint::Fill(0),
+        ])
+        .areas(area);
+        // resized columns keep their width, and the rest share what remains
+        let visible = self.visible_columns();
+        let constraints =
+            visible.iter().map(
+                |x| match self.column_width_overrides.get(*x).copied().flatten() {
+                    Some(width) => Constraint::Length(width),
+                    None => Constraint::Fill(1),
+                },
+            );
+        let rects = Layout::horizontal(constraints)
+            .flex(Flex::Start)
+            .spacing(1)
+            .split(columns_area);
+        // hidden columns aren't given any width
+        let mut widths = vec![0; self.table.columns.len()];
+        for (x, rect) in std::iter::zip(visible, rects.iter()) {
+            widths[x] = rect.width;
+        }
+        widths
+    }
+
+    /// Returns the width the column at the passed index was last rendered with
+    fn rendered_column_width(&self, x: usize) -> u16 {
+        self.column_widths.get(x).copied().unwrap_or(1).max(1)
+    }
+
+    /// Number of rows of ROW_HEIGHT which fit within the table as it was
+    /// last rendered, leaving out the line taken by the header, which is
+    /// how far a page moves
+    fn visible_rows(&self) -> usize {
+        (self.area().height.saturating_sub(1) as usize / ROW_HEIGHT).max(1)
+    }
+
+    /// Computes the line offset of the passed row from the top of the table,
+    /// using the heights of the rows as they were last rendered
+    fn row_offset(&self, row: usize) -> usize {
+        (0..row)
+            .map(|y| {
+                self.last_rendered_heights
+                    .get(y)
+                    .map_or(ROW_HEIGHT, |height| *height as usize)
+            })
+            .sum()
+    }
+
+    /// Highlights the row at the passed index, clamped to the last row
+    pub fn jump_to_row(&mut self, row: usize) {
+        let row = row.min(self.table.rows.len().saturating_sub(1));
+        self.table_state.select(Some(row));
+        self.scroll_state = self.scroll_state.position(self.row_offset(row));
+    }
+
+    /// Returns the digits of the row number being typed to jump to, if any
+    pub fn jump_buffer(&self) -> Option<&str> {
+        self.jump_buffer.as_deref()
+    }
+
+    /// Jumps to the typed 1-indexed row, clamping rows outside the table
+    /// to the nearest one and buzzing as they can't be jumped to exactly
+    fn jump_to_typed_row(&mut self, typed: &str) -> Vec<Action> {
+        if typed.is_empty() || self.table.rows.is_empty() {
+            return vec![Action::Noop];
+        }
+        // numbers too large to parse are past the end of any table
+        let row = typed.parse::<usize>().unwrap_or(usize::MAX);
+        self.jump_to_row(row.saturating_sub(1));
+        if row == 0 || row > self.table.rows.len() {
+            vec![Action::HighlightChanged, Action::VeryLoudWrongBuzzer]
+        } else {
+            vec![Action::HighlightChanged]
+        }
+    }
+
+    /// Moves the selected cell to the left by amount.
+    /// Wraps selection to the last column if we are at column 0.
+    /// Light wrapper of TableState's same-named function.
+    fn scroll_left_by(&mut self, amount: u16) {
+        // if self.uses_rows {
+        //     return;
+        // }
+        // hidden columns are skipped, so move between the shown ones
+        let visible = self.visible_columns();
+        let Some(x) = self.highlit_col_index() else {
+            return;
+        };
+        let pos = visible.iter().position(|visible_x| *visible_x >= x);
+        match pos {
+            Some(0) | None => self.table_state.select_column(visible.last().copied()),
+            Some(pos) => self
+                .table_state
+                .select_column(Some(visible[pos.saturating_sub(amount as usize)])),
+        }
+    }
+
+    /// Moves the selected cell to the right by amount.
+    /// Wraps selection to the first column if we are at the last one.
+    /// Light wrapper of TableState's same-named function.
+    fn scroll_right_by(&mut self, amount: u16) {
+        // if self.uses_rows {
+        //     return;
+        // }
+        // hidden columns are skipped, so move between the shown ones
+        let visible = self.visible_columns();
+        let Some(x) = self.highlit_col_index() else {
+            return;
+        };
+        let pos = visible.iter().rposition(|visible_x| *visible_x <= x);
+        match pos {
+            Some(pos) if pos + 1 < visible.len() => self.table_state.select_column(Some(
+                visible[(pos + amount as usize).min(visible.len() - 1)],
+            )),
+            _ => self.table_state.select_column(visible.first().copied()),
+        }
+    }
+
+    /// Moves the selected row/cell up by amount, stopping at row 0.
+    /// Wraps selection to the last row if we are at row 0.
+    fn scroll_up_by(&mut self, amount: u16) {
+        let Some(last_row) = self.table.rows.len().checked_sub(1) else {
+            // there are no rows to move between
+            return;
+        };
+        let y = self.table_state.selected().unwrap_or(0).min(last_row);
+        if y == 0 {
+            self.table_state.select(Some(last_row));
+            self.scroll_state.last();
+            return;
+        }
+        // clamp within the rows as the TableState doesn't know how many there are
+        let new_y = y.saturating_sub(amount as usize);
+        self.table_state.select(Some(new_y));
+        self.scroll_state = self.scroll_state.position(self.row_offset(new_y));
+    }
+
+    /// Moves the selected row/cell down by amount, stopping at the last row.
+    /// Wraps selection to the first row if we are at the last one.
+    fn scroll_down_by(&mut self, amount: u16) {
+        let Some(last_row) = self.table.rows.len().checked_sub(1) else {
+            // there are no rows to move between
+            return;
+        };
+        let y = self.table_state.selected().unwrap_or(0).min(last_row);
+        if y == last_row {
+            self.table_state.select(Some(0));
+            self.scroll_state.first();
+            return;
+        }
+        // clamp within the rows as the TableState doesn't know how many there are
+        let new_y = y.saturating_add(amount as usize).min(last_row);
+        self.table_state.select(Some(new_y));
+        self.scroll_state = self.scroll_state.position(self.row_offset(new_y));
+    }
+}
+
+impl Component for TableDisplay {
+    fn name(&self) -> &'static str {
+        "Table"
+    }
+
+    fn handle_resize(&mut self, _width: u16, _height: u16) {
+        // the rows will wrap differently within the new size
+        self.last_rendered_heights.clear();
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent) -> Result<Vec<Action>, AppError> {
+        // ignore key releases
+        if key.kind == KeyEventKind::Release {
+            return Ok(vec![Action::Noop]);
+        }
+
+        // while jumping to a row, digits build up its number until Enter
+        // jumps to it, and any other key leaves without jumping
+        if let Some(mut buffer) = self.jump_buffer.take() {
+            return match key.code {
+                KeyCode::Char(c) if c.is_ascii_digit() => {
+                    buffer.push(c);
+                    self.jump_buffer = Some(buffer);
+                    Ok(vec![Action::Noop])
+                }
+                KeyCode::Backspace => {
+                    buffer.pop();
+                    self.jump_buffer = Some(buffer);
+                    Ok(vec![Action::Noop])
+                }
+                // gg still jumps to the first row
+                KeyCode::Char('g') if buffer.is_empty() => {
+                    self.table_state.select_first();
+                    self.scroll_state.first();
+                    Ok(vec![Action::HighlightChanged])
+                }
+                KeyCode::Enter => Ok(self.jump_to_typed_row(&buffer)),
+                _ => Ok(vec![Action::Noop]),
+            };
+        }
+
+        // a row number typed before G only applies to the key directly after it
+        let pending_jump = self.pending_jump.take();
+        // likewise a pending selection is only confirmed by the next key
+        let pending_selection = self.pending_selection.take();
+
+        match key.code {
+            // esc clears any selections before it would terminate
+            KeyCode::Esc if !self.state.selections.is_empty() => {
+                self.state.deselect_all();
+                Ok(vec![Action::DeselectAll])
+            }
+            KeyCode::Esc => Ok(vec![Action::Quit]), // terminate on encountering Esc
+            KeyCode::Char(c) if c.is_ascii_digit() => {
+                // accumulate the digits of the row number to jump to
+                let digit = c.to_digit(10).unwrap() as usize;
+                self.pending_jump = Some(
+                    pending_jump
+                        .unwrap_or(0)
+                        .saturating_mul(10)
+                        .saturating_add(digit),
+                );
+                Ok(vec![Action::Noop])
+            }
+            // ctrl+home/end jump to the first and last columns, and home/end
+            // to the first and last rows
+            KeyCode::Home if key.modifiers == KeyModifiers::CONTROL => {
+                let first = self.visible_columns().first().copied();
+                self.table_state.select_column(first);
+                Ok(vec![Action::HighlightChanged])
+            }
+            KeyCode::End if key.modifiers == KeyModifiers::CONTROL => {
+                let last = self.visible_columns().last().copied();
+                self.table_state.select_column(last);
+                Ok(vec![Action::HighlightChanged])
+            }
+            KeyCode::Home => {
+                self.table_state.select_first();
+                self.scroll_state.first();
+                Ok(vec![Action::HighlightChanged])
+            }
+            KeyCode::End => {
+                self.table_state.select_last();
+                self.scroll_state.last();
+                Ok(vec![Action::HighlightChanged])
+            }
+            KeyCode::Char('g') => {
+                self.jump_buffer = Some(String::new());
+                Ok(vec![Action::Noop])
+            }
+            KeyCode::Char('G') => {
+                if let Some(row) = pending_jump {
+                    // row numbers are typed 1-indexed
+                    self.jump_to_row(row.saturating_sub(1));
+                } else {
+                    self.table_state.select_last();
+                    self.scroll_state.last();
+                }
+                Ok(vec![Action::HighlightChanged])
+            }
+            // there is nothing to select within an empty table
+            KeyCode::Enter if self.table.rows.is_empty() => Ok(vec![Action::Noop]),
+            KeyCode::Enter => {
+                let selection_opt: Option<MultiTableSelection> = if self.uses_rows {
+                    self.table_state.selected().map(|row| row.into())
+                } else {
+                    self.table_state.selected_cell().map(|cell| cell.into())
+                };
+                if let Some(selection) = selection_opt
+                    && self.require_double_enter
+                    && !pending_selection.is_some_and(|(pending, pressed_at)| {
+                        pending == selection && pressed_at.elapsed() < DOUBLE_ENTER_WINDOW
+                    })
+                {
+                    // wait for a second Enter to confirm the selection
+                    self.pending_selection = Some((selection, Instant::now()));
+                    Ok(vec![Action::Noop])
+                } else if let Some(selection) = selection_opt {
+                    // if selection was added, return SelectionChanged, else Noop
+                    if self.state.select(selection) {
+                        Ok(vec![Action::SelectionChanged])
+                    } else {
+                        Ok(vec![Action::Noop])
+                    }
+                } else {
+                    Ok(vec![Action::Noop])
+                }
+            }
+            // ctrl+a selects every row, as far as the selections allow
+            KeyCode::Char('a') if key.modifiers == KeyModifiers::CONTROL => {
+                if !self.uses_rows || self.state.max_selections <= 1 {
+                    Ok(vec![Action::VeryLoudWrongBuzzer])
+                } else if self.state.select_all(self.table.rows.len()) {
+                    Ok(vec![Action::SelectionChanged])
+                } else {
+                    Ok(vec![Action::Noop])
+                }
+            }
+            // ctrl+shift+left/right resize the highlit column, as ctrl+left/right
+            // already move the focus between components
+            KeyCode::Left if key.modifiers == KeyModifiers::CONTROL | KeyModifiers::SHIFT => {
+                Ok(vec![Action::ResizeColumn(-1)])
+            }
+            KeyCode::Right if key.modifiers == KeyModifiers::CONTROL | KeyModifiers::SHIFT => {
+                Ok(vec![Action::ResizeColumn(1)])
+            }
+            KeyCode::Left => {
+                self.scroll_left_by(1);
+                Ok(vec![Action::HighlightChanged])
+            }
+            KeyCode::Right => {
+                self.scroll_right_by(1);
+                Ok(vec![Action::HighlightChanged])
+            }
+            KeyCode::PageUp => {
+                self.scroll_up_by(self.visible_rows() as u16);
+                Ok(vec![Action::HighlightChanged])
+            }
+            KeyCode::PageDown => {
+                self.scroll_down_by(self.visible_rows() as u16);
+                Ok(vec![Action::HighlightChanged])
+            }
+            KeyCode::Up => {
+                self.scroll_up_by(1);
+                Ok(vec![Action::HighlightChanged])
+            }
+            KeyCode::Down => {
+                self.scroll_down_by(1);
+                Ok(vec![Action::HighlightChanged])
+            }
+            _ => Ok(vec![Action::Noop]),
+        }
+    }
+
+    fn render(&mut self, f: &mut Frame, rect: Rect, block: Block) {
+        // the rows are laid out within the block, so remember that area
+        self.set_area(block.inner(rect));
+        // size each row to fit the wrapped content of its cells
+        self.column_widths = self.compute_column_widths(block.inner(rect));
+        self.update_row_heights();
+
+        // hidden columns are left out of both the header and the rows
+        let visible = self.visible_columns();
+        // map the column names into cells for the sake of the header row of the table
+        let columns = Row::from_iter(visible.iter().map(|x| {
+            let (x, column) = (*x, &self.table.columns[*x]);
+            let indicator = self
+                .sort_columns
+                .iter()
+                .position(|(sorted_x, _)| *sorted_x == x)
+                .map(|rank| {
+                    let arrow = self.sort_columns[rank].1.arrow();
+                    if self.sort_columns.len() > 1 {
+                        format!(" {}{}", arrow, rank + 1)
+                    } else {
+                        format!(" {}", arrow)
+                    }
+                });
+            let indicator_width = indicator.as_deref().map_or(0, |i| i.width() as u16);
+            // mark names too long for their column rather than cutting them off,
+            // keeping the sort indicator visible
+            let name = truncate_with_ellipsis(
+                column,
+                self.rendered_column_width(x)
+                    .saturating_sub(indicator_width),
+            );
+            let mut spans = vec![Span::raw(name.into_owned())];
+            if let Some(indicator) = indicator {
+                spans.push(Span::styled(indicator, Style::new().bold()));
+            }
+            Text::from(Line::from(spans)).centered()
+        }));
+
+        // define the style for each row
+        let row_style = Style::default()
+            .fg(DEFAULT_APP_COLORS.main_fg)
+            .bg(DEFAULT_APP_COLORS.main_bg);
+
+        let selection_colors = DEFAULT_APP_COLORS.selection_colors();
+        // map the rows' cells into Ratatui rows for the sake of the display
+        let rows: Vec<Row> = self
+            .table
+            .rows
+            .iter()
+            .enumerate()
+            .map(|(y, row)| {
+                // determine the color to use for the current selection
+                let selected_style_base = Style::default().bold();
+                // determine if this row needs to be selected as it overrides cell styles
+                let row_selected_ind = if self.uses_rows {
+                    self.state.index_of(MultiTableSelection::Row(y))
+                } else {
+                    None
+                };
+                // update highlighting depending on selection style and selected items
+                Row::new(visible.iter().enumerate().map(|(rendered_x, x)| {
+                    let (x, cell) = (*x, &row[*x]);
+                    let mut cur_cell_style = if row_selected_ind.is_none() {
+                        // current row is not selected, so column color is more complex
+                        if self
+                            .table_state
+                            .selected_cell()
+                            .is_some_and(|(_, highlit_col)| highlit_col == x)
+                        {
+                            // make the whole highlit column have a special bg color,
+                            // which selected cells override below
+                            Style::new().bg(DEFAULT_APP_COLORS.highlit_bg)
+                        } else if let Some(bg) = self.cell_backgrounds.get(&(y, x)) {
+                            Style::new().bg(*bg)
+                        } else if rendered_x % 2 == 0
+                            && y >= self.pinned_count
+                            && !self.row_backgrounds.contains_key(&y)
+                        {
+                            // alternate color as column is not highlit
+                            Style::new().bg(DEFAULT_APP_COLORS.alt_bg)
+                        } else {
+                            // just use no style as the row style acts as a default
+                            Style::new()
+                        }
+                    } else {
+                        // just use no style as the row style acts as a default
+                        Style::new()
+                    };
+                    if !self.uses_rows {
+                        // cell selection is used, so change style if this cell is selected
+                        if let Some(i) = self.state.index_of(MultiTableSelection::Cell((y, x))) {
+                            cur_cell_style = selected_style_base
+                                .bg(selection_colors[i % selection_colors.len()]);
+                        }
+                    }
+                    // flash the item awaiting a second Enter
+                    let pending = if self.uses_rows {
+                        MultiTableSelection::Row(y)
+                    } else {
+                        MultiTableSelection::Cell((y, x))
+                    };
+                    if self.pending_pulse_on(pending) {
+                        cur_cell_style = cur_cell_style.reversed();
+                    }
+                    let content = self.display_value(x, cell);
+                    let col_width = self.rendered_column_width(x);
+                    // wide characters may still leave a line too long, so mark
+                    // where it is cut off rather than letting the cell drop it
+                    let lines = wrap_with_max_lines(&content, col_width, MAX_ROW_HEIGHT)
+                        .into_iter()
+                        .map(|line| {
+                            let line = line.trim_end_matches('\n');
+                            Line::from(truncate_with_ellipsis(line, col_width).into_owned())
+                        });
+                    Cell::from(Text::from_iter(lines)).style(cur_cell_style)
+                }))
+                .style(if let Some(i) = row_selected_ind {
+                    selected_style_base.bg(selection_colors[i % selection_colors.len()])
+                } else if y < self.pinned_count {
+                    row_style.bg(DEFAULT_APP_COLORS.pinned_bg)
+                } else if let Some(bg) = self.row_backgrounds.get(&y) {
+                    row_style.bg(*bg)
+                } else {
+                    row_style
+                })
+                .height(self.last_rendered_heights[y])
+            })
+            .collect();
+        // set up the styling of the table, its header, and its selections
+        let header_style = Style::default()
+            .fg(DEFAULT_APP_COLORS.header_fg)
+            .bg(DEFAULT_APP_COLORS.header_bg);
+        let highlight_style = Style::new().reversed();
+
+        let mut table = TuiTable::default()
+            .block(block)
+            .bg(DEFAULT_APP_COLORS.main_bg)
+            .highlight_symbol(
+                // each item in the vec is a line, so 2 lines in accordance with ROW_HEIGHT,
+                // with rows of a single line only showing the first
+                Text::from(vec![" ╲ ".into(), " ╱ ".into()])
+                    .fg(DEFAULT_APP_COLORS.main_fg)
+                    .bold(),
+            );
+
+        if self.uses_rows {
+            table = table.row_highlight_style(highlight_style);
+        } else {
+            table = table.cell_highlight_style(highlight_style);
+        }
+        // make it have the desired columns and rows
+        table = table
+            .header(columns.style(header_style).height(1))
+            .rows(rows)
+            .widths(
+                visible
+                    .iter()
+                    .map(|x| Constraint::Length(self.column_widths[*x])),
+            );
+        // the rendered table only knows of the shown columns, so highlight
+        // the highlit column by its position among them while rendering
+        let highlit_col = self.table_state.selected_column();
+        if let Some(x) = self.highlit_col_index() {
+            self.table_state
+                .select_column(visible.iter().position(|visible_x| *visible_x >= x));
+        }
+        f.render_stateful_widget(table, rect, &mut self.table_state);
+        self.table_state.select_column(highlit_col);
+
+        let last_row = self.table.rows.len().saturating_sub(1);
+        let selected_row = self.table_state.selected().unwrap_or(0).min(last_row);
+        self.scroll_state = self
+            .scroll_state
+            .content_length(self.row_offset(last_row))
+            .position(self.row_offset(selected_row));
+
+        // render the scrollbar for the table
+        let mut scrollbar_rect = rect;
+        scrollbar_rect.y += 1;
+        scrollbar_rect.height = scrollbar_rect.height.saturating_sub(1);
+        f.render_stateful_widget(
+            Scrollbar::default()
+                .orientation(ratatui::widgets::ScrollbarOrientation::VerticalRight)
+                .begin_symbol(None)
+                .end_symbol(None)
+                .style(DEFAULT_APP_COLORS.main_fg),
+            scrollbar_rect,
+            &mut self.scroll_state,
+        );
+    }
+}
+
+impl AreaComponent for TableDisplay {
+    fn area(&self) -> Rect {
+        self.area
+    }
+
+    fn set_area(&mut self, rect: Rect) {
+        self.area = rect;
+    }
+}
+
+/// A collection of multiple selections, up to the passed amount,
+/// defaulting to 1 max selection
+struct MultiTableState {
+    pub(crate) max_selections: usize,
+    /// Whether each selected cell must be within a different row
+    pub(crate) require_unique_rows: bool,
+    pub(crate) selections: Vec<MultiTableSelection>,
+}
+
+/// Enum storing selections depending on whether the MultiTable selects rows
+/// or cells.
+///
+/// When storing cells, the values are stored in (y, x) order as it is in Ratatui
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum MultiTableSelection {
+    /// Tuple storing a coordinate in (y, x) order
+    Cell((usize, usize)),
+    /// Offset of the row/the y value of any cell in a row
+    Row(usize),
+}
+
+impl From<(usize, usize)> for MultiTableSelection {
+    fn from(value: (usize, usize)) -> Self {
+        MultiTableSelection::Cell(value)
+    }
+}
+
+impl From<usize> for MultiTableSelection {
+    fn from(value: usize) -> Self {
+        MultiTableSelection::Row(value)
+    }
+}
+
+impl Default for MultiTableState {
+    fn default() -> Self {
+        Self {
+            max_selections: 1,
+            require_unique_rows: false,
+            selections: Vec::with_capacity(1),
+        }
+    }
+}
+
+impl MultiTableState {
+    fn new(max_selections: usize) -> Self {
+        Self {
+            max_selections,
+            require_unique_rows: false,
+            // max_selections may be unbounded, so don't preallocate for it
+            selections: Vec::new(),
+        }
+    }
+
+    /// Removes every selection
+    fn deselect_all(&mut self) {
+        self.selections.clear();
+    }
+
+    /// Returns the index of the equivalent selection within the list of
+    /// selections if present, else None
+    fn index_of(&self, selection: MultiTableSelection) -> Option<usize> {
+        self.selections.iter().position(|item| *item == selection)
+    }
+
+    /// Returns true if the passed selection is a cell within a row which
+    /// already has a different cell selected
+    fn row_in_use(&self, selection: MultiTableSelection) -> bool {
+        let MultiTableSelection::Cell((y, _)) = selection else {
+            return false;
+        };
+        self.selections
+            .iter()
+            .any(|item| matches!(item, MultiTableSelection::Cell((other_y, _)) if *other_y == y))
+    }
+
+    /// Adds the passed selection to the Vec of selections,
+    /// or removes it if it is already present
+    ///
+    /// Pushes new selections to the end of the list such that
+    /// older selections will be at the front of the list.
+    ///
+    /// Rejects a new cell selection within a row which already has a selected
+    /// cell if unique rows are required.
+    ///
+    /// Returns true if the selection was added, false if not
+    fn select(&mut self, selection: MultiTableSelection) -> bool {
+        // search for item in reverse under the naive, but somewhat true
+        // assumption that the selections which get removed most are those
+        // which have been more recently added
+        if let Some(ind) = self.selections.iter().rposition(|item| *item == selection) {
+            self.selections.remove(ind);
+        } else if self.require_unique_rows && self.row_in_use(selection) {
+            return false;
+        } else if self.selections.len() < self.max_selections {
+            self.selections.push(selection);
+            return true;
+        }
+        false
+    }
+
+    /// Selects each of the first num_rows rows which isn't already selected,
+    /// in order, stopping once max_selections is reached
+    ///
+    /// Returns true if any selection was added, false if not
+    fn select_all(&mut self, num_rows: usize) -> bool {
+        let num_selections = self.selections.len();
+        for y in 0..num_rows {
+            if self.selections.len() >= self.max_selections {
+                break;
+            }
+            if self.index_of(MultiTableSelection::Row(y)).is_none() {
+                self.selections.push(MultiTableSelection::Row(y));
+            }
+        }
+        self.selections.len() > num_selections
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds a table of people, with a row for each of the passed names
+    fn people(names: &[&str]) -> Table {
+        names
+            .iter()
+            .enumerate()
+            .fold(
+                Table::builder().column("id").column("name"),
+                |builder, (id, name)| {
+                    builder.row(vec![
+                        Value::Integer(id as i64),
+                        Value::Text(name.to_string()),
+                    ])
+                },
+            )
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn from_table_highlights_the_first_cell() {
+        let display = TableDisplay::from_table(people(&["ann", "bob"]), false, 1);
+        assert_eq!(display.columns(), ["id", "name"]);
+        assert_eq!(display.rows().len(), 2);
+        assert_eq!(display.highlit_cell(), Some((0, 0)));
+        assert_eq!(display.highlit_col_name(), Some("id"));
+        assert_eq!(display.highlit_cell_value(), Some("0".to_string()));
+    }
+
+    #[test]
+    fn table_without_rows_has_no_highlit_cell() {
+        let table = Table::new(vec!["id".to_string()], Vec::new());
+        let display = TableDisplay::from_table(table, false, 1);
+        assert_eq!(display.highlit_cell(), None);
+        assert_eq!(display.highlit_cell_value(), None);
+    }
+
+    #[test]
+    fn pinned_rows_move_to_the_top_in_order() {
+        let mut display = TableDisplay::from_table(people(&["ann", "bob", "cat", "dan"]), true, 1);
+        display.pin_rows(&[3, 1]);
+        let names: Vec<String> = display
+            .rows()
+            .iter()
+            .map(|row| row[1].to_string())
+            .collect();
+        assert_eq!(names, ["dan", "bob", "ann", "cat"]);
+    }
+
+    #[test]
+    fn hiding_the_highlit_column_moves_the_highlight() {
+        let mut display = TableDisplay::from_table(people(&["ann"]), false, 1);
+        // hiding every column is ignored
+        display.set_hidden_columns(HashSet::from([0, 1]));
+        assert!(display.hidden_columns().is_empty());
+
+        display.set_hidden_columns(HashSet::from([0]));
+        assert_eq!(display.hidden_columns(), &HashSet::from([0]));
+        assert_eq!(display.highlit_col_name(), Some("name"));
+    }
+
+    #[test]
+    fn row_heights_fit_the_tallest_shown_cell() {
+        let table = Table::builder()
+            .column("short")
+            .column("long")
+            .row(vec![
+                Value::Text("a".to_string()),
+                Value::Text("abcdefghij".to_string()),
+            ])
+            .row(vec![Value::Text("b".to_string()), Value::Null])
+            .build()
+            .unwrap();
+        let mut display = TableDisplay::from_table(table, false, 1);
+        assert_eq!(display.compute_row_heights(&[4, 4]), [3, 1]);
+        display.set_hidden_columns(HashSet::from([1]));
+        assert_eq!(display.compute_row_heights(&[4, 4]), [1, 1]);
+    }
+
+    #[test]
+    fn row_heights_are_kept_until_the_rows_or_widths_change() {
+        let mut display = TableDisplay::from_table(people(&["ann", "bob"]), false, 1);
+        display.column_widths = vec![4, 4];
+        display.update_row_heights();
+        assert_eq!(display.last_rendered_heights, [1, 1]);
+
+        display.set_value(1, 1, Value::Text("bobbington".to_string()));
+        display.update_row_heights();
+        assert_eq!(display.last_rendered_heights, [1, 3]);
+        display.column_widths = vec![4, 10];
+        display.update_row_heights();
+        assert_eq!(display.last_rendered_heights, [1, 1]);
+    }
+
+    #[test]
+    fn scrolling_down_an_empty_table_does_nothing() {
+        let mut display = TableDisplay::from_table(people(&[]), false, 1);
+        display.scroll_down_by(3);
+        assert_eq!(display.highlit_cell(), None);
+        assert_eq!(display.scroll_state, display.scroll_state.position(0));
+    }
+
+    #[test]
+    fn scrolling_down_a_single_row_stays_on_it() {
+        let mut display = TableDisplay::from_table(people(&["ann"]), false, 1);
+        display.scroll_down_by(3);
+        assert_eq!(display.highlit_cell(), Some((0, 0)));
+        assert_eq!(display.scroll_state, display.scroll_state.position(0));
+    }
+
+    #[test]
+    fn scrolling_down_past_the_last_row_clamps_then_wraps() {
+        let mut display =
+            TableDisplay::from_table(people(&["ann", "bob", "cat", "dan", "eve"]), false, 1);
+        display.scroll_down_by(2);
+        assert_eq!(display.highlit_cell(), Some((2, 0)));
+        // moving past the last row stops on it
+        display.scroll_down_by(10);
+        assert_eq!(display.highlit_cell(), Some((4, 0)));
+        assert_eq!(
+            display.scroll_state,
+            display.scroll_state.position(display.row_offset(4))
+        );
+        // moving on from the last row wraps to the first
+        display.scroll_down_by(10);
+        assert_eq!(display.highlit_cell(), Some((0, 0)));
+        assert_eq!(display.scroll_state, display.scroll_state.position(0));
+    }
+
+    #[test]
+    fn export_selection_gives_rows_in_selection_order() {
+        let mut display = TableDisplay::from_table(people(&["ann", "bob", "cat"]), true, 2);
+        display.select(MultiTableSelection::Row(2));
+        display.select(MultiTableSelection::Row(0));
+        assert_eq!(
+            display.export_selection(),
+            vec![
+                vec![Value::Integer(2), Value::Text("cat".to_string())],
+                vec![Value::Integer(0), Value::Text("ann".to_string())],
+            ]
+        );
+    }
+
+    #[test]
+    fn export_selection_gives_selected_cells_alone() {
+        let mut display = TableDisplay::from_table(people(&["ann", "bob"]), false, 1);
+        display.select(MultiTableSelection::Cell((1, 1)));
+        assert_eq!(
+            display.export_selection(),
+            vec![vec![Value::Text("bob".to_string())]]
+        );
+    }
+}
@@ -1,133 +1,557 @@
-use ratatui::{
-    style::{Color, palette::tailwind},
-    widgets::{Block, BorderType},
-};
-use std::{collections::HashMap, env, fs::read_dir};
-
-// Just a file containing useful config information
-use crate::{
-    autofill::{AutoFillFn, html_filepath},
-    component::{command_list::EditCommand, selected_table::TableMetadata},
-};
-
-pub const WORKING_DIRECTORY: &str = "Website";
-pub const DATABASE_PATH: &str = "./data/site-content.db";
-pub const PHP_PATH: &str = "php";
-
-/// Changes the working directory to be the ancestor directory with the
-/// base name specified by the [`WORKING_DIRECTORY`] constant defined within
-/// the config.rs file
-pub fn change_working_directory_to_root() {
-    let mut current_dir = env::current_dir().expect("Invalid cwd, or no permissions to access cwd");
-    // Find the directory specified by WORKING_DIRECTORY to root out of
-    while let Ok(dir_iter) = read_dir(&current_dir) {
-        let root_dir_opt = dir_iter
-            .filter_map(|entry_res| match entry_res {
-                Ok(entry) => {
-                    if entry.path().is_dir() {
-                        Some(entry)
-                    } else {
-                        None
-                    }
-                }
-                Err(_) => None,
-            })
-            .find(|entry| entry.file_name().eq(WORKING_DIRECTORY));
-        if let Some(root_dir) = root_dir_opt {
-            current_dir = root_dir.path();
-            break;
-        } else {
-            current_dir.pop();
-        }
-    }
-    if current_dir.file_name().is_none() {
-        panic!("Couldn't find {} directory to root from", WORKING_DIRECTORY);
-    }
-    env::set_current_dir(current_dir.as_path()).expect("Failed to change working directory");
-}
-
-pub struct AppColors {
-    pub main_fg: Color,
-    pub main_bg: Color,
-    pub alt_bg: Color,
-    pub highlit_bg: Color,
-    pub header_fg: Color,
-    pub header_bg: Color,
-    pub border_color: Color,
-    pub selection_one_bg: Color,
-    pub selection_two_bg: Color,
-    pub selection_three_bg: Color,
-    pub selection_four_bg: Color,
-}
-
-impl AppColors {
-    pub fn selection_colors(&self) -> Vec<Color> {
-        vec![
-            self.selection_one_bg,
-            self.selection_two_bg,
-            self.selection_three_bg,
-            self.selection_four_bg,
-        ]
-    }
-
-    pub fn default_block(&self) -> Block {
-        Block::bordered().border_style(self.border_color)
-    }
-
-    pub fn focused_block(&self) -> Block {
-        self.default_block()
-            .border_type(BorderType::QuadrantOutside)
-    }
-}
-
-/// A collection of colors used by components of the app to synchronize style
-/// a bit easier and allow for ease of app redesign,
-///
-/// Highlight style of lists and tables should just be `Style::new().reversed()`
-pub const DEFAULT_APP_COLORS: AppColors = AppColors {
-    main_fg: tailwind::SLATE.c200,
-    main_bg: tailwind::SLATE.c950,
-    alt_bg: tailwind::SLATE.c900,
-    highlit_bg: tailwind::GRAY.c800,
-    header_fg: tailwind::SLATE.c200,
-    header_bg: tailwind::BLUE.c900,
-    border_color: tailwind::CYAN.c400,
-    selection_one_bg: Color::Rgb(113, 169, 247), // 113, 169, 247 | 104, 125, 211
-    selection_two_bg: Color::Rgb(148, 79, 160),
-    selection_three_bg: Color::Rgb(199, 102, 116),
-    selection_four_bg: Color::Rgb(154, 153, 69),
-};
-
-pub fn editable_tables() -> Vec<TableMetadata> {
-    vec![
-        TableMetadata {
-            commands: vec![
-                EditCommand::Modify,
-                EditCommand::Reorder,
-                EditCommand::Delete,
-                EditCommand::Add,
-            ],
-            display_name: "Category",
-            table_name: "category",
-            autofill_funcs: HashMap::from([("cat_index_path", html_filepath as AutoFillFn)]),
-        },
-        TableMetadata {
-            commands: vec![EditCommand::Modify, EditCommand::Delete, EditCommand::Add],
-            display_name: "Document",
-            table_name: "document",
-            autofill_funcs: HashMap::from([("doc_path", html_filepath as AutoFillFn)]),
-        },
-        TableMetadata {
-            commands: vec![
-                EditCommand::Modify,
-                EditCommand::Reorder,
-                EditCommand::Swap,
-                EditCommand::Delete,
-                EditCommand::Add,
-            ],
-            display_name: "CategoryDocument",
-            table_name: "categorydocument",
-            autofill_funcs: HashMap::with_capacity(0),
-        },
-    ]
-}
+use getopts::Options;
+use ratatui::{
+    style::{Color, palette::tailwind},
+    widgets::{Block, BorderType},
+};
+use std::{
+    collections::HashMap,
+    env,
+    error::Error,
+    fs::{self, read_dir},
+    path::{self, Path, PathBuf},
+    time::Duration,
+};
+
+// Just a file containing useful config information
+use crate::{
+    autofill::filepath_autofill_factory,
+    component::{command_list::EditCommand, selected_table::TableMetadata},
+    value::{DisplayFormatFn, Value},
+};
+
+pub const DEFAULT_WORKING_DIRECTORY: &str = "Website";
+pub const DEFAULT_DATABASE_PATH: &str = "./data/site-content.db";
+pub const CONFIG_DIRECTORY: &str = "cli-tool";
+pub const CONFIG_FILE: &str = "config.toml";
+pub const PHP_PATH: &str = "php";
+pub const IMAGES_PATH: &str = "images";
+/// Most distinct values of a column searched when completing a cell from them
+pub const VALUE_AUTOFILL_LIMIT: usize = 10;
+/// How long a status message stays shown before being cleared
+pub const STATUS_MESSAGE_DURATION: Duration = Duration::from_secs(5);
+/// How long notifications are shown for before being dismissed
+pub const NOTIFICATION_DURATION: Duration = Duration::from_secs(3);
+/// File the executed queries are logged to when debugging
+pub const QUERY_LOG_PATH: &str = "query.log";
+/// Size in bytes past which the query log is rotated
+pub const MAX_QUERY_LOG_SIZE: u64 = 1024 * 1024;
+pub const DEFAULT_FPS: u64 = 60;
+pub const MIN_FPS: u64 = 10;
+/// Frame rate above which terminal rendering rarely gets any smoother
+pub const MAX_USEFUL_FPS: u64 = 60;
+/// Smallest terminal size the app's layout fits within, below which a
+/// warning is shown instead
+pub const MIN_WIDTH: u16 = 40;
+pub const MIN_HEIGHT: u16 = 15;
+/// Locale used for the text displayed by the app, where only English text
+/// is available so far
+pub const LOCALE: &str = "en";
+/// Number of changes to the database which can be undone by default
+pub const DEFAULT_UNDO_DEPTH: usize = 50;
+/// Names of the columns which may hold a row's display order, from the most
+/// to the least preferred when a table has several of them
+pub const ORDER_COLUMN_NAMES: [&str; 6] = [
+    "order_index",
+    "position",
+    "order",
+    "seq",
+    "sort_order",
+    "rank",
+];
+/// Key combinations the app responds to alongside what they do, listed by
+/// the help popup
+pub const KEYBINDINGS: &[(&str, &str)] = &[
+    ("?", "Show or hide this help"),
+    (":", "Open the command palette"),
+    (
+        "Ctrl+Left / Ctrl+Right",
+        "Move the focus between components",
+    ),
+    (
+        "Arrow keys",
+        "Move the highlight, or through the cell reader",
+    ),
+    (
+        "Shift+Arrow keys",
+        "Move the highlight while the cell reader is shown",
+    ),
+    ("Enter", "Select the highlit table, command, row, or cell"),
+    ("Esc", "Clear the selections, close a popup, or quit"),
+    ("1-9", "Jump to a table in the list of tables"),
+    ("g <number> Enter", "Jump to the numbered row"),
+    ("gg / G", "Jump to the first or last row"),
+    ("<number> G", "Jump to the numbered row"),
+    ("Home / End", "Jump to the first or last row"),
+    (
+        "PageUp / PageDown",
+        "Move the highlight up or down by a page of rows",
+    ),
+    ("Ctrl+Home / Ctrl+End", "Jump to the first or last column"),
+    ("s", "Sort by the highlit column"),
+    ("S", "Add the highlit column as a tie-breaking sort"),
+    (
+        "Ctrl+Shift+Left / Right",
+        "Narrow or widen the highlit column",
+    ),
+    ("/", "Filter the table as a search term is typed"),
+    ("Ctrl+F", "Open the filter"),
+    (
+        "f",
+        "Follow the highlit foreign key to the row it references",
+    ),
+    ("Ctrl+E", "Edit and run the raw SQL query of the table"),
+    ("F5", "Run the query being edited"),
+    (
+        "Ctrl+Enter",
+        "Run the query being edited, or commit the open batch",
+    ),
+    ("v", "Choose which columns are shown"),
+    ("e", "Edit the highlit cell when modifying"),
+    (
+        "Ctrl+Space",
+        "Suggest how to complete the cell being edited",
+    ),
+    (
+        "Ctrl+A",
+        "Select every row when several rows can be selected",
+    ),
+    ("d", "Delete the selected rows when deleting"),
+    ("r", "Reorder the selected rows when reordering"),
+    ("Ctrl+D", "Duplicate the selected or highlit row"),
+    ("Ctrl+P", "Pin or unpin the highlit row"),
+    ("Ctrl+Q", "Copy the query used for the shown table"),
+    ("x", "Export the shown table to a CSV file"),
+    (
+        "Ctrl+Z",
+        "Undo the last change to the table, or to the text being typed",
+    ),
+    ("Ctrl+Y", "Redo the last undone change"),
+    (
+        "Ctrl+B",
+        "Begin a batch of changes, or commit the open batch",
+    ),
+    ("Ctrl+Esc", "Discard the changes made during the open batch"),
+];
+
+/// Configuration determined when the app starts up
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub database_path: PathBuf,
+    pub working_directory: PathBuf,
+    /// Whether each edit command is displayed with its icon
+    pub show_command_icons: bool,
+    /// How long the app waits for input between each rendered frame
+    pub tick_duration: Duration,
+    /// Whether animations are shown, such as the spinner while loading
+    pub animations: bool,
+    /// Whether executed queries are logged to [`QUERY_LOG_PATH`]
+    pub debug: bool,
+    /// Whether the database is opened without allowing writes to it
+    pub read_only: bool,
+    /// Other databases to attach, as their schema names and paths
+    pub attached_databases: Vec<(String, PathBuf)>,
+    /// Number of changes to the database which can be undone
+    pub undo_depth: usize,
+}
+
+/// Options passed to the app on the command line
+#[derive(Debug, Clone)]
+pub struct CliOptions {
+    pub fps: u64,
+    pub animations: bool,
+    pub debug: bool,
+    pub read_only: bool,
+    /// Database to open in place of the configured one
+    pub database_path: Option<PathBuf>,
+}
+
+impl Default for CliOptions {
+    fn default() -> Self {
+        Self {
+            fps: DEFAULT_FPS,
+            animations: true,
+            debug: false,
+            read_only: false,
+            database_path: None,
+        }
+    }
+}
+
+/// Parses the passed command line arguments, which exclude the program name.
+/// Returns None if the help message was requested and printed instead
+pub fn parse_args(args: &[String]) -> Result<Option<CliOptions>, Box<dyn Error>> {
+    let mut opts = Options::new();
+    opts.optopt(
+        "",
+        "fps",
+        &format!("frames rendered per second (default {DEFAULT_FPS}, minimum {MIN_FPS})"),
+        "N",
+    );
+    opts.optflag(
+        "",
+        "no-animation",
+        "render at the minimum frame rate without animated effects",
+    );
+    opts.optflag(
+        "",
+        "debug",
+        &format!("log every executed query to {QUERY_LOG_PATH}"),
+    );
+    opts.optflag(
+        "",
+        "readonly",
+        "open the database without allowing any changes to it",
+    );
+    opts.optopt(
+        "",
+        "db",
+        "open the database at PATH instead of the configured one",
+        "PATH",
+    );
+    opts.optflag("h", "help", "print this help message");
+    let matches = opts.parse(args)?;
+    if matches.opt_present("help") {
+        print!("{}", opts.usage("Usage: cli [options]"));
+        return Ok(None);
+    }
+
+    let debug = matches.opt_present("debug");
+    let read_only = matches.opt_present("readonly");
+    let database_path = matches.opt_str("db").map(PathBuf::from);
+    if matches.opt_present("no-animation") {
+        return Ok(Some(CliOptions {
+            fps: MIN_FPS,
+            animations: false,
+            debug,
+            read_only,
+            database_path,
+        }));
+    }
+    let mut fps = match matches.opt_str("fps") {
+        Some(fps) => fps.parse::<u64>()?.max(MIN_FPS),
+        None => DEFAULT_FPS,
+    };
+    if fps > MAX_USEFUL_FPS {
+        eprintln!(
+            "Frame rates above {MAX_USEFUL_FPS} rarely render any smoother, using {MAX_USEFUL_FPS}"
+        );
+        fps = MAX_USEFUL_FPS;
+    }
+    Ok(Some(CliOptions {
+        fps,
+        animations: true,
+        debug,
+        read_only,
+        database_path,
+    }))
+}
+
+/// Resolves the config used by the app and changes the working directory to
+/// the one it specifies.
+///
+/// The `database_path` key of the config file at
+/// `$XDG_CONFIG_HOME/cli-tool/config.toml` (or `$HOME/.config/cli-tool/config.toml`)
+/// is used if present, alongside its optional `working_directory` key which
+/// otherwise defaults to the current directory. Without a config file the
+/// working directory is found by [`find_root_directory`] and the database
+/// path defaults to [`DEFAULT_DATABASE_PATH`]. Its `show_command_icons` key
+/// controls whether icons are shown next to the edit commands, its
+/// `attach` table maps schema names to the paths of databases to attach, and
+/// its `undo_depth` key sets how many changes can be undone. A database path
+/// passed with `--db` overrides the one found otherwise
+pub fn resolve_config(options: CliOptions) -> Config {
+    let file_table = read_config_file();
+    let (database_path, working_directory) = file_table
+        .as_ref()
+        .and_then(paths_from_file)
+        .unwrap_or_else(|| (PathBuf::from(DEFAULT_DATABASE_PATH), find_root_directory()));
+    // a passed path is relative to where the app was run from, not the
+    // working directory it's about to change to
+    let database_path = options
+        .database_path
+        .map(|path| path::absolute(&path).unwrap_or(path))
+        .unwrap_or(database_path);
+    // dumb terminals are unlikely to be able to display the icons
+    let show_command_icons = file_table
+        .as_ref()
+        .and_then(|table| table.get("show_command_icons"))
+        .and_then(|show| show.as_bool())
+        .unwrap_or(true)
+        && env::var("TERM").map_or(true, |term| term != "dumb");
+    let attached_databases = file_table
+        .as_ref()
+        .and_then(|table| table.get("attach"))
+        .and_then(|attach| attach.as_table())
+        .map_or(Vec::new(), |attach| {
+            attach
+                .iter()
+                .filter_map(|(schema, path)| Some((schema.clone(), PathBuf::from(path.as_str()?))))
+                .collect()
+        });
+    let undo_depth = file_table
+        .as_ref()
+        .and_then(|table| table.get("undo_depth"))
+        .and_then(|depth| depth.as_integer())
+        .and_then(|depth| usize::try_from(depth).ok())
+        .unwrap_or(DEFAULT_UNDO_DEPTH);
+    let config = Config {
+        database_path,
+        working_directory,
+        show_command_icons,
+        tick_duration: Duration::from_millis(1000 / options.fps),
+        animations: options.animations,
+        debug: options.debug,
+        read_only: options.read_only,
+        attached_databases,
+        undo_depth,
+    };
+    env::set_current_dir(config.working_directory.as_path())
+        .expect("Failed to change working directory");
+    config
+}
+
+/// Returns the path the config file is expected to be at, if there is a
+/// config directory for the current user
+fn config_file_path() -> Option<PathBuf> {
+    let config_home = env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(config_home.join(CONFIG_DIRECTORY).join(CONFIG_FILE))
+}
+
+/// Reads and parses the config file, returning None if there is no config
+/// file. An invalid config file is warned about, then ignored so the
+/// defaults are used instead
+fn read_config_file() -> Option<toml::Table> {
+    let path = config_file_path()?;
+    let contents = fs::read_to_string(&path).ok()?;
+    parse_config_file(&path, &contents)
+}
+
+/// Parses the contents of the config file at the passed path, warning about
+/// and returning None for invalid TOML
+fn parse_config_file(path: &Path, contents: &str) -> Option<toml::Table> {
+    match contents.parse::<toml::Table>() {
+        Ok(table) => Some(table),
+        Err(err) => {
+            eprintln!(
+                "Ignoring the invalid config file {}, using the defaults: {}",
+                path.display(),
+                err
+            );
+            None
+        }
+    }
+}
+
+/// Reads the database path and working directory from the parsed config
+/// file, returning None if it does not specify a database path
+fn paths_from_file(table: &toml::Table) -> Option<(PathBuf, PathBuf)> {
+    let database_path = PathBuf::from(table.get("database_path")?.as_str()?);
+    let working_directory = match table.get("working_directory").and_then(|dir| dir.as_str()) {
+        Some(dir) => PathBuf::from(dir),
+        None => env::current_dir().expect("Invalid cwd, or no permissions to access cwd"),
+    };
+    Some((database_path, working_directory))
+}
+
+/// Finds the ancestor directory with the base name specified by the
+/// [`DEFAULT_WORKING_DIRECTORY`] constant defined within the config.rs file
+pub fn find_root_directory() -> PathBuf {
+    let mut current_dir = env::current_dir().expect("Invalid cwd, or no permissions to access cwd");
+    // Find the directory specified by WORKING_DIRECTORY to root out of
+    while let Ok(dir_iter) = read_dir(&current_dir) {
+        let root_dir_opt = dir_iter
+            .filter_map(|entry_res| match entry_res {
+                Ok(entry) => {
+                    if entry.path().is_dir() {
+                        Some(entry)
+                    } else {
+                        None
+                    }
+                }
+                Err(_) => None,
+            })
+            .find(|entry| entry.file_name().eq(DEFAULT_WORKING_DIRECTORY));
+        if let Some(root_dir) = root_dir_opt {
+            current_dir = root_dir.path();
+            break;
+        } else {
+            current_dir.pop();
+        }
+    }
+    if current_dir.file_name().is_none() {
+        panic!(
+            "Couldn't find {} directory to root from",
+            DEFAULT_WORKING_DIRECTORY
+        );
+    }
+    current_dir
+}
+
+pub struct AppColors {
+    pub main_fg: Color,
+    pub main_bg: Color,
+    pub alt_bg: Color,
+    pub highlit_bg: Color,
+    pub header_fg: Color,
+    pub header_bg: Color,
+    pub border_color: Color,
+    /// Border of the focused component while the database is read-only
+    pub read_only_border_color: Color,
+    pub pinned_bg: Color,
+    pub invalid_field_bg: Color,
+    pub error_fg: Color,
+    pub warning_fg: Color,
+    pub diff_changed_bg: Color,
+    pub diff_added_bg: Color,
+    pub diff_removed_bg: Color,
+    pub selection_one_bg: Color,
+    pub selection_two_bg: Color,
+    pub selection_three_bg: Color,
+    pub selection_four_bg: Color,
+}
+
+impl AppColors {
+    pub fn selection_colors(&self) -> Vec<Color> {
+        vec![
+            self.selection_one_bg,
+            self.selection_two_bg,
+            self.selection_three_bg,
+            self.selection_four_bg,
+        ]
+    }
+
+    pub fn default_block(&self) -> Block<'_> {
+        Block::bordered().border_style(self.border_color)
+    }
+
+    pub fn focused_block(&self) -> Block<'_> {
+        self.default_block()
+            .border_type(BorderType::QuadrantOutside)
+    }
+
+    /// Focused block drawn in another color to show that the database
+    /// can't be changed
+    pub fn focused_read_only_block(&self) -> Block<'_> {
+        self.focused_block()
+            .border_style(self.read_only_border_color)
+    }
+}
+
+/// A collection of colors used by components of the app to synchronize style
+/// a bit easier and allow for ease of app redesign,
+///
+/// Highlight style of lists and tables should just be `Style::new().reversed()`
+pub const DEFAULT_APP_COLORS: AppColors = AppColors {
+    main_fg: tailwind::SLATE.c200,
+    main_bg: tailwind::SLATE.c950,
+    alt_bg: tailwind::SLATE.c900,
+    highlit_bg: tailwind::GRAY.c800,
+    header_fg: tailwind::SLATE.c200,
+    header_bg: tailwind::BLUE.c900,
+    border_color: tailwind::CYAN.c400,
+    read_only_border_color: tailwind::GREEN.c400,
+    pinned_bg: tailwind::SLATE.c800,
+    invalid_field_bg: tailwind::RED.c950,
+    error_fg: tailwind::RED.c400,
+    warning_fg: tailwind::AMBER.c400,
+    diff_changed_bg: tailwind::AMBER.c900,
+    diff_added_bg: tailwind::GREEN.c900,
+    diff_removed_bg: tailwind::RED.c900,
+    selection_one_bg: Color::Rgb(113, 169, 247), // 113, 169, 247 | 104, 125, 211
+    selection_two_bg: Color::Rgb(148, 79, 160),
+    selection_three_bg: Color::Rgb(199, 102, 116),
+    selection_four_bg: Color::Rgb(154, 153, 69),
+};
+
+/// Displays the integer stored within a status column as the name of the
+/// status it represents
+pub fn format_status(value: &Value) -> String {
+    match value {
+        Value::Integer(0) => "Draft".to_string(),
+        Value::Integer(1) => "Published".to_string(),
+        Value::Integer(2) => "Archived".to_string(),
+        other => other.to_string(),
+    }
+}
+
+pub fn editable_tables() -> Vec<TableMetadata> {
+    vec![
+        TableMetadata {
+            commands: vec![
+                EditCommand::Modify,
+                EditCommand::Reorder,
+                EditCommand::Delete,
+                EditCommand::Add,
+                EditCommand::Import,
+            ],
+            display_name: "Category",
+            table_name: "category",
+            autofill_funcs: HashMap::from([(
+                "cat_index_path",
+                filepath_autofill_factory(PHP_PATH, "html"),
+            )]),
+            autofill_factory: None,
+            display_format_funcs: HashMap::new(),
+            min_cols_for_search: Some(5),
+            is_read_only: false,
+            order_column: None,
+        },
+        TableMetadata {
+            commands: vec![
+                EditCommand::Modify,
+                EditCommand::Delete,
+                EditCommand::Add,
+                EditCommand::Import,
+                EditCommand::Filter,
+            ],
+            display_name: "Document",
+            table_name: "document",
+            autofill_funcs: HashMap::from([
+                ("doc_path", filepath_autofill_factory(PHP_PATH, "html")),
+                ("image_path", filepath_autofill_factory(IMAGES_PATH, "png")),
+            ]),
+            autofill_factory: None,
+            display_format_funcs: HashMap::from([("status", format_status as DisplayFormatFn)]),
+            min_cols_for_search: Some(5),
+            is_read_only: false,
+            order_column: None,
+        },
+        TableMetadata {
+            commands: vec![
+                EditCommand::Modify,
+                EditCommand::Reorder,
+                EditCommand::Swap,
+                EditCommand::Delete,
+                EditCommand::Add,
+                EditCommand::Import,
+            ],
+            display_name: "CategoryDocument",
+            table_name: "categorydocument",
+            autofill_funcs: HashMap::with_capacity(0),
+            autofill_factory: None,
+            display_format_funcs: HashMap::new(),
+            min_cols_for_search: Some(5),
+            is_read_only: false,
+            order_column: None,
+        },
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invalid_config_files_are_ignored() {
+        let path = Path::new("config.toml");
+        assert!(parse_config_file(path, "database_path = ").is_none());
+        let table = parse_config_file(path, "undo_depth = 5").unwrap();
+        assert_eq!(
+            table.get("undo_depth").and_then(|d| d.as_integer()),
+            Some(5)
+        );
+    }
+}
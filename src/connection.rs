@@ -1,175 +1,880 @@
-use std::error::Error;
-
-use crate::{
-    config::DATABASE_PATH,
-    value::{Value, ValueType},
-};
-use rusqlite::{Connection as RsqConnection, OpenFlags, Params, types::Value as RsqValue};
-
-/// A table of Values, generated through a query to some database
-#[derive(Debug, Clone)]
-pub struct Table {
-    pub(crate) rows: Vec<Vec<Value>>,
-    pub(crate) columns: Vec<String>,
-    pub(crate) query: Option<String>,
-}
-
-impl Table {
-    pub fn column_index(&self, name: &str) -> Option<usize> {
-        self.columns
-            .iter()
-            .position(|column| column.as_str() == name)
-    }
-
-    /// Function to get the value stored at the column with the
-    /// specified name within the row at the passed index
-    pub fn row_get(&self, row: usize, name: &str) -> Option<&Value> {
-        let col = self.column_index(name)?;
-        Some(&self.rows[row][col])
-    }
-}
-
-#[derive(Debug)]
-pub struct ColumnInfo {
-    pub(crate) name: String,
-    pub(crate) data_type: ValueType,
-    pub(crate) is_not_null: bool,
-    pub(crate) default: Value,
-    pub(crate) is_primary_key: bool,
-    pub(crate) cid: usize,
-}
-
-impl std::fmt::Display for ColumnInfo {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let info_vec: Vec<&str> = [
-            self.is_primary_key.then_some("PK"),
-            self.is_not_null.then_some("Required"),
-            Some(match self.data_type {
-                ValueType::Null => "Null",
-                ValueType::Integer => "Int",
-                ValueType::Real => "Real",
-                ValueType::Text => "Text",
-                ValueType::Blob => "Blob",
-            }),
-        ]
-        .into_iter()
-        .flatten()
-        .collect();
-        write!(f, "{}", info_vec.join(", "))
-    }
-}
-
-/// A connection to the database updated and read by the app
-pub struct Connection {
-    connection: RsqConnection,
-}
-
-impl Connection {
-    pub fn new() -> Result<Self, Box<dyn Error>> {
-        let connection = RsqConnection::open_with_flags(
-            DATABASE_PATH,
-            OpenFlags::SQLITE_OPEN_READ_WRITE
-                | OpenFlags::SQLITE_OPEN_URI
-                | OpenFlags::SQLITE_OPEN_NO_MUTEX,
-        )?;
-        Ok(Self { connection })
-    }
-
-    /// Computes the passed query using this connection
-    ///
-    /// returns a Result containing the resulting rows of the table,
-    /// or an Error indicating the failure
-    pub fn query<T: Params>(&self, query: &str, params: T) -> Result<Table, Box<dyn Error>> {
-        // generate a unique, index associated pair of vectors for
-        // the column names and the row data associated with those columns
-        let mut stmt = self.connection.prepare(query)?;
-        let columns: Vec<String> = stmt
-            .column_names()
-            .iter()
-            .map(|col| col.to_string())
-            .collect();
-        // map the query into a 2d array of returned values
-        let rows: Vec<Vec<Value>> = stmt
-            .query_map(params, |row| {
-                let mut row_fields: Vec<Value> = Vec::new();
-                let mut ind = 0;
-                // turbofish needed to ensure proper typing
-                while let Ok(field) = row.get::<usize, RsqValue>(ind) {
-                    row_fields.push(field.into());
-                    ind += 1;
-                }
-                Ok(row_fields)
-            })?
-            .filter_map(|res| res.ok())
-            .collect();
-        let query = stmt.expanded_sql();
-        Ok(Table {
-            rows,
-            columns,
-            query,
-        })
-    }
-
-    /// Simple wrapper over Rusqlite's Statement.insert(params) function
-    /// which should be only used for the sake of a single insertion
-    /// An example insert statement is as follows:
-    ///
-    /// `INSERT INTO table (col1, col2, col3) VALUES (val1, val2, val3);`
-    pub fn insert<T: Params>(&self, query: &str, params: T) -> Result<i64, Box<dyn Error>> {
-        let mut stmt = self.connection.prepare(query)?;
-        Ok(stmt.insert(params)?)
-    }
-
-    /// Simple wrapper over Rusqlite's Statement.execute(params) function
-    /// which should be only used for the sake of deletion.
-    /// An example delete statement is as follows:
-    ///
-    /// `DELETE FROM table WHERE col_name = value ORDER BY col LIMIT num;`
-    pub fn delete<T: Params>(&self, query: &str, params: T) -> Result<usize, Box<dyn Error>> {
-        let mut stmt = self.connection.prepare(query)?;
-        Ok(stmt.execute(params)?)
-    }
-
-    /// Simple wrapper over Rusqlite's Statement.execute(params) function
-    /// which should be only used for the sake of modifying a cell.
-    /// An example modification statement is as follows:
-    ///
-    /// `UPDATE table SET col_name = value WHERE pk_name = pk_val;`
-    pub fn modify<T: Params>(&self, query: &str, params: T) -> Result<(), Box<dyn Error>> {
-        let mut stmt = self.connection.prepare(query)?;
-        stmt.execute(params)?;
-        Ok(())
-    }
-
-    pub fn get_columns(&self, table: &str) -> Result<Vec<String>, Box<dyn Error>> {
-        let stmt = self
-            .connection
-            .prepare(format!("SELECT * FROM {};", table).as_str())?;
-        Ok(stmt
-            .column_names()
-            .iter()
-            .map(|col| col.to_string())
-            .collect())
-    }
-
-    pub fn get_column_info(&self, table: &str) -> Result<Vec<ColumnInfo>, Box<dyn Error>> {
-        let mut stmt = self
-            .connection
-            .prepare(format!("SELECT * FROM pragma_table_info('{}');", table).as_str())?;
-        let column_info = stmt
-            .query_map([], |row| {
-                Ok(ColumnInfo {
-                    name: row.get("name")?,
-                    data_type: ValueType::try_from(row.get::<&str, String>("type")?)
-                        .expect("Retrieved impossible Data Type"),
-                    is_not_null: row.get("notnull")?,
-                    default: row.get::<&str, RsqValue>("dflt_value")?.into(),
-                    is_primary_key: row.get::<&str, usize>("pk")? != 0,
-                    cid: row.get("cid")?,
-                })
-            })?
-            .filter_map(|res| res.ok())
-            .collect();
-        Ok(column_info)
-    }
-}
+use std::{
+    env,
+    fs::{self, File, OpenOptions},
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+    sync::{Mutex, MutexGuard, PoisonError},
+};
+
+use crate::{
+    config::{MAX_QUERY_LOG_SIZE, ORDER_COLUMN_NAMES, QUERY_LOG_PATH},
+    error::AppError,
+    value::{Value, ValueType},
+};
+use rusqlite::{
+    Connection as RsqConnection, DatabaseName, ErrorCode, OpenFlags, Params,
+    types::Value as RsqValue,
+};
+
+/// A table of Values, generated through a query to some database
+#[derive(Debug, Clone)]
+pub struct Table {
+    pub(crate) rows: Vec<Vec<Value>>,
+    pub(crate) columns: Vec<String>,
+    /// SQL which produced the table with its parameters expanded, or None
+    /// if the table wasn't queried from a database
+    pub(crate) query: Option<String>,
+}
+
+impl Table {
+    /// Creates a table directly from its columns and rows, without a query.
+    /// Panics if any row doesn't have exactly one value per column, which
+    /// [`Table::try_from`] returns as an error instead
+    pub fn new(columns: Vec<String>, rows: Vec<Vec<Value>>) -> Self {
+        Self::try_from((columns, rows)).expect("Every row should have a value for each column")
+    }
+
+    /// Begins building a table column by column and row by row
+    pub fn builder() -> TableBuilder {
+        TableBuilder::default()
+    }
+
+    pub fn column_index(&self, name: &str) -> Option<usize> {
+        self.columns
+            .iter()
+            .position(|column| column.as_str() == name)
+    }
+
+    /// Function to get the value stored at the column with the
+    /// specified name within the row at the passed index
+    pub fn row_get(&self, row: usize, name: &str) -> Option<&Value> {
+        let col = self.column_index(name)?;
+        Some(&self.rows[row][col])
+    }
+
+    /// Formats the table as RFC 4180 CSV, with the column names as the
+    /// header record and each field formatted by [`Value::to_csv_field`]
+    pub fn to_csv(&self) -> String {
+        let header = self
+            .columns
+            .iter()
+            .map(|column| Value::Text(column.clone()).to_csv_field());
+        std::iter::once(header.collect::<Vec<String>>().join(","))
+            .chain(self.rows.iter().map(|row| {
+                row.iter()
+                    .map(Value::to_csv_field)
+                    .collect::<Vec<String>>()
+                    .join(",")
+            }))
+            .map(|record| record + "\r\n")
+            .collect()
+    }
+}
+
+impl From<Table> for Vec<Vec<Value>> {
+    fn from(table: Table) -> Self {
+        table.rows
+    }
+}
+
+impl TryFrom<(Vec<String>, Vec<Vec<Value>>)> for Table {
+    type Error = AppError;
+
+    /// Creates a table from its columns and rows, failing if any row doesn't
+    /// have exactly one value per column
+    fn try_from((columns, rows): (Vec<String>, Vec<Vec<Value>>)) -> Result<Self, Self::Error> {
+        if let Some(row) = rows.iter().find(|row| row.len() != columns.len()) {
+            return Err(AppError::RowLengthMismatch {
+                expected: columns.len(),
+                found: row.len(),
+            });
+        }
+        Ok(Self {
+            rows,
+            columns,
+            query: None,
+        })
+    }
+}
+
+/// Builder for a [`Table`] which isn't retrieved from a database
+#[derive(Debug, Default)]
+pub struct TableBuilder {
+    columns: Vec<String>,
+    rows: Vec<Vec<Value>>,
+}
+
+impl TableBuilder {
+    /// Appends a column with the passed name
+    pub fn column(mut self, name: &str) -> Self {
+        self.columns.push(name.to_owned());
+        self
+    }
+
+    /// Appends a row, which should have a value for each column
+    pub fn row(mut self, values: Vec<Value>) -> Self {
+        self.rows.push(values);
+        self
+    }
+
+    /// Builds the table, failing if any row doesn't have exactly one value
+    /// per column
+    pub fn build(self) -> Result<Table, AppError> {
+        Table::try_from((self.columns, self.rows))
+    }
+}
+
+/// Column of another table which a foreign key column references
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ForeignKeyRef {
+    /// Name of the referenced table, qualified by its schema if attached
+    pub table: String,
+    pub column: String,
+}
+
+#[derive(Debug)]
+pub struct ColumnInfo {
+    pub(crate) name: String,
+    pub(crate) data_type: ValueType,
+    pub(crate) is_not_null: bool,
+    #[allow(dead_code)]
+    pub(crate) default: Value,
+    pub(crate) is_primary_key: bool,
+    pub(crate) is_unique: bool,
+    #[allow(dead_code)]
+    pub(crate) cid: usize,
+    /// Column this one references as a foreign key, if it does
+    pub(crate) foreign_key: Option<ForeignKeyRef>,
+}
+
+impl std::fmt::Display for ColumnInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let info_vec: Vec<&str> = [
+            self.is_primary_key.then_some("PK"),
+            self.is_not_null.then_some("Required"),
+            self.is_unique.then_some("UNIQUE"),
+            self.foreign_key.is_some().then_some("FK"),
+            Some(match self.data_type {
+                ValueType::Null => "Null",
+                ValueType::Integer => "Int",
+                ValueType::Real => "Real",
+                ValueType::Text => "Text",
+                ValueType::Blob => "Blob",
+            }),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        write!(f, "{}", info_vec.join(", "))
+    }
+}
+
+/// Escapes the wildcards of LIKE within the text, along with the escape
+/// character itself, so the text is matched literally by a pattern
+/// followed by `ESCAPE '\'`
+pub fn escape_like(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+/// Quotes the identifier so that names which are keywords or contain
+/// spaces and quotes can be placed directly within a query
+pub fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+/// Quotes the table name, quoting its schema separately when it's
+/// qualified by the schema of an attached database
+pub fn quote_table(table: &str) -> String {
+    let (schema, name) = split_schema(table);
+    format!("{}.{}", quote_identifier(schema), quote_identifier(name))
+}
+
+/// Splits a table name qualified by the schema of an attached database,
+/// such as `aux.table`, into its schema and table, where unqualified names
+/// belong to the `main` schema
+fn split_schema(table: &str) -> (&str, &str) {
+    table.split_once('.').unwrap_or(("main", table))
+}
+
+/// A connection to the database updated and read by the app.
+/// Meant to be opened once and shared between components within an `Arc`,
+/// so access to the underlying connection is guarded by a `Mutex`
+pub struct Connection {
+    connection: Mutex<RsqConnection>,
+    query_log: Option<Mutex<QueryLog>>,
+}
+
+/// File which executed queries are written to, moved aside to a `.1` file
+/// once it grows past [`MAX_QUERY_LOG_SIZE`]
+struct QueryLog {
+    path: PathBuf,
+    writer: BufWriter<File>,
+}
+
+impl QueryLog {
+    fn open(path: &Path) -> Result<Self, AppError> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self {
+            path: path.to_owned(),
+            writer: BufWriter::new(file),
+        })
+    }
+
+    /// Writes the query to the log alongside the current time,
+    /// rotating the log first if it has grown too large
+    fn write(&mut self, query: &str) -> Result<(), AppError> {
+        if self.writer.get_ref().metadata()?.len() > MAX_QUERY_LOG_SIZE {
+            let mut rotated = self.path.clone().into_os_string();
+            rotated.push(".1");
+            fs::rename(&self.path, rotated)?;
+            *self = Self::open(&self.path)?;
+        }
+        writeln!(
+            self.writer,
+            "[{}] {}",
+            chrono::Utc::now().to_rfc3339(),
+            query
+        )?;
+        // flush so the size of the file stays accurate
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Builder for a [`Connection`] opened with something other than the
+/// default flags, which open the database for reading and writing
+#[derive(Debug, Default)]
+pub struct ConnectionBuilder {
+    path: PathBuf,
+    read_only: bool,
+    /// Either the shared or private cache flag, if one was chosen
+    cache: Option<OpenFlags>,
+    debug: bool,
+}
+
+impl ConnectionBuilder {
+    /// Sets the path of the database file to open
+    pub fn path(mut self, path: &Path) -> Self {
+        self.path = path.to_owned();
+        self
+    }
+
+    /// Sets a `file:` URI to open instead of a path, whose query parameters
+    /// such as `mode` or `cache` are understood by SQLite
+    #[allow(dead_code)]
+    pub fn uri(mut self, uri: &str) -> Self {
+        self.path = PathBuf::from(uri);
+        self
+    }
+
+    /// Fails any query that tries to write to the database
+    pub fn read_only(mut self) -> Self {
+        self.read_only = true;
+        self
+    }
+
+    /// Shares the cache with other connections to the same database
+    #[allow(dead_code)]
+    pub fn shared_cache(mut self) -> Self {
+        self.cache = Some(OpenFlags::SQLITE_OPEN_SHARED_CACHE);
+        self
+    }
+
+    /// Keeps the cache private to this connection, even if shared caches
+    /// were enabled for the whole process
+    #[allow(dead_code)]
+    pub fn private_cache(mut self) -> Self {
+        self.cache = Some(OpenFlags::SQLITE_OPEN_PRIVATE_CACHE);
+        self
+    }
+
+    /// Logs every executed query to [`QUERY_LOG_PATH`] if debug is true or
+    /// the `RUST_LOG` environment variable is set to `debug`
+    pub fn debug(mut self, debug: bool) -> Self {
+        self.debug = debug;
+        self
+    }
+
+    pub fn build(self) -> Result<Connection, AppError> {
+        let mode = if self.read_only {
+            OpenFlags::SQLITE_OPEN_READ_ONLY
+        } else {
+            OpenFlags::SQLITE_OPEN_READ_WRITE
+        };
+        let connection = RsqConnection::open_with_flags(
+            &self.path,
+            mode | self.cache.unwrap_or(OpenFlags::empty())
+                | OpenFlags::SQLITE_OPEN_URI
+                | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+        )?;
+        let mut connection = Connection {
+            connection: Mutex::new(connection),
+            query_log: None,
+        };
+        if self.debug || env::var("RUST_LOG").is_ok_and(|level| level == "debug") {
+            connection.enable_query_log(Path::new(QUERY_LOG_PATH))?;
+        }
+        Ok(connection)
+    }
+}
+
+impl Connection {
+    /// Opens a connection to the database at the passed path, logging every
+    /// executed query to [`QUERY_LOG_PATH`] if debug is true or the
+    /// `RUST_LOG` environment variable is set to `debug`
+    pub fn new(path: &Path, debug: bool) -> Result<Self, AppError> {
+        ConnectionBuilder::default().path(path).debug(debug).build()
+    }
+
+    /// Opens a connection to the database at the passed path, as with
+    /// [`Connection::new`], which fails any query that tries to write to it
+    pub fn new_read_only(path: &Path, debug: bool) -> Result<Self, AppError> {
+        ConnectionBuilder::default()
+            .path(path)
+            .read_only()
+            .debug(debug)
+            .build()
+    }
+
+    /// Begins writing every query executed through this connection to the
+    /// file at the passed path, appending to it if it already exists
+    pub fn enable_query_log(&mut self, path: &Path) -> Result<(), AppError> {
+        self.query_log = Some(Mutex::new(QueryLog::open(path)?));
+        Ok(())
+    }
+
+    /// Writes the query to the query log if it is enabled. Failing to log
+    /// isn't worth failing the query over, so errors are ignored
+    fn log_query(&self, query: &str) {
+        if let Some(log) = &self.query_log {
+            let _ = log
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .write(query);
+        }
+    }
+
+    /// Runs SQLite's integrity check over the database, returning true if
+    /// it found no problems. A database too corrupt for the check to even
+    /// run is treated as having failed it
+    pub fn check_integrity(&self) -> Result<bool, AppError> {
+        let result = self
+            .lock()
+            .query_row("PRAGMA integrity_check;", [], |row| row.get::<_, String>(0));
+        match result {
+            Ok(result) => Ok(result == "ok"),
+            Err(err) if err.sqlite_error_code() == Some(ErrorCode::DatabaseCorrupt) => Ok(false),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Opens a connection to a fresh database held entirely in memory,
+    /// allowing for use of a database without touching the one on disk
+    #[cfg(test)]
+    pub fn open_in_memory() -> Result<Self, AppError> {
+        let connection = RsqConnection::open_in_memory()?;
+        Ok(Self {
+            connection: Mutex::new(connection),
+            query_log: None,
+        })
+    }
+
+    /// Runs the passed batch of SQL statements to set up a schema within
+    /// the database, which is expected to be one from `open_in_memory`
+    #[cfg(test)]
+    pub fn create_test_table(&self, schema: &str) -> Result<(), AppError> {
+        self.lock().execute_batch(schema)?;
+        Ok(())
+    }
+
+    /// Locks the underlying Rusqlite connection for use by a single query.
+    /// A poisoned lock is recovered from, as the connection holds no state
+    /// that a panicking holder could have left partially updated
+    fn lock(&self) -> MutexGuard<'_, RsqConnection> {
+        self.connection
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Computes the passed query using this connection
+    ///
+    /// returns a Result containing the resulting rows of the table,
+    /// or an Error indicating the failure
+    pub fn query<T: Params>(&self, query: &str, params: T) -> Result<Table, AppError> {
+        self.log_query(query);
+        // generate a unique, index associated pair of vectors for
+        // the column names and the row data associated with those columns
+        let connection = self.lock();
+        let mut stmt = connection.prepare(query)?;
+        let columns: Vec<String> = stmt
+            .column_names()
+            .iter()
+            .map(|col| col.to_string())
+            .collect();
+        // map the query into a 2d array of returned values
+        let rows: Vec<Vec<Value>> = stmt
+            .query_map(params, |row| {
+                let mut row_fields: Vec<Value> = Vec::new();
+                let mut ind = 0;
+                // turbofish needed to ensure proper typing
+                while let Ok(field) = row.get::<usize, RsqValue>(ind) {
+                    row_fields.push(field.into());
+                    ind += 1;
+                }
+                Ok(row_fields)
+            })?
+            .filter_map(|res| res.ok())
+            .collect();
+        let query = stmt.expanded_sql();
+        Ok(Table {
+            rows,
+            columns,
+            query,
+        })
+    }
+
+    /// Returns the query with the passed params expanded into its positional
+    /// args, such as to show the query to be edited
+    pub fn expand_query(&self, query: &str, params: &[RsqValue]) -> Result<String, AppError> {
+        let connection = self.lock();
+        let mut stmt = connection.prepare(query)?;
+        for (ind, param) in params.iter().enumerate() {
+            stmt.raw_bind_parameter(ind + 1, param)?;
+        }
+        Ok(stmt.expanded_sql().unwrap_or_else(|| query.to_owned()))
+    }
+
+    /// Simple wrapper over Rusqlite's Statement.insert(params) function
+    /// which should be only used for the sake of a single insertion
+    /// An example insert statement is as follows:
+    ///
+    /// `INSERT INTO table (col1, col2, col3) VALUES (val1, val2, val3);`
+    pub fn insert<T: Params>(&self, query: &str, params: T) -> Result<i64, AppError> {
+        self.log_query(query);
+        let connection = self.lock();
+        let mut stmt = connection.prepare(query)?;
+        Ok(stmt.insert(params)?)
+    }
+
+    /// Simple wrapper over Rusqlite's Statement.execute(params) function
+    /// which should be only used for the sake of deletion.
+    /// An example delete statement is as follows:
+    ///
+    /// `DELETE FROM table WHERE col_name = value ORDER BY col LIMIT num;`
+    pub fn delete<T: Params>(&self, query: &str, params: T) -> Result<usize, AppError> {
+        self.log_query(query);
+        let connection = self.lock();
+        let mut stmt = connection.prepare(query)?;
+        Ok(stmt.execute(params)?)
+    }
+
+    /// Begins a batch of changes which are only saved to the database once
+    /// [`Connection::commit`] is called, or discarded by
+    /// [`Connection::rollback`]. Batches can't be nested
+    pub fn begin(&self) -> Result<(), AppError> {
+        if self.in_transaction() {
+            return Err(AppError::TransactionAlreadyOpen);
+        }
+        self.lock().execute_batch("BEGIN TRANSACTION;")?;
+        Ok(())
+    }
+
+    /// Saves the changes made since [`Connection::begin`]
+    pub fn commit(&self) -> Result<(), AppError> {
+        self.lock().execute_batch("COMMIT;")?;
+        Ok(())
+    }
+
+    /// Discards the changes made since [`Connection::begin`]
+    pub fn rollback(&self) -> Result<(), AppError> {
+        self.lock().execute_batch("ROLLBACK;")?;
+        Ok(())
+    }
+
+    /// Returns true if the database was opened without allowing writes
+    pub fn is_read_only(&self) -> bool {
+        self.lock().is_readonly(DatabaseName::Main).unwrap_or(false)
+    }
+
+    /// Returns true if changes are being held within an open transaction
+    /// rather than being saved as soon as they are made
+    pub fn in_transaction(&self) -> bool {
+        !self.lock().is_autocommit()
+    }
+
+    /// Begins a transaction which lasts until either
+    /// [`Connection::commit_transaction`] or [`Connection::rollback_transaction`]
+    /// is called. Savepoints are used so that these may be nested, such as
+    /// within a batch begun by [`Connection::begin`]
+    pub fn begin_transaction(&self) -> Result<(), AppError> {
+        self.lock().execute_batch("SAVEPOINT change;")?;
+        Ok(())
+    }
+
+    /// Commits the changes made since [`Connection::begin_transaction`], or
+    /// merges them into the enclosing transaction if there is one
+    pub fn commit_transaction(&self) -> Result<(), AppError> {
+        self.lock().execute_batch("RELEASE change;")?;
+        Ok(())
+    }
+
+    /// Discards the changes made since [`Connection::begin_transaction`]
+    pub fn rollback_transaction(&self) -> Result<(), AppError> {
+        self.lock()
+            .execute_batch("ROLLBACK TO change; RELEASE change;")?;
+        Ok(())
+    }
+
+    /// Simple wrapper over Rusqlite's Statement.execute(params) function
+    /// which should be only used for the sake of modifying a cell.
+    /// An example modification statement is as follows:
+    ///
+    /// `UPDATE table SET col_name = value WHERE pk_name = pk_val;`
+    pub fn modify<T: Params>(&self, query: &str, params: T) -> Result<(), AppError> {
+        self.log_query(query);
+        let connection = self.lock();
+        let mut stmt = connection.prepare(query)?;
+        stmt.execute(params)?;
+        Ok(())
+    }
+
+    pub fn get_columns(&self, table: &str) -> Result<Vec<String>, AppError> {
+        let connection = self.lock();
+        let stmt = connection.prepare(format!("SELECT * FROM {};", quote_table(table)).as_str())?;
+        Ok(stmt
+            .column_names()
+            .iter()
+            .map(|col| col.to_string())
+            .collect())
+    }
+
+    /// Finds the column of the passed table holding the display order of its
+    /// rows, being the first of [`ORDER_COLUMN_NAMES`] which it has. Tables
+    /// with none of them use their first INTEGER column which isn't part of
+    /// the primary key, if any
+    pub fn detect_order_column(&self, table: &str) -> Result<Option<String>, AppError> {
+        let (schema, table) = split_schema(table);
+        let connection = self.lock();
+        let mut stmt =
+            connection.prepare("SELECT name, type, pk FROM pragma_table_info(?1, ?2);")?;
+        let columns = stmt
+            .query_map([table, schema], |row| {
+                Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+            })?
+            .collect::<Result<Vec<(String, String, i64)>, _>>()?;
+        let named = ORDER_COLUMN_NAMES.iter().find_map(|candidate| {
+            columns
+                .iter()
+                .find(|(name, _, _)| name.eq_ignore_ascii_case(candidate))
+                .map(|(name, _, _)| name.clone())
+        });
+        // otherwise fall back to the first integer column outside the primary key
+        Ok(named.or_else(|| {
+            columns
+                .iter()
+                .find(|(_, data_type, pk)| *pk == 0 && data_type.eq_ignore_ascii_case("INTEGER"))
+                .map(|(name, _, _)| name.clone())
+        }))
+    }
+
+    /// Determines whether the passed table has a rowid, which is true for
+    /// every table not declared `WITHOUT ROWID`
+    pub fn table_has_rowid(&self, table: &str) -> Result<bool, AppError> {
+        let (schema, table) = split_schema(table);
+        let connection = self.lock();
+        let (table_type, sql): (String, String) = connection.query_row(
+            &format!(
+                "SELECT type, sql FROM \"{}\".sqlite_master \
+                 WHERE type IN ('table', 'view') AND name = ?1;",
+                schema
+            ),
+            [table],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?;
+        // views never have a rowid of their own
+        Ok(table_type == "table" && !sql.to_uppercase().contains("WITHOUT ROWID"))
+    }
+
+    /// Lists the names of the tables within the database, excluding the
+    /// internal tables SQLite creates such as `sqlite_sequence`
+    #[allow(dead_code)]
+    pub fn get_table_list(&self) -> Result<Vec<String>, AppError> {
+        self.get_schema_names("main", "table")
+    }
+
+    /// Lists the names of the views within the database
+    pub fn get_view_list(&self) -> Result<Vec<String>, AppError> {
+        self.get_schema_names("main", "view")
+    }
+
+    /// Lists the names of the tables within the database attached under the
+    /// passed schema name, which aren't qualified by the schema
+    pub fn get_attached_table_list(&self, schema_name: &str) -> Result<Vec<String>, AppError> {
+        self.get_schema_names(schema_name, "table")
+    }
+
+    /// Attaches the database at the passed path under the passed schema
+    /// name, after which its tables are queried as `<schema_name>.<table>`
+    pub fn attach(&self, path: &Path, schema_name: &str) -> Result<(), AppError> {
+        let path = path.to_string_lossy();
+        self.log_query(&format!("ATTACH DATABASE '{}' AS {};", path, schema_name));
+        self.lock()
+            .execute("ATTACH DATABASE ?1 AS ?2;", [path.as_ref(), schema_name])?;
+        Ok(())
+    }
+
+    /// Detaches the database attached under the passed schema name
+    #[allow(dead_code)]
+    pub fn detach(&self, schema_name: &str) -> Result<(), AppError> {
+        self.log_query(&format!("DETACH DATABASE {};", schema_name));
+        self.lock().execute("DETACH DATABASE ?1;", [schema_name])?;
+        Ok(())
+    }
+
+    /// Lists the names of the schema objects of the passed type, such as
+    /// `table` or `view`, within the passed schema which aren't internal
+    /// to SQLite
+    fn get_schema_names(&self, schema: &str, object_type: &str) -> Result<Vec<String>, AppError> {
+        let connection = self.lock();
+        let mut stmt = connection.prepare(&format!(
+            "SELECT name FROM \"{}\".sqlite_master \
+             WHERE type = ?1 AND name NOT LIKE 'sqlite_%' ORDER BY name;",
+            schema
+        ))?;
+        let names = stmt
+            .query_map([object_type], |row| row.get(0))?
+            .collect::<Result<Vec<String>, _>>()?;
+        Ok(names)
+    }
+
+    pub fn get_column_info(&self, table: &str) -> Result<Vec<ColumnInfo>, AppError> {
+        let (schema, table) = split_schema(table);
+        let connection = self.lock();
+        let mut stmt = connection.prepare(
+            format!(
+                "SELECT * FROM pragma_table_info('{}', '{}');",
+                table, schema
+            )
+            .as_str(),
+        )?;
+        let mut column_info = stmt
+            .query_map([], |row| {
+                Ok(ColumnInfo {
+                    name: row.get("name")?,
+                    data_type: ValueType::try_from(row.get::<&str, String>("type")?)
+                        .expect("Retrieved impossible Data Type"),
+                    is_not_null: row.get("notnull")?,
+                    default: row.get::<&str, RsqValue>("dflt_value")?.into(),
+                    is_primary_key: row.get::<&str, usize>("pk")? != 0,
+                    is_unique: false,
+                    cid: row.get("cid")?,
+                    foreign_key: None,
+                })
+            })?
+            .filter_map(|res| res.ok())
+            .collect::<Vec<ColumnInfo>>();
+        drop(stmt);
+
+        // mark the columns which are alone within a unique index, as multi-column
+        // indices only require the combination of values to be unique
+        let mut stmt = connection.prepare(
+            format!(
+                "SELECT info.name FROM pragma_index_list('{1}', '{0}') AS list, \
+                 pragma_index_info(list.name, '{0}') AS info \
+                 WHERE list.\"unique\" = 1 AND list.origin != 'pk' \
+                 GROUP BY list.name HAVING COUNT(*) = 1;",
+                schema, table
+            )
+            .as_str(),
+        )?;
+        let unique_columns: Vec<String> = stmt
+            .query_map([], |row| row.get(0))?
+            .filter_map(|res| res.ok())
+            .collect();
+        for info in column_info.iter_mut() {
+            info.is_unique = unique_columns.contains(&info.name);
+        }
+        drop(stmt);
+
+        // mark the columns referencing another table, where a missing "to"
+        // column means the key references that table's primary key
+        let mut stmt = connection.prepare(
+            format!(
+                "SELECT fk.\"from\", fk.\"table\", \
+                 COALESCE(fk.\"to\", (SELECT name FROM pragma_table_info(fk.\"table\", '{0}') \
+                 WHERE pk = 1)) \
+                 FROM pragma_foreign_key_list('{1}', '{0}') AS fk;",
+                schema, table
+            )
+            .as_str(),
+        )?;
+        let foreign_keys: Vec<(String, String, Option<String>)> = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
+            .filter_map(|res| res.ok())
+            .collect();
+        for (from, referenced, to) in foreign_keys {
+            let Some(to) = to else {
+                continue;
+            };
+            if let Some(info) = column_info.iter_mut().find(|info| info.name == from) {
+                info.foreign_key = Some(ForeignKeyRef {
+                    // attached tables are only found when qualified by their schema
+                    table: if schema == "main" {
+                        referenced
+                    } else {
+                        format!("{}.{}", schema, referenced)
+                    },
+                    column: to,
+                });
+            }
+        }
+        Ok(column_info)
+    }
+
+    /// Queries the row of the table with the passed rowid, with the rowid
+    /// itself as the first column
+    pub fn query_by_rowid(&self, table: &str, rowid: i64) -> Result<Table, AppError> {
+        self.query(
+            &format!(
+                "SELECT rowid, * FROM {} WHERE rowid = ?1;",
+                quote_table(table)
+            ),
+            [rowid],
+        )
+    }
+
+    /// Returns true if the query only reads from the database. Fails if the
+    /// query isn't a single valid statement
+    pub fn is_read_only_query(&self, query: &str) -> Result<bool, AppError> {
+        let connection = self.lock();
+        Ok(connection.prepare(query)?.readonly())
+    }
+
+    /// Counts every row of the table, independent of any filtered query
+    pub fn get_row_count(&self, table: &str) -> Result<usize, AppError> {
+        let query = format!("SELECT COUNT(*) FROM {};", quote_table(table));
+        self.log_query(&query);
+        let connection = self.lock();
+        Ok(connection.query_row(&query, [], |row| row.get(0))?)
+    }
+
+    /// Counts the rows of the table whose column holds the passed value
+    pub fn count_matching(
+        &self,
+        table: &str,
+        column: &str,
+        value: RsqValue,
+    ) -> Result<usize, AppError> {
+        let query = format!(
+            "SELECT COUNT(*) FROM {} WHERE {} = ?1;",
+            quote_table(table),
+            quote_identifier(column)
+        );
+        self.log_query(&query);
+        let connection = self.lock();
+        Ok(connection.query_row(&query, [value], |row| row.get(0))?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builder_builds_the_same_table_as_new() {
+        let built = Table::builder()
+            .column("id")
+            .column("name")
+            .row(vec![Value::Integer(1), Value::Text("ann".to_string())])
+            .build()
+            .unwrap();
+        let table = Table::new(
+            vec!["id".to_string(), "name".to_string()],
+            vec![vec![Value::Integer(1), Value::Text("ann".to_string())]],
+        );
+        assert_eq!(built.columns, table.columns);
+        assert_eq!(built.rows, table.rows);
+        assert_eq!(
+            built.row_get(0, "name"),
+            Some(&Value::Text("ann".to_string()))
+        );
+        assert_eq!(built.row_get(0, "age"), None);
+    }
+
+    #[test]
+    fn builder_refuses_rows_of_the_wrong_length() {
+        let built = Table::builder()
+            .column("id")
+            .column("name")
+            .row(vec![Value::Integer(1)])
+            .build();
+        assert!(matches!(
+            built,
+            Err(AppError::RowLengthMismatch {
+                expected: 2,
+                found: 1
+            })
+        ));
+    }
+
+    #[test]
+    fn counts_rows_of_tables_with_awkward_names() {
+        let connection = Connection::open_in_memory().unwrap();
+        connection
+            .create_test_table(
+                "CREATE TABLE \"my \"\"table\"\"\" (id INTEGER PRIMARY KEY, \"order\" INTEGER);
+                INSERT INTO \"my \"\"table\"\"\" (\"order\") VALUES (1), (2);",
+            )
+            .unwrap();
+        let table = "my \"table\"";
+        assert_eq!(connection.get_row_count(table).unwrap(), 2);
+        assert_eq!(
+            connection
+                .count_matching(table, "order", RsqValue::Integer(2))
+                .unwrap(),
+            1
+        );
+        assert_eq!(connection.query_by_rowid(table, 1).unwrap().rows.len(), 1);
+        assert_eq!(connection.get_columns(table).unwrap(), ["id", "order"]);
+    }
+
+    #[test]
+    fn read_only_connection_refuses_writes() {
+        let path = env::temp_dir().join(format!("cli-read-only-{}.db", std::process::id()));
+        // connections never create the database, so start from an empty file
+        std::fs::File::create(&path).unwrap();
+        let writable = Connection::new(&path, false).unwrap();
+        writable
+            .modify("CREATE TABLE IF NOT EXISTS t (id INTEGER);", [])
+            .unwrap();
+        assert!(!writable.is_read_only());
+
+        let read_only = Connection::new_read_only(&path, false).unwrap();
+        assert!(read_only.is_read_only());
+        assert!(read_only.modify("INSERT INTO t VALUES (1);", []).is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn builder_opens_uris_with_either_cache() {
+        let path = env::temp_dir().join(format!("cli-uri-{}.db", std::process::id()));
+        std::fs::File::create(&path).unwrap();
+        let uri = format!("file:{}", path.display());
+        let shared = ConnectionBuilder::default()
+            .uri(&uri)
+            .shared_cache()
+            .build()
+            .unwrap();
+        shared.modify("CREATE TABLE t (id INTEGER);", []).unwrap();
+        let private = ConnectionBuilder::default()
+            .uri(&format!("{}?mode=ro", uri))
+            .private_cache()
+            .build()
+            .unwrap();
+        assert!(private.is_read_only());
+        assert_eq!(private.get_row_count("t").unwrap(), 0);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
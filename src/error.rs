@@ -0,0 +1,72 @@
+#[cfg(feature = "backtrace")]
+use std::backtrace::Backtrace;
+use std::num::{ParseFloatError, ParseIntError};
+
+use thiserror::Error;
+
+use crate::{action::Action, value::ValueType};
+
+/// Errors which may occur anywhere within the app, allowing callers to
+/// match on the kind of error instead of only displaying it
+#[derive(Debug, Error)]
+pub enum AppError {
+    #[error("Database error: {0}")]
+    DatabaseError(#[from] rusqlite::Error),
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+    #[error("Failed to parse value: {0}")]
+    ParseError(String),
+    #[error("Unhandled action `{action:?}` in component `{component}`")]
+    UnhandledAction {
+        action: Action,
+        component: &'static str,
+        /// Boxed as thiserror only supports bare backtraces on nightly
+        #[cfg(feature = "backtrace")]
+        backtrace: Box<Backtrace>,
+    },
+    #[error("Trying to get ValueType from invalid string: {0}")]
+    InvalidColumnType(String),
+    #[error("No order column detected")]
+    MissingOrderColumn,
+    #[error("A transaction is already open")]
+    TransactionAlreadyOpen,
+    #[error("Row has {found} values but the table has {expected} columns")]
+    RowLengthMismatch { expected: usize, found: usize },
+    #[error(transparent)]
+    ParseColumnError(#[from] ParseColumnError),
+}
+
+/// Error from parsing text entered by the user as the type of a column,
+/// describing the text and the type it was expected to be
+#[derive(Debug, Error)]
+#[error("Cannot parse '{input}' as {column_type}: {source}")]
+pub struct ParseColumnError {
+    pub column_type: ValueType,
+    pub input: String,
+    pub source: Box<dyn std::error::Error + Send + Sync>,
+}
+
+impl AppError {
+    /// Creates the error for an action the named component can't handle,
+    /// capturing a backtrace if the `backtrace` feature is enabled
+    pub fn unhandled_action(action: Action, component: &'static str) -> Self {
+        AppError::UnhandledAction {
+            action,
+            component,
+            #[cfg(feature = "backtrace")]
+            backtrace: Box::new(Backtrace::capture()),
+        }
+    }
+}
+
+impl From<ParseIntError> for AppError {
+    fn from(err: ParseIntError) -> Self {
+        AppError::ParseError(err.to_string())
+    }
+}
+
+impl From<ParseFloatError> for AppError {
+    fn from(err: ParseFloatError) -> Self {
+        AppError::ParseError(err.to_string())
+    }
+}
@@ -0,0 +1,41 @@
+use std::{fs, io, path::Path};
+
+use crate::connection::Table;
+
+/// Writes the table as CSV, formatted by [`Table::to_csv`], to the file at
+/// the passed path, replacing it if it already exists
+pub fn write_csv(path: &Path, table: &Table) -> io::Result<()> {
+    fs::write(path, table.to_csv())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+    use crate::{import::import_csv, value::Value};
+
+    #[test]
+    fn written_blobs_are_read_back_as_base64() {
+        let path = env::temp_dir().join(format!("cli-export-{}.csv", std::process::id()));
+        let table = Table::builder()
+            .column("name")
+            .column("data")
+            .row(vec![
+                Value::Text("a, \"b\"".to_string()),
+                Value::Blob(vec![0, 255]),
+            ])
+            .build()
+            .unwrap();
+        write_csv(&path, &table).unwrap();
+        let records = import_csv(path.to_str().unwrap()).unwrap();
+        assert_eq!(
+            records,
+            [
+                vec!["name".to_string(), "data".to_string()],
+                vec!["a, \"b\"".to_string(), "base64:AP8=".to_string()],
+            ]
+        );
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,318 @@
+use std::iter;
+
+use rusqlite::{params_from_iter, types::Value as RsqValue};
+
+use crate::{
+    connection::{Connection, quote_identifier, quote_table},
+    error::AppError,
+    value::Value,
+};
+
+/// Values of a row at the time it was changed, which are used to find the
+/// row again when reversing the change
+#[derive(Debug, Clone, PartialEq)]
+pub struct RowSnapshot {
+    /// Each column of the row alongside its value
+    pub values: Vec<(String, Value)>,
+    /// Names of the primary key columns. Rows of tables without a primary
+    /// key are identified by all of their columns instead
+    pub primary_key: Vec<String>,
+}
+
+impl RowSnapshot {
+    /// Returns a copy of the snapshot with the column set to the passed value
+    fn with_value(&self, column: &str, value: &Value) -> Self {
+        let mut snapshot = self.clone();
+        for (name, old) in snapshot.values.iter_mut() {
+            if name == column {
+                *old = value.clone();
+            }
+        }
+        snapshot
+    }
+
+    /// Returns the value the snapshot holds for the passed column
+    fn value(&self, column: &str) -> Option<&Value> {
+        self.values
+            .iter()
+            .find(|(name, _)| name == column)
+            .map(|(_, value)| value)
+    }
+
+    /// Creates the condition matching the row within the passed table, in
+    /// the form of "COL_NAME IS ?IND AND ...", alongside the values to bind
+    /// to its positional args, which are numbered after start_offset
+    fn condition(&self, table: &str, start_offset: usize) -> (String, Vec<RsqValue>) {
+        let (pos, params): (Vec<String>, Vec<RsqValue>) = self
+            .values
+            .iter()
+            .filter(|(name, _)| self.primary_key.is_empty() || self.primary_key.contains(name))
+            .enumerate()
+            .map(|(ind, (name, value))| {
+                // IS so that NULLs compare equal when matching every column
+                (
+                    format!("{} IS ?{}", quote_identifier(name), ind + start_offset + 1),
+                    value.into(),
+                )
+            })
+            .unzip();
+        let pos = pos.join(" AND ");
+        if self.primary_key.is_empty() {
+            // several rows may share every value, so only use the first of them.
+            // tables without a primary key always have a rowid
+            (
+                format!(
+                    "rowid IN (SELECT rowid FROM {} WHERE {} LIMIT 1)",
+                    quote_table(table),
+                    pos
+                ),
+                params,
+            )
+        } else {
+            (pos, params)
+        }
+    }
+}
+
+/// A change made to a table of the database which can be reversed
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change {
+    /// Sets the column of the row from its value in the snapshot to new
+    Modify {
+        table: String,
+        row: RowSnapshot,
+        column: String,
+        new: Value,
+    },
+    Insert {
+        table: String,
+        rows: Vec<RowSnapshot>,
+    },
+    Delete {
+        table: String,
+        rows: Vec<RowSnapshot>,
+    },
+}
+
+impl Change {
+    /// Name of the table the change was made to
+    pub fn table(&self) -> &str {
+        match self {
+            Self::Modify { table, .. }
+            | Self::Insert { table, .. }
+            | Self::Delete { table, .. } => table,
+        }
+    }
+
+    /// Returns the change which reverses this one
+    pub fn inverse(&self) -> Self {
+        match self {
+            Self::Modify {
+                table,
+                row,
+                column,
+                new,
+            } => Self::Modify {
+                table: table.clone(),
+                row: row.with_value(column, new),
+                column: column.clone(),
+                new: row.value(column).cloned().unwrap_or(Value::Null),
+            },
+            Self::Insert { table, rows } => Self::Delete {
+                table: table.clone(),
+                rows: rows.clone(),
+            },
+            Self::Delete { table, rows } => Self::Insert {
+                table: table.clone(),
+                rows: rows.clone(),
+            },
+        }
+    }
+
+    /// Makes the change to the database within a single transaction
+    pub fn apply(&self, connection: &Connection) -> Result<(), AppError> {
+        connection.begin_transaction()?;
+        if let Err(err) = self.execute(connection) {
+            // don't leave the change partly made
+            connection.rollback_transaction()?;
+            return Err(err);
+        }
+        connection.commit_transaction()
+    }
+
+    fn execute(&self, connection: &Connection) -> Result<(), AppError> {
+        match self {
+            Self::Modify {
+                table,
+                row,
+                column,
+                new,
+            } => {
+                let (pos, params) = row.condition(table, 1);
+                // UPDATE table SET col_name = value WHERE pk_name IS pk_val;
+                let query = format!(
+                    "UPDATE {} SET {} = ?1 WHERE {};",
+                    quote_table(table),
+                    quote_identifier(column),
+                    pos
+                );
+                connection.modify(
+                    &query,
+                    params_from_iter(iter::once(new.into()).chain(params)),
+                )
+            }
+            Self::Insert { table, rows } => {
+                for row in rows {
+                    // every column is inserted, including an autoincremented
+                    // primary key, so the row returns under the same key
+                    let (columns, pos): (Vec<String>, Vec<String>) = row
+                        .values
+                        .iter()
+                        .enumerate()
+                        .map(|(ind, (name, _))| (quote_identifier(name), format!("?{}", ind + 1)))
+                        .unzip();
+                    let query = format!(
+                        "INSERT INTO {} ({}) VALUES ({});",
+                        quote_table(table),
+                        columns.join(", "),
+                        pos.join(", ")
+                    );
+                    let params = row.values.iter().map(|(_, value)| RsqValue::from(value));
+                    connection.insert(&query, params_from_iter(params))?;
+                }
+                Ok(())
+            }
+            Self::Delete { table, rows } => {
+                for row in rows {
+                    let (pos, params) = row.condition(table, 0);
+                    let query = format!("DELETE FROM {} WHERE {};", quote_table(table), pos);
+                    connection.delete(&query, params_from_iter(params))?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Changes which can be undone, alongside the undone changes which can be
+/// redone, both holding the most recent change last
+#[derive(Debug)]
+pub struct UndoStack {
+    undo: Vec<Change>,
+    redo: Vec<Change>,
+    /// Number of changes kept for undoing, past which the oldest is dropped
+    max_depth: usize,
+}
+
+impl UndoStack {
+    pub fn new(max_depth: usize) -> Self {
+        Self {
+            undo: Vec::new(),
+            redo: Vec::new(),
+            max_depth,
+        }
+    }
+
+    /// Changes how many changes are kept, dropping the oldest ones if there
+    /// are now too many
+    pub fn set_max_depth(&mut self, max_depth: usize) {
+        self.max_depth = max_depth;
+        for changes in [&mut self.undo, &mut self.redo] {
+            let excess = changes.len().saturating_sub(max_depth);
+            changes.drain(..excess);
+        }
+    }
+
+    /// Forgets every change, such as when they were discarded from the
+    /// database without being undone
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+
+    /// Records a change which was just made, after which the undone
+    /// changes can no longer be redone
+    pub fn push(&mut self, change: Change) {
+        self.redo.clear();
+        self.undo.push(change);
+        if self.undo.len() > self.max_depth {
+            self.undo.remove(0);
+        }
+    }
+
+    /// Reverses the most recent change if it was made to the passed table.
+    /// Returns false if there was no such change to undo
+    pub fn undo(&mut self, connection: &Connection, table: &str) -> Result<bool, AppError> {
+        let Some(change) = self.undo.pop_if(|change| change.table() == table) else {
+            return Ok(false);
+        };
+        if let Err(err) = change.inverse().apply(connection) {
+            self.undo.push(change);
+            return Err(err);
+        }
+        self.redo.push(change);
+        Ok(true)
+    }
+
+    /// Makes the most recently undone change again if it was made to the
+    /// passed table. Returns false if there was no such change to redo
+    pub fn redo(&mut self, connection: &Connection, table: &str) -> Result<bool, AppError> {
+        let Some(change) = self.redo.pop_if(|change| change.table() == table) else {
+            return Ok(false);
+        };
+        if let Err(err) = change.apply(connection) {
+            self.redo.push(change);
+            return Err(err);
+        }
+        self.undo.push(change);
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn changes_apply_to_tables_with_awkward_names() {
+        let connection = Connection::open_in_memory().unwrap();
+        connection
+            .create_test_table(
+                "CREATE TABLE \"my table\" (\"group\" TEXT, \"order\" INTEGER);
+                INSERT INTO \"my table\" VALUES ('a', 1);",
+            )
+            .unwrap();
+        let row = RowSnapshot {
+            values: vec![
+                ("group".to_string(), Value::Text("a".to_string())),
+                ("order".to_string(), Value::Integer(1)),
+            ],
+            primary_key: vec![],
+        };
+        let change = Change::Modify {
+            table: "my table".to_string(),
+            row: row.clone(),
+            column: "order".to_string(),
+            new: Value::Integer(2),
+        };
+        let orders = |connection: &Connection| {
+            connection
+                .query("SELECT \"order\" FROM \"my table\";", [])
+                .unwrap()
+                .rows
+        };
+        change.apply(&connection).unwrap();
+        assert_eq!(orders(&connection), [vec![Value::Integer(2)]]);
+        change.inverse().apply(&connection).unwrap();
+        assert_eq!(orders(&connection), [vec![Value::Integer(1)]]);
+
+        let delete = Change::Delete {
+            table: "my table".to_string(),
+            rows: vec![row],
+        };
+        delete.apply(&connection).unwrap();
+        assert!(orders(&connection).is_empty());
+        delete.inverse().apply(&connection).unwrap();
+        assert_eq!(orders(&connection), [vec![Value::Integer(1)]]);
+    }
+}
@@ -0,0 +1,66 @@
+use std::{fs, mem};
+
+use crate::error::AppError;
+
+/// Reads the CSV file at the passed path into its records, each holding
+/// the fields of one line
+pub fn import_csv(path: &str) -> Result<Vec<Vec<String>>, AppError> {
+    let contents = fs::read_to_string(path)?;
+    parse_csv(&contents)
+}
+
+/// Splits CSV text into its records following RFC 4180. Quoted fields may
+/// hold commas, line breaks, and quotes doubled to escape them. Lines may
+/// end in either CRLF or LF, and blank lines are skipped
+pub fn parse_csv(contents: &str) -> Result<Vec<Vec<String>>, AppError> {
+    // files saved by spreadsheet programs often begin with a byte order mark
+    let contents = contents.strip_prefix('\u{feff}').unwrap_or(contents);
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = contents.chars().peekable();
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                // a doubled quote is a quote within the field
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                c => field.push(c),
+            }
+            continue;
+        }
+        match c {
+            '"' if field.is_empty() => in_quotes = true,
+            ',' => record.push(mem::take(&mut field)),
+            // the line ends at the LF following it
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                record.push(mem::take(&mut field));
+                push_record(&mut records, mem::take(&mut record));
+            }
+            c => field.push(c),
+        }
+    }
+    if in_quotes {
+        return Err(AppError::ParseError(
+            "CSV file ends within a quoted field".to_string(),
+        ));
+    }
+    // the last line may not end in a line break
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        push_record(&mut records, record);
+    }
+    Ok(records)
+}
+
+/// Adds the record to the list unless it came from a blank line
+fn push_record(records: &mut Vec<Vec<String>>, record: Vec<String>) {
+    if record.len() > 1 || record.first().is_some_and(|field| !field.is_empty()) {
+        records.push(record);
+    }
+}
@@ -0,0 +1,17 @@
+//! The app's components and the database access behind them, kept apart
+//! from the terminal setup within main so they can be tested from outside
+//! the crate
+
+// define modules within this crate
+pub mod action;
+pub mod app;
+pub mod autofill;
+pub mod component;
+pub mod config;
+pub mod connection;
+pub mod error;
+pub mod export;
+pub mod history;
+pub mod import;
+pub mod value;
+pub mod wrap;
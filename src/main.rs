@@ -1,53 +1,108 @@
-// #![allow(dead_code, unused)]
-// define modules within this crate
-mod action;
-mod app;
-mod autofill;
-mod component;
-mod config;
-mod connection;
-mod value;
-mod wrap;
-
 use ratatui::crossterm::execute;
-use std::{error::Error, io};
+use std::{env, error::Error, io};
 // import external crates
 use ratatui::{
     Terminal,
-    crossterm::terminal::{
-        EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode,
+    crossterm::{
+        event::{
+            KeyboardEnhancementFlags, PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
+        },
+        terminal::{
+            EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode,
+            supports_keyboard_enhancement,
+        },
     },
     prelude::*,
+    widgets::{Block, Paragraph},
+};
+
+use cli::{
+    app::App,
+    config::{DEFAULT_APP_COLORS, parse_args, resolve_config},
 };
 
-use app::App;
-use config::change_working_directory_to_root;
+/// Draws a static screen showing the passed message alongside the versions
+/// of the app and SQLite, for use while the app is still being set up
+fn render_splash<B: Backend>(terminal: &mut Terminal<B>, message: &str) -> io::Result<()> {
+    let text = Text::from(vec![
+        Line::from(message),
+        Line::from(""),
+        Line::from(format!(
+            "v{} | SQLite {}",
+            env!("CARGO_PKG_VERSION"),
+            rusqlite::version()
+        ))
+        .fg(DEFAULT_APP_COLORS.header_fg),
+    ]);
+    terminal.draw(|frame| {
+        // center the text vertically within the screen
+        let [_, text_rect, _] = Layout::vertical([
+            Constraint::Fill(1),
+            Constraint::Length(text.height() as u16),
+            Constraint::Fill(1),
+        ])
+        .areas(frame.area());
+        frame.render_widget(Block::new().bg(DEFAULT_APP_COLORS.main_bg), frame.area());
+        frame.render_widget(
+            Paragraph::new(text.clone())
+                .centered()
+                .fg(DEFAULT_APP_COLORS.main_fg),
+            text_rect,
+        );
+    })?;
+    Ok(())
+}
 
 fn main() -> Result<(), Box<dyn Error>> {
     // DEBUG
     // env::set_var("RUST_BACKTRACE", "1");
 
-    // set the current working directory to be the root Website directory
-    change_working_directory_to_root();
+    // parse the command line options, only printing the help message if asked
+    let args: Vec<String> = env::args().skip(1).collect();
+    let Some(options) = parse_args(&args)? else {
+        return Ok(());
+    };
+
+    // determine the database to use and set the current working directory
+    // to be the root Website directory or the configured one
+    let config = resolve_config(options);
 
     // set up the terminal to run
     enable_raw_mode()?; // allow for full control over the I/O processing in the terminal
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen)?;
+    // have the terminal report modifiers held with keys such as Enter and
+    // Esc, so that ctrl+enter and ctrl+esc are told apart from them
+    let enhanced_keys = supports_keyboard_enhancement().unwrap_or(false);
+    if enhanced_keys {
+        execute!(
+            stdout,
+            PushKeyboardEnhancementFlags(KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES)
+        )?;
+    }
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
+    // show something while a large or slow database is being opened
+    render_splash(
+        &mut terminal,
+        &format!("Loading {}…", config.database_path.display()),
+    )?;
+
     // create and run the app, catching any errors it may propagate
-    let result = match App::new() {
+    let result = match App::new(&config) {
         Ok(mut app) => app.run(&mut terminal),
         Err(err) => Err(err),
     };
 
     // restore the terminal after the app finishes running
+    if enhanced_keys {
+        execute!(terminal.backend_mut(), PopKeyboardEnhancementFlags)?;
+    }
     disable_raw_mode()?;
     execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
     terminal.show_cursor()?;
 
     // return result of running the app
-    result
+    Ok(result?)
 }
@@ -1,167 +1,250 @@
-use std::error::Error;
-
-use ratatui::widgets::Cell;
-use rusqlite::types::Value as RsqValue;
-
-/// Mirror of Rusqlite's value type, but is, importantly, owned by this
-/// crate allowing for implementations of traits, functions, etc.
-#[derive(Debug, Clone, PartialEq, PartialOrd)]
-pub enum Value {
-    Null,
-    Integer(i64),
-    Real(f64),
-    Text(String),
-    Blob(Vec<u8>),
-}
-
-/// Fieldless version of [`Value`] for the sake of signaling
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub enum ValueType {
-    Null,
-    Integer,
-    Real,
-    Text,
-    Blob,
-}
-
-/// Error for unhandled actions
-#[derive(Debug, Clone)]
-pub struct InvalidValueTypeError {
-    origin: String,
-}
-
-impl InvalidValueTypeError {
-    pub fn new(origin: String) -> Self {
-        InvalidValueTypeError { origin }
-    }
-}
-
-impl Error for InvalidValueTypeError {}
-
-impl std::fmt::Display for InvalidValueTypeError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "Trying to get ValueType from invalid string: {}",
-            self.origin
-        )
-    }
-}
-
-impl TryFrom<String> for ValueType {
-    type Error = InvalidValueTypeError;
-
-    fn try_from(value: String) -> Result<Self, Self::Error> {
-        ValueType::try_from(value.as_str())
-    }
-}
-
-impl TryFrom<&str> for ValueType {
-    type Error = InvalidValueTypeError;
-
-    fn try_from(value: &str) -> Result<Self, Self::Error> {
-        match value {
-            "NULL" => Ok(ValueType::Null),
-            "INTEGER" => Ok(ValueType::Integer),
-            "REAL" => Ok(ValueType::Real),
-            "TEXT" => Ok(ValueType::Text),
-            "BLOB" => Ok(ValueType::Blob),
-            unknown => Err(InvalidValueTypeError::new(unknown.to_string())),
-        }
-    }
-}
-
-impl Value {
-    pub fn parse_column(data_type: &ValueType, text: &str) -> Result<Value, Box<dyn Error>> {
-        match data_type {
-            ValueType::Null => Ok(Value::Null),
-            ValueType::Integer => Ok(Value::Integer(text.parse()?)),
-            ValueType::Real => Ok(Value::Real(text.parse()?)),
-            ValueType::Text => Ok(Value::Text(text.to_string())),
-            ValueType::Blob => Ok(Value::Blob(text.bytes().collect())),
-        }
-    }
-}
-
-impl std::fmt::Display for Value {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let data = match self {
-            // The value is a `NULL` value.
-            Self::Null => "NULL".to_string(),
-            // The value is a signed integer.
-            Self::Integer(int) => int.to_string(),
-            // The value is a floating point number.
-            Self::Real(real) => real.to_string(),
-            // The value is a text string.
-            Self::Text(text) => text.clone(),
-            // The value is a blob of data
-            Self::Blob(blob) => {
-                if blob.is_empty() {
-                    "Empty Blob".to_string()
-                } else {
-                    // map blob to a single string of bytes
-                    blob.iter().fold("Blob data:\t".to_string(), |cur, item| {
-                        cur + item.to_string().as_str()
-                    })
-                }
-            }
-        };
-        write!(f, "{}", data)
-    }
-}
-
-// Used for taking implementation ownership of the Rusqlite Value in
-// so that code can be added as needed
-impl From<RsqValue> for Value {
-    fn from(value: RsqValue) -> Self {
-        match value {
-            RsqValue::Null => Self::Null,
-            RsqValue::Integer(int) => Self::Integer(int),
-            RsqValue::Real(real) => Self::Real(real),
-            RsqValue::Text(text) => Self::Text(text),
-            RsqValue::Blob(blob) => Self::Blob(blob),
-        }
-    }
-}
-
-// Converts back from our implemented Value type to Rusqlite's one
-impl From<Value> for RsqValue {
-    fn from(value: Value) -> Self {
-        match value {
-            Value::Null => Self::Null,
-            Value::Integer(int) => Self::Integer(int),
-            Value::Real(real) => Self::Real(real),
-            Value::Text(text) => Self::Text(text),
-            Value::Blob(blob) => Self::Blob(blob),
-        }
-    }
-}
-
-// Converts back from our implemented Value type to Rusqlite's one
-impl From<&Value> for RsqValue {
-    fn from(value: &Value) -> Self {
-        match value {
-            Value::Null => Self::Null,
-            Value::Integer(int) => Self::Integer(*int),
-            Value::Real(real) => Self::Real(*real),
-            Value::Text(text) => Self::Text(text.clone()),
-            Value::Blob(blob) => Self::Blob(blob.clone()),
-        }
-    }
-}
-
-/// Consuming conversion from Value to Cell, required for simple creation of
-/// Ratatui Rows from Vec<Value>
-impl From<Value> for Cell<'_> {
-    fn from(value: Value) -> Self {
-        Self::from(&value)
-    }
-}
-
-/// Consuming conversion from Value to Cell, required for simple creation of
-/// Ratatui Rows from Vec<Value> without consuming within creation
-impl From<&Value> for Cell<'_> {
-    fn from(value: &Value) -> Self {
-        Self::new(value.to_string())
-    }
-}
+use std::num::ParseIntError;
+
+use base64::{Engine, engine::general_purpose::STANDARD as BASE64};
+use ratatui::widgets::Cell;
+use rusqlite::types::Value as RsqValue;
+
+use crate::error::{AppError, ParseColumnError};
+
+/// Mirror of Rusqlite's value type, but is, importantly, owned by this
+/// crate allowing for implementations of traits, functions, etc.
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub enum Value {
+    Null,
+    Integer(i64),
+    Real(f64),
+    Text(String),
+    Blob(Vec<u8>),
+}
+
+/// Function formatting a value for display only, such as showing the name
+/// of the status an integer represents
+pub type DisplayFormatFn = fn(&Value) -> String;
+
+/// Fieldless version of [`Value`] for the sake of signaling
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValueType {
+    Null,
+    Integer,
+    Real,
+    Text,
+    Blob,
+}
+
+impl TryFrom<String> for ValueType {
+    type Error = AppError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        ValueType::try_from(value.as_str())
+    }
+}
+
+impl TryFrom<&str> for ValueType {
+    type Error = AppError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "NULL" => Ok(ValueType::Null),
+            "INTEGER" => Ok(ValueType::Integer),
+            "REAL" => Ok(ValueType::Real),
+            "TEXT" => Ok(ValueType::Text),
+            "BLOB" => Ok(ValueType::Blob),
+            unknown => Err(AppError::InvalidColumnType(unknown.to_string())),
+        }
+    }
+}
+
+impl std::fmt::Display for ValueType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            ValueType::Null => "NULL",
+            ValueType::Integer => "INTEGER",
+            ValueType::Real => "REAL",
+            ValueType::Text => "TEXT",
+            ValueType::Blob => "BLOB",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl Value {
+    /// Parses an integer which may be written in hexadecimal, binary, or octal
+    /// using a `0x`, `0b`, or `0o` prefix, with an optional leading minus sign
+    fn parse_integer(text: &str) -> Result<i64, ParseIntError> {
+        let (negative, digits) = match text.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, text),
+        };
+        let radix = match digits.get(..2) {
+            Some("0x" | "0X") => 16,
+            Some("0b" | "0B") => 2,
+            Some("0o" | "0O") => 8,
+            _ => return text.parse(),
+        };
+        let rest = &digits[2..];
+        if rest.starts_with(['+', '-']) {
+            // don't allow a second sign after the prefix
+            return text.parse();
+        }
+        let value = i64::from_str_radix(rest, radix)?;
+        Ok(if negative { -value } else { value })
+    }
+
+    /// Formats the value as a single CSV field. Text is always quoted so that
+    /// it can't be mistaken for a blob, which is instead written unquoted as
+    /// base64 prefixed with `base64:`, and NULL is an empty field
+    pub fn to_csv_field(&self) -> String {
+        match self {
+            Self::Null => String::new(),
+            Self::Integer(int) => int.to_string(),
+            Self::Real(real) => real.to_string(),
+            Self::Text(text) => format!("\"{}\"", text.replace('"', "\"\"")),
+            Self::Blob(blob) => format!("base64:{}", BASE64.encode(blob)),
+        }
+    }
+
+    /// Formats the value as an SQL literal, quoting text and writing blobs
+    /// in hexadecimal, so that it can be inlined within a query
+    pub fn to_sql_literal(&self) -> String {
+        match self {
+            Self::Null => "NULL".to_string(),
+            Self::Integer(int) => int.to_string(),
+            Self::Real(real) => real.to_string(),
+            Self::Text(text) => format!("'{}'", text.replace('\'', "''")),
+            Self::Blob(blob) => {
+                let hex: String = blob.iter().map(|byte| format!("{:02X}", byte)).collect();
+                format!("X'{}'", hex)
+            }
+        }
+    }
+
+    /// Parses the text entered for a column of the passed type, with the
+    /// error describing both the text and the type it should have been
+    pub fn parse_column(data_type: &ValueType, text: &str) -> Result<Value, ParseColumnError> {
+        let error = |source| ParseColumnError {
+            column_type: data_type.clone(),
+            input: text.to_string(),
+            source,
+        };
+        match data_type {
+            ValueType::Null => Ok(Value::Null),
+            ValueType::Integer => Self::parse_integer(text)
+                .map(Value::Integer)
+                .map_err(|err| error(err.into())),
+            ValueType::Real => text
+                .parse()
+                .map(Value::Real)
+                .map_err(|err: std::num::ParseFloatError| error(err.into())),
+            ValueType::Text => Ok(Value::Text(text.to_string())),
+            ValueType::Blob => Ok(Value::Blob(text.bytes().collect())),
+        }
+    }
+}
+
+impl std::fmt::Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let data = match self {
+            // The value is a `NULL` value.
+            Self::Null => "NULL".to_string(),
+            // The value is a signed integer.
+            Self::Integer(int) => int.to_string(),
+            // The value is a floating point number.
+            Self::Real(real) => real.to_string(),
+            // The value is a text string.
+            Self::Text(text) => text.clone(),
+            // The value is a blob of data
+            Self::Blob(blob) => {
+                if blob.is_empty() {
+                    "Empty Blob".to_string()
+                } else {
+                    // map blob to a single string of bytes
+                    blob.iter().fold("Blob data:\t".to_string(), |cur, item| {
+                        cur + item.to_string().as_str()
+                    })
+                }
+            }
+        };
+        write!(f, "{}", data)
+    }
+}
+
+// Used for taking implementation ownership of the Rusqlite Value in
+// so that code can be added as needed
+impl From<RsqValue> for Value {
+    fn from(value: RsqValue) -> Self {
+        match value {
+            RsqValue::Null => Self::Null,
+            RsqValue::Integer(int) => Self::Integer(int),
+            RsqValue::Real(real) => Self::Real(real),
+            RsqValue::Text(text) => Self::Text(text),
+            RsqValue::Blob(blob) => Self::Blob(blob),
+        }
+    }
+}
+
+// Converts back from our implemented Value type to Rusqlite's one
+impl From<Value> for RsqValue {
+    fn from(value: Value) -> Self {
+        match value {
+            Value::Null => Self::Null,
+            Value::Integer(int) => Self::Integer(int),
+            Value::Real(real) => Self::Real(real),
+            Value::Text(text) => Self::Text(text),
+            Value::Blob(blob) => Self::Blob(blob),
+        }
+    }
+}
+
+// Converts back from our implemented Value type to Rusqlite's one
+impl From<&Value> for RsqValue {
+    fn from(value: &Value) -> Self {
+        match value {
+            Value::Null => Self::Null,
+            Value::Integer(int) => Self::Integer(*int),
+            Value::Real(real) => Self::Real(*real),
+            Value::Text(text) => Self::Text(text.clone()),
+            Value::Blob(blob) => Self::Blob(blob.clone()),
+        }
+    }
+}
+
+/// Consuming conversion from Value to Cell, required for simple creation of
+/// Ratatui Rows from Vec<Value>
+impl From<Value> for Cell<'_> {
+    fn from(value: Value) -> Self {
+        Self::from(&value)
+    }
+}
+
+/// Consuming conversion from Value to Cell, required for simple creation of
+/// Ratatui Rows from Vec<Value> without consuming within creation
+impl From<&Value> for Cell<'_> {
+    fn from(value: &Value) -> Self {
+        Self::new(value.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_integer(text: &str) -> Result<Value, ParseColumnError> {
+        Value::parse_column(&ValueType::Integer, text)
+    }
+
+    #[test]
+    fn integers_parse_with_a_radix_prefix() {
+        assert_eq!(parse_integer("0xFF").unwrap(), Value::Integer(255));
+        assert_eq!(parse_integer("0b1010").unwrap(), Value::Integer(10));
+        assert_eq!(parse_integer("0o17").unwrap(), Value::Integer(15));
+        assert_eq!(parse_integer("-0x10").unwrap(), Value::Integer(-16));
+        assert_eq!(parse_integer("42").unwrap(), Value::Integer(42));
+    }
+
+    #[test]
+    fn integers_refuse_invalid_digits_and_second_signs() {
+        assert!(parse_integer("0b102").is_err());
+        assert!(parse_integer("0x-1").is_err());
+        assert!(parse_integer("0x").is_err());
+    }
+}
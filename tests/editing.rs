@@ -0,0 +1,105 @@
+//! Edits made through the components against a SQLite file on disk
+
+use std::{env, fs, path::PathBuf, sync::Arc};
+
+use cli::{
+    action::Action,
+    component::{
+        Component, add_component::AddComponent, database_component::DatabaseComp,
+        selected_table::TableMetadata, table_display::MultiTableSelection,
+    },
+    connection::Connection,
+};
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+const SCHEMA: &str =
+    "CREATE TABLE people (id INTEGER PRIMARY KEY, name TEXT NOT NULL, age INTEGER);
+    INSERT INTO people (name, age) VALUES ('ann', 31), ('bob', 42), ('cat', 27);";
+
+/// Creates a database file holding the people table, named after the test
+/// so tests running at once don't share it, and connects to it
+fn people_database(test: &str) -> (PathBuf, Arc<Connection>) {
+    let path = env::temp_dir().join(format!("cli-{}-{}.db", test, std::process::id()));
+    let _ = fs::remove_file(&path);
+    rusqlite::Connection::open(&path)
+        .unwrap()
+        .execute_batch(SCHEMA)
+        .unwrap();
+    let connection = Connection::new(&path, false).unwrap();
+    (path, Arc::new(connection))
+}
+
+/// Reads the name and age of each person straight from the file, by id
+fn stored_people(path: &PathBuf) -> Vec<(String, i64)> {
+    let connection = rusqlite::Connection::open(path).unwrap();
+    let mut stmt = connection
+        .prepare("SELECT name, age FROM people ORDER BY id;")
+        .unwrap();
+    stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+        .unwrap()
+        .map(Result::unwrap)
+        .collect()
+}
+
+/// Creates the component showing the people table, focusing the table
+fn people_component(connection: Arc<Connection>, uses_rows: bool) -> DatabaseComp {
+    let metadata = TableMetadata::from_connection(&connection, "people").unwrap();
+    let mut component = DatabaseComp::new(connection, "", 1, uses_rows).unwrap();
+    component.change_table_used(&metadata).unwrap();
+    component.focus_last();
+    component
+}
+
+fn key(code: KeyCode) -> KeyEvent {
+    KeyEvent::new(code, KeyModifiers::NONE)
+}
+
+#[test]
+fn adding_a_row_inserts_it_into_the_file() {
+    let (path, connection) = people_database("add");
+    let mut add_comp = AddComponent::new(connection, "people").unwrap();
+    // skip the id, then fill in the name and age
+    for text in ["dan", "19"] {
+        add_comp.handle_key_event(key(KeyCode::Right)).unwrap();
+        add_comp.handle_key_event(key(KeyCode::Enter)).unwrap();
+        for c in text.chars() {
+            add_comp.handle_key_event(key(KeyCode::Char(c))).unwrap();
+        }
+        add_comp.handle_key_event(key(KeyCode::Enter)).unwrap();
+    }
+    add_comp.handle_key_event(key(KeyCode::Down)).unwrap();
+    add_comp.handle_key_event(key(KeyCode::Enter)).unwrap();
+    // confirm the popup, whose first choice is yes
+    let actions = add_comp.handle_key_event(key(KeyCode::Enter)).unwrap();
+    assert!(actions.contains(&Action::SelectInsertedRow(4)));
+    assert_eq!(stored_people(&path)[3], ("dan".to_string(), 19));
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn modifying_a_cell_updates_the_file() {
+    let (path, connection) = people_database("modify");
+    let mut component = people_component(connection, false);
+    component.select(MultiTableSelection::Cell((1, 2)));
+    component.set_editor_text("43");
+    assert!(component.submit_modify().unwrap());
+    // accept the confirmation popup
+    component
+        .handle_event(Action::KeyEvent(key(KeyCode::Enter)))
+        .unwrap();
+    assert_eq!(stored_people(&path)[1], ("bob".to_string(), 43));
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn deleting_a_row_removes_it_from_the_file() {
+    let (path, connection) = people_database("delete");
+    let mut component = people_component(connection, true);
+    component.select(MultiTableSelection::Row(0));
+    assert_eq!(component.delete().unwrap(), 1);
+    assert_eq!(
+        stored_people(&path),
+        [("bob".to_string(), 42), ("cat".to_string(), 27)]
+    );
+    fs::remove_file(&path).unwrap();
+}